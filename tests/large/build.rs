@@ -0,0 +1,35 @@
+//! Generates the enum of the tests, cycling through unit variants and
+//! variants with one and two fields.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=VARIATION_LARGE_VARIANTS");
+
+    let count = env::var("VARIATION_LARGE_VARIANTS")
+        .ok()
+        .and_then(|count| count.parse::<usize>().ok())
+        .unwrap_or(600);
+    let mut variants = String::new();
+
+    for i in 0..count {
+        match i % 3 {
+            0 => writeln!(variants, "    Unit{},", i),
+            1 => writeln!(variants, "    Single{}(u32),", i),
+            _ => writeln!(variants, "    Pair{}(u32, String),", i),
+        }
+        .unwrap();
+    }
+
+    let source = format!(
+        "#[derive(Debug, PartialEq, Variation)]\n\
+         #[variation(kind, count_kinds, drain, visitor, markers)]\n\
+         pub enum Large {{\n{}}}\n",
+        variants
+    );
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("large.rs"), source).unwrap();
+}
@@ -0,0 +1,7 @@
+//! A generated enum with hundreds of variants, to see how long
+//! `#[derive(Variation)]` takes to expand on one with
+//! `cargo build -p large --tests --timings`. `VARIATION_LARGE_VARIANTS` sets
+//! the number of variants, 600 by default.
+//!
+//! The tests only check that the expansion compiles and works, they don't
+//! bound its time, which has to be compared by hand between builds.
@@ -0,0 +1,29 @@
+use variation::Variation;
+
+include!(concat!(env!("OUT_DIR"), "/large.rs"));
+
+#[test]
+fn large_accessors() {
+    let mut pair = Large::Pair2(1, "pair".to_owned());
+
+    assert!(Large::Unit0.is_unit0());
+    assert!(!Large::Unit0.is_single1());
+    assert_eq!(Some(&7), Large::Single1(7).as_single1());
+
+    if let Some((number, _)) = pair.as_pair2_mut() {
+        *number += 1;
+    }
+
+    assert_eq!((2, "pair".to_owned()), pair.into_pair2());
+    assert_eq!(Err(Large::Unit0), Large::Unit0.into_variant::<large_markers::Single1>());
+}
+
+#[test]
+fn large_whole_enum() {
+    let mut values = vec![Large::Single1(1), Large::Unit0, Large::Single1(2)];
+
+    assert_eq!(vec![1, 2], Large::drain_single1s(&mut values));
+    assert_eq!(vec![Large::Unit0], values);
+    assert_eq!("Single4", Large::Single4(1).variant_name());
+    assert_eq!(LargeKind::Unit3, Large::Unit3.kind());
+}
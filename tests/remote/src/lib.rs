@@ -0,0 +1,15 @@
+//! Enums without `#[derive(Variation)]` for the tests of `variation_remote!`.
+
+use std::io;
+
+#[derive(Debug)]
+pub enum Status {
+    Ready,
+    Failed(io::Error),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Tree<T> {
+    Leaf(T),
+    Node(Box<Tree<T>>, Box<Tree<T>>),
+}
@@ -0,0 +1,4 @@
+//! A facade re-exporting `variation-kind`, which this crate depends on under
+//! another name, for the tests of `#[variation(crate = "...")]`.
+
+pub use kind_support as kind;
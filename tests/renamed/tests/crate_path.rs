@@ -0,0 +1,32 @@
+use variation::Variation;
+
+#[derive(Variation)]
+#[variation(kind(variant_kind), crate = "kind_support")]
+enum Renamed {
+    Ready,
+    Busy(u8),
+}
+
+#[derive(Variation)]
+#[variation(kind(variant_kind), crate = "renamed::kind")]
+enum ReExported {
+    Ready,
+}
+
+#[test]
+fn renamed_dependency() {
+    use kind_support::VariantKind;
+
+    assert_eq!(2, Renamed::VARIANT_COUNT);
+    assert_eq!(RenamedKind::Busy, VariantKind::kind(&Renamed::Busy(1)));
+    assert_eq!("Ready", VariantKind::variant_name(&Renamed::Ready));
+    assert_eq!(Some(&1), Renamed::Busy(1).as_busy());
+}
+
+#[test]
+fn re_exported_crate() {
+    use renamed::kind::VariantKind;
+
+    assert_eq!(1, ReExported::VARIANT_COUNT);
+    assert_eq!(ReExportedKind::Ready, VariantKind::kind(&ReExported::Ready));
+}
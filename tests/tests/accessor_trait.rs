@@ -0,0 +1,72 @@
+use variation::Variation;
+
+mod values {
+    use variation::Variation;
+
+    #[derive(Debug, PartialEq, Variation)]
+    #[variation(trait = "ValueExt", trait_object_safe)]
+    pub enum Value {
+        Integer(i64),
+        Text(String),
+        Null,
+    }
+
+    #[derive(Debug, PartialEq, Variation)]
+    #[variation(trait = "NumberExt", trait_vis = "pub")]
+    pub(crate) enum Number {
+        Int(i64),
+        Float(f64),
+    }
+}
+
+use values::{Number, NumberExt, Value, ValueExt};
+
+fn describe(value: &dyn ValueExt) -> String {
+    match (value.as_integer(), value.as_text()) {
+        (Some(integer), _) => integer.to_string(),
+        (_, Some(text)) => text.clone(),
+        _ if value.is_null() => "null".to_owned(),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn trait_object() {
+    let mut text = Value::Text("a".to_owned());
+
+    if let Some(text) = (&mut text as &mut dyn ValueExt).as_text_mut() {
+        text.push('b');
+    }
+
+    assert_eq!("1", describe(&Value::Integer(1)));
+    assert_eq!("ab", describe(&text));
+    assert_eq!("null", describe(&Value::Null));
+}
+
+#[test]
+fn trait_consuming() {
+    assert_eq!(1, Number::Int(1).into_int());
+    assert_eq!(Some(&1.5), Number::Float(1.5).as_float());
+    assert!(Number::Int(2).is_int());
+}
+
+#[derive(Debug, Variation)]
+#[variation(trait = "ShapeExt", into_mode = "option")]
+enum Shape<T> {
+    Circle(T),
+    Rect(T, T),
+}
+
+fn area<S: ShapeExt<f64>>(shape: S) -> f64 {
+    match shape.into_rect() {
+        Some((w, h)) => w * h,
+        None => 0.0,
+    }
+}
+
+#[test]
+fn trait_generic() {
+    assert_eq!(6.0, area(Shape::Rect(2.0, 3.0)));
+    assert_eq!(0.0, area(Shape::Circle(1.0)));
+    assert_eq!(Some(&1), Shape::Circle(1).as_circle());
+}
@@ -0,0 +1,54 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+enum Shape {
+    #[variation(alias = "square", alias = "quad")]
+    Rectangle(u32, u32),
+    #[variation(alias = "dot")]
+    Point,
+}
+
+#[test]
+#[allow(deprecated)]
+fn alias_forwards() {
+    let mut shape = Shape::Rectangle(2, 3);
+
+    assert!(shape.is_square());
+    assert!(shape.is_quad());
+    assert!(!shape.is_dot());
+    assert!(Shape::Point.is_dot());
+    assert_eq!(Some((&2, &3)), shape.as_square());
+
+    if let Some((width, _)) = shape.as_quad_mut() {
+        *width = 4;
+    }
+
+    assert_eq!(shape.as_rectangle(), shape.as_quad());
+    assert_eq!((4, 3), shape.into_square());
+}
+
+mod traits {
+    use variation::Variation;
+
+    #[derive(Debug, PartialEq, Variation)]
+    #[variation(trait = "ShapeExt", into_mode = "option")]
+    pub enum Shape {
+        #[variation(alias = "square")]
+        Rectangle(u32, u32),
+        Point,
+    }
+}
+
+#[allow(deprecated)]
+fn square<T: traits::ShapeExt>(shape: T) -> Option<(u32, u32)> {
+    assert_eq!(shape.is_rectangle(), shape.is_square());
+    shape.into_square()
+}
+
+#[test]
+fn alias_in_trait() {
+    use traits::Shape;
+
+    assert_eq!(Some((2, 3)), square(Shape::Rectangle(2, 3)));
+    assert_eq!(None, square(Shape::Point));
+}
@@ -0,0 +1,50 @@
+use variation::Variation;
+
+#[derive(Variation)]
+enum Wrapper {
+    #[variation(as_ref)]
+    Value(Vec<u8>),
+}
+
+#[test]
+fn as_ref_field_type() {
+    let mut wrapper = Wrapper::Value(vec![1, 2]);
+
+    AsMut::<Vec<u8>>::as_mut(&mut wrapper).push(3);
+
+    assert_eq!(&vec![1, 2, 3], AsRef::<Vec<u8>>::as_ref(&wrapper));
+    assert!(wrapper.is_value());
+}
+
+#[derive(Variation)]
+enum Name {
+    #[variation(as_ref(str))]
+    Owned(String),
+}
+
+fn length<T: AsRef<str>>(value: T) -> usize {
+    value.as_ref().len()
+}
+
+#[test]
+fn as_ref_forwarded_target() {
+    let mut name = Name::Owned(String::from("variation"));
+
+    name.as_mut().make_ascii_uppercase();
+
+    assert_eq!("VARIATION", AsRef::<str>::as_ref(&name));
+    assert_eq!(9, length(name));
+}
+
+#[derive(Variation)]
+enum Generic<T> {
+    #[variation(as_ref)]
+    Value(T),
+}
+
+#[test]
+fn as_ref_generic() {
+    let value = Generic::Value(5);
+
+    assert_eq!(&5, value.as_ref());
+}
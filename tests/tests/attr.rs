@@ -0,0 +1,79 @@
+//! The attributes of `#[variation(attr(...))]` end up on the generated code,
+//! which is checked by attributes that change what compiles.
+#![deny(missing_docs)]
+
+use variation::Variation;
+
+/// An enum whose accessors are documented through `attr(...)` alone, which
+/// `missing_docs` checks.
+#[derive(Debug, PartialEq, Variation)]
+#[variation(attr(
+    is = "doc = \"Checks the variant.\"",
+    as = "doc = \"Borrows the inner values.\"",
+    as_mut = "doc = \"Borrows the inner values mutably.\"",
+    into = "doc = \"Moves the inner values out.\""
+))]
+pub enum Value {
+    /// An integer.
+    #[variation(alias = "int")]
+    Integer(i64),
+    /// A value that is only ever checked.
+    #[variation(attr(cfg(any())))]
+    Hidden(u8),
+}
+
+// The accessors of `Hidden` are compiled out by its `attr(cfg(any()))`, so
+// these don't clash with them.
+impl Value {
+    /// Always true, unlike the generated method.
+    pub fn is_hidden(&self) -> bool {
+        true
+    }
+}
+
+/// An enum whose generated code is compiled out entirely.
+#[derive(Debug, Variation)]
+#[variation(attr(cfg(any())), kind)]
+pub enum Compiled {
+    /// A variant.
+    Out(u8),
+}
+
+impl Compiled {
+    /// Always true, unlike the generated method.
+    pub fn is_out(&self) -> bool {
+        true
+    }
+}
+
+/// The kind of `Compiled`, which isn't generated.
+pub enum CompiledKind {}
+
+#[test]
+#[allow(deprecated)]
+fn attr_families() {
+    let mut value = Value::Integer(1);
+
+    if let Some(integer) = value.as_integer_mut() {
+        *integer += 1;
+    }
+
+    assert!(value.is_integer());
+    assert!(value.is_int());
+    assert_eq!(Some(&2), value.as_integer());
+    assert_eq!(2, value.into_integer());
+}
+
+#[test]
+fn attr_variant() {
+    assert!(Value::Integer(1).is_hidden());
+    assert!(Value::Hidden(1) != Value::Integer(1));
+}
+
+#[test]
+fn attr_container() {
+    let out = Compiled::Out(1);
+
+    assert!(out.is_out());
+    assert!(std::mem::size_of::<CompiledKind>() == 0);
+}
@@ -0,0 +1,68 @@
+use variation::augment;
+
+macro_rules! define_enum {
+    ($vis:vis enum $name:ident { $($variant:ident $(($($ty:ty),*))?),* $(,)? }) => {
+        #[derive(Debug, PartialEq)]
+        $vis enum $name {
+            $($variant $(($($ty),*))?),*
+        }
+    };
+}
+
+mod generated {
+    define_enum! {
+        pub enum Token {
+            Number(u32),
+            Word(String),
+            End,
+        }
+    }
+}
+
+augment! {
+    #[variation(into_mode = "option")]
+    enum generated::Token {
+        Number(u32),
+        Word(String),
+        End,
+    }
+}
+
+use generated::Token;
+
+#[test]
+fn augmented_methods() {
+    let mut word = Token::Word("a".to_owned());
+
+    if let Some(word) = word.as_word_mut() {
+        word.push('b');
+    }
+
+    assert!(Token::End.is_end());
+    assert_eq!(Some(&1), Token::Number(1).as_number());
+    assert_eq!(Some("ab".to_owned()), word.into_word());
+    assert_eq!(None, Token::End.into_number());
+    assert_eq!("End", Token::End.variant_name());
+}
+
+define_enum! {
+    enum Op {
+        Add,
+        Neg(i64),
+    }
+}
+
+augment! {
+    #[variation(kind)]
+    enum Op {
+        Add,
+        Neg(i64),
+    }
+}
+
+#[test]
+fn augmented_in_scope() {
+    assert_eq!(OpKind::Neg, Op::Neg(1).kind());
+    assert_eq!(Some(&1), Op::Neg(1).as_neg());
+    assert!(Op::Add.is_add());
+}
@@ -27,6 +27,8 @@ fn as_implementation() {
     assert!(Type::Bool(true).as_bool().unwrap());
     assert_eq!(Some(&5), Type::Number(5).as_number());
     assert_eq!(None, Type::String(String::new()).as_number());
+    assert_eq!(Some(&String::new()), Type::String(String::new()).as_string());
+    assert_eq!(Some((&1, &2)), Foo::Point(1, 2).as_point());
     assert_eq!(Some((&String::new(), &true)), Type::Tuple(String::new(), true).as_tuple());
 }
 
@@ -51,3 +53,25 @@ fn into_implementation() {
     assert_eq!(5, num.into_number());
 
 }
+
+// Implements no traits, so `is_*` can't rely on any.
+struct Opaque;
+
+// Stored in the niche of the `String`, without a tag of its own.
+#[derive(Variation)]
+enum Niche {
+    Empty,
+    Named(String),
+    Opaque(Opaque),
+}
+
+#[test]
+fn is_niche_implementation() {
+    let values = [Niche::Empty, Niche::Named(String::from("a")), Niche::Opaque(Opaque)];
+
+    assert_eq!(
+        vec![(true, false, false), (false, true, false), (false, false, true)],
+        values.iter().map(|value| (value.is_empty(), value.is_named(), value.is_opaque())).collect::<Vec<_>>()
+    );
+    assert_eq!(Some(&String::from("a")), values[1].as_named());
+}
@@ -0,0 +1,60 @@
+use std::borrow::{Borrow, BorrowMut};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use variation::Variation;
+
+#[derive(Debug, Variation)]
+enum Key {
+    #[variation(borrow(str))]
+    Name(String),
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Key) -> bool {
+        Borrow::<str>::borrow(self) == Borrow::<str>::borrow(other)
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Borrow::<str>::borrow(self).hash(state)
+    }
+}
+
+#[test]
+fn borrow_hash_map_lookup() {
+    let mut map = HashMap::new();
+    map.insert(Key::Name(String::from("one")), 1);
+    map.insert(Key::Name(String::from("two")), 2);
+
+    assert_eq!(Some(&1), map.get("one"));
+    assert_eq!(Some(&2), map.get("two"));
+    assert_eq!(None, map.get("three"));
+}
+
+#[test]
+fn borrow_mut_forwarded_target() {
+    let mut key = Key::Name(String::from("key"));
+
+    BorrowMut::<str>::borrow_mut(&mut key).make_ascii_uppercase();
+
+    assert_eq!(Key::Name(String::from("KEY")), key);
+    assert!(key.is_name());
+}
+
+#[derive(Variation)]
+enum Counter {
+    #[variation(borrow)]
+    Count(u64),
+}
+
+#[test]
+fn borrow_field_type() {
+    let mut counter = Counter::Count(1);
+
+    *BorrowMut::<u64>::borrow_mut(&mut counter) += 1;
+
+    assert_eq!(&2, Borrow::<u64>::borrow(&counter));
+}
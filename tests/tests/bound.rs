@@ -0,0 +1,37 @@
+use std::rc::Rc;
+use variation::Variation;
+
+#[derive(Variation)]
+#[variation(bound = "T: Clone + std::fmt::Debug")]
+enum Slot<T> {
+    Full(T),
+    Empty,
+}
+
+#[test]
+fn impl_bound() {
+    let slot = Slot::Full(vec![1]);
+
+    assert_eq!(Some(&vec![1]), slot.as_full());
+    assert!(Slot::<u8>::Empty.is_empty());
+}
+
+#[derive(Variation)]
+#[variation(bound(into = "T: Send", as_mut = "T: Send"))]
+enum Shared<T> {
+    Value(T),
+}
+
+#[test]
+fn method_bound() {
+    // `Rc` isn't `Send`, but only `into_*` and `as_*_mut` require it.
+    let rc = Shared::Value(Rc::new(1));
+
+    assert!(rc.is_value());
+    assert_eq!(Some(&Rc::new(1)), rc.as_value());
+
+    let mut arc = Shared::Value(std::sync::Arc::new(1));
+
+    assert!(arc.as_value_mut().is_some());
+    assert_eq!(1, *arc.into_value());
+}
@@ -0,0 +1,37 @@
+use variation::Variation;
+
+// Large on purpose, as the values are meant to be kept boxed.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Variation)]
+#[variation(boxed)]
+enum Node {
+    Leaf(i32),
+    Branch([u8; 512], Vec<Node>),
+    #[variation(into = "take_count")]
+    Count(u32),
+    Empty,
+}
+
+#[test]
+fn into_boxed() {
+    let leaf = Box::new(Node::Leaf(5));
+    let branch = Box::new(Node::Branch([1; 512], vec![Node::Empty]));
+
+    assert_eq!(5, leaf.into_leaf_boxed());
+
+    let (bytes, children) = branch.into_branch_boxed();
+    assert_eq!([1; 512], bytes);
+    assert!(children[0].is_empty());
+}
+
+#[test]
+fn into_boxed_renamed() {
+    assert_eq!(3, Box::new(Node::Count(3)).take_count_boxed());
+    assert_eq!(4, Node::Count(4).take_count());
+}
+
+#[test]
+#[should_panic]
+fn into_boxed_wrong_variant() {
+    Box::new(Node::Empty).into_leaf_boxed();
+}
@@ -0,0 +1,48 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(cfg(feature = "introspection"), kind, count_kinds)]
+enum Event {
+    Click(u32, u32),
+    Key(char),
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(cfg(is = "feature = \"introspection\"", into = "not(feature = \"introspection\")"))]
+enum Token {
+    #[variation(alias = "digit")]
+    Number(u8),
+    Word(String),
+}
+
+#[cfg(feature = "introspection")]
+#[test]
+fn cfg_enabled() {
+    let events = [Event::Click(1, 2), Event::Key('a')];
+
+    assert!(events[0].is_click());
+    assert_eq!(Some(&'a'), events[1].as_key());
+    assert_eq!(EventKind::Key, events[1].kind());
+    assert_eq!(1, EventKindCounts::from_iter(&events).click());
+    assert!(Token::Number(1).is_number());
+}
+
+#[cfg(not(feature = "introspection"))]
+#[test]
+#[allow(deprecated)]
+fn cfg_disabled() {
+    assert_ne!(Event::Click(1, 2), Event::Key('a'));
+    assert_eq!(1, Token::Number(1).into_number());
+    assert_eq!(2, Token::Number(2).into_digit());
+}
+
+#[test]
+fn cfg_families() {
+    let mut word = Token::Word("a".to_owned());
+
+    if let Some(word) = word.as_word_mut() {
+        word.push('b');
+    }
+
+    assert_eq!(Some(&"ab".to_owned()), word.as_word());
+}
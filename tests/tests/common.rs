@@ -0,0 +1,75 @@
+use variation::Variation;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Span(usize, usize);
+
+#[derive(Debug, Variation)]
+enum Expr {
+    Literal(#[variation(common = "span")] Span, i64),
+    Neg(#[variation(common = "span")] Span, Box<Expr>),
+    Add(Box<Expr>, #[variation(common = "span")] Span, Box<Expr>),
+}
+
+#[test]
+fn common_accessor() {
+    let literal = Expr::Literal(Span(0, 1), 1);
+    let add = Expr::Add(Box::new(Expr::Literal(Span(0, 1), 1)), Span(0, 5), Box::new(Expr::Literal(Span(4, 5), 2)));
+
+    assert_eq!(&Span(0, 1), literal.span());
+    assert_eq!(&Span(0, 5), add.span());
+    assert_eq!(Some(&1), literal.as_literal().map(|(_, value)| value));
+    assert_eq!(Some(&Span(4, 5)), add.as_add().map(|(_, _, right)| right.span()));
+    assert!(add.as_add().is_some_and(|(left, _, _)| left.is_literal()));
+}
+
+#[test]
+fn common_accessor_mut() {
+    let mut neg = Expr::Neg(Span(0, 2), Box::new(Expr::Literal(Span(1, 2), 1)));
+
+    neg.span_mut().1 = 3;
+
+    assert_eq!(&Span(0, 3), neg.span());
+    assert_eq!(Some(&Span(1, 2)), neg.as_neg().map(|(_, inner)| inner.span()));
+}
+
+#[derive(Variation)]
+enum Event {
+    Click(#[variation(common = "id")] u32, #[variation(common = "time")] u64),
+    Key(#[variation(common = "time")] u64, char, #[variation(common = "id")] u32),
+}
+
+#[test]
+fn several_common_accessors() {
+    let mut key = Event::Key(20, 'a', 2);
+
+    *key.time_mut() += 1;
+
+    assert_eq!(&2, key.id());
+    assert_eq!(&21, key.time());
+    assert_eq!(&1, Event::Click(1, 10).id());
+    assert_eq!(Some(&'a'), key.as_key().map(|(_, key, _)| key));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Type {
+    Int,
+    Float,
+}
+
+#[derive(Variation)]
+enum Value {
+    Int(#[variation(common = "type")] Type, i64),
+    Float(f64, #[variation(common = "type")] Type),
+}
+
+#[test]
+fn keyword_common_accessor() {
+    let mut value = Value::Int(Type::Int, 1);
+
+    *value.type_mut() = Type::Float;
+
+    assert_eq!(&Type::Float, value.r#type());
+    assert_eq!(&Type::Float, Value::Float(1.0, Type::Float).r#type());
+    assert_eq!(Some(&1), value.as_int().map(|(_, value)| value));
+    assert_eq!(Some(&1.0), Value::Float(1.0, Type::Float).as_float().map(|(value, _)| value));
+}
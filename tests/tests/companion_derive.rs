@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use variation::Variation;
+
+#[derive(Debug, Clone, PartialEq, Variation)]
+#[variation(partition, count_kinds, markers, companion_derive(Clone, PartialEq, Debug, Hash))]
+enum Type {
+    Unit,
+    Integer(i64),
+    #[variation(companion_derive(PartialEq, Eq, std::hash::Hash))]
+    Text(String),
+}
+
+fn values() -> Vec<Type> {
+    vec![Type::Integer(1), Type::Unit, Type::Text("a".to_owned()), Type::Integer(2)]
+}
+
+#[test]
+fn companion_derive_partition() {
+    let partition = Type::partition_variants(values());
+
+    assert_eq!(partition.clone(), partition);
+    assert_eq!(vec![1, 2], partition.integer);
+}
+
+#[test]
+fn companion_derive_counts() {
+    let counts = TypeKindCounts::from_iter(&values());
+    let set = vec![counts, TypeKindCounts::from_iter(&values())].into_iter().collect::<HashSet<_>>();
+
+    assert_eq!(1, set.len());
+}
+
+#[test]
+fn companion_derive_markers() {
+    fn hashable<T: Hash>(_: T) {}
+
+    hashable(type_markers::Integer);
+    assert_eq!(type_markers::Integer, type_markers::Integer.clone());
+    assert!(Some(type_markers::Text).into_iter().collect::<HashSet<_>>().contains(&type_markers::Text));
+}
+
+#[cfg(feature = "serde")]
+mod serde_derive {
+    use serde::Serialize;
+    use variation::Variation;
+
+    #[derive(Serialize, Variation)]
+    #[variation(partition, markers, companion_derive(Serialize))]
+    enum Shape {
+        Circle(u32),
+        #[variation(companion_derive(serde::Serialize, Default))]
+        Square(u32),
+    }
+
+    #[test]
+    fn companion_derive_serde() {
+        let partition = Shape::partition_variants(vec![Shape::Circle(1), Shape::Square(2), Shape::Circle(3)]);
+
+        assert_eq!(r#"{"circle":[1,3],"square":[2]}"#, serde_json::to_string(&partition).unwrap());
+        assert_eq!("null", serde_json::to_string(&shape_markers::Circle).unwrap());
+
+        let square: shape_markers::Square = Default::default();
+        assert_eq!("null", serde_json::to_string(&square).unwrap());
+    }
+}
@@ -0,0 +1,59 @@
+use variation::Variation;
+
+// The signatures `enum-as-inner` generates for the same enum.
+#[derive(Debug, PartialEq, Variation)]
+#[variation(compat = "enum_as_inner")]
+enum AsInner {
+    Integer(i64),
+    Pair(u8, String),
+    Null,
+}
+
+#[test]
+fn enum_as_inner_signatures() {
+    let _: fn(&AsInner) -> bool = AsInner::is_integer;
+    let _: fn(&AsInner) -> bool = AsInner::is_null;
+    let _: fn(&AsInner) -> Option<&i64> = AsInner::as_integer;
+    let _: fn(&mut AsInner) -> Option<&mut i64> = AsInner::as_integer_mut;
+    let _: fn(AsInner) -> Result<i64, AsInner> = AsInner::into_integer;
+    let _: fn(&AsInner) -> Option<(&u8, &String)> = AsInner::as_pair;
+    let _: fn(AsInner) -> Result<(u8, String), AsInner> = AsInner::into_pair;
+}
+
+#[test]
+fn enum_as_inner_behavior() {
+    assert_eq!(Ok(1), AsInner::Integer(1).into_integer());
+    assert_eq!(Err(AsInner::Null), AsInner::Null.into_integer());
+    assert_eq!(Ok((1, "a".to_owned())), AsInner::Pair(1, "a".to_owned()).into_pair());
+}
+
+// The signatures of `derive_more`'s `IsVariant` and `Unwrap` for the same
+// enum.
+#[derive(Debug, PartialEq, Variation)]
+#[variation(compat = "derive_more")]
+enum Unwrap {
+    Integer(i64),
+    Pair(u8, String),
+    Null,
+}
+
+#[test]
+fn derive_more_signatures() {
+    let _: fn(&Unwrap) -> bool = Unwrap::is_integer;
+    let _: fn(&Unwrap) -> bool = Unwrap::is_null;
+    let _: fn(Unwrap) -> i64 = Unwrap::unwrap_integer;
+    let _: fn(Unwrap) -> (u8, String) = Unwrap::unwrap_pair;
+}
+
+#[test]
+fn derive_more_behavior() {
+    assert_eq!(1, Unwrap::Integer(1).unwrap_integer());
+    assert_eq!((1, "a".to_owned()), Unwrap::Pair(1, "a".to_owned()).unwrap_pair());
+    assert!(Unwrap::Null.is_null());
+}
+
+#[test]
+#[should_panic]
+fn derive_more_wrong_variant() {
+    Unwrap::Null.unwrap_integer();
+}
@@ -0,0 +1,35 @@
+use variation::Variation;
+
+#[derive(Debug, Variation)]
+#[variation(const_fn)]
+enum Limit<T> {
+    #[variation(alias = "cap")]
+    Max(T),
+    Range(T, T),
+    None,
+}
+
+const LIMIT: Limit<u32> = Limit::Range(1, 10);
+
+const _: () = assert!(LIMIT.is_range());
+const _: () = assert!(!LIMIT.is_none());
+
+#[allow(deprecated)]
+const _: () = assert!(Limit::Max(3u32).is_cap());
+
+#[test]
+fn const_is() {
+    const KINDS: [bool; 3] = [LIMIT.is_max(), LIMIT.is_range(), LIMIT.is_none()];
+
+    assert_eq!([false, true, false], KINDS);
+    assert!(Limit::<u8>::None.is_none());
+}
+
+#[test]
+fn const_as() {
+    const BOUNDS: Option<(&u32, &u32)> = LIMIT.as_range();
+    const NAME: &str = LIMIT.variant_name();
+
+    assert_eq!(Some((&1, &10)), BOUNDS);
+    assert_eq!("Range", NAME);
+}
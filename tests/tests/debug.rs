@@ -0,0 +1,36 @@
+use variation::Variation;
+
+/// Doesn't implement `Debug`.
+struct Password(String);
+
+#[derive(Variation)]
+#[variation(debug = "redacted")]
+enum Credentials {
+    Anonymous,
+    Password(String, Password),
+    Token(String),
+}
+
+#[test]
+fn redacted_debug() {
+    let password = Credentials::Password(String::from("user"), Password(String::from("hunter2")));
+    let token = Credentials::Token(String::from("secret"));
+
+    assert_eq!("Credentials::Anonymous", format!("{:?}", Credentials::Anonymous));
+    assert_eq!("Credentials::Password(..)", format!("{:?}", password));
+    assert_eq!("Credentials::Token(..)", format!("{:#?}", token));
+    assert_eq!(Some(&String::from("secret")), token.as_token());
+    assert_eq!(Some("hunter2"), password.as_password().map(|(_, password)| &*password.0));
+}
+
+#[derive(Variation)]
+#[variation(debug = "redacted")]
+enum Generic<T> {
+    Value(T),
+}
+
+#[test]
+fn redacted_debug_generic() {
+    assert_eq!("Generic::Value(..)", format!("{:?}", Generic::Value(Password(String::new()))));
+    assert_eq!(Some(&1), Generic::Value(1).as_value());
+}
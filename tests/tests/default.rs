@@ -0,0 +1,40 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+enum State {
+    #[variation(default)]
+    Idle,
+    Running(u32),
+}
+
+#[test]
+fn unit_default() {
+    assert_eq!(State::Idle, State::default());
+    assert_eq!(Some(&3), State::Running(3).as_running());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+enum Buffer {
+    Closed,
+    #[variation(default)]
+    Open(Vec<u8>, usize),
+}
+
+#[test]
+fn data_default() {
+    assert_eq!(Buffer::Open(Vec::new(), 0), Buffer::default());
+    assert!(Buffer::Closed.is_closed());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+enum Wrapper<T> {
+    #[variation(default)]
+    Value(T),
+    Missing,
+}
+
+#[test]
+fn generic_default() {
+    assert_eq!(Wrapper::Value(String::new()), Wrapper::default());
+    assert!(Wrapper::<i32>::Missing.is_missing());
+}
@@ -0,0 +1,268 @@
+use std::future::{self, Future, Pending, Ready};
+use std::io::{Cursor, IoSlice, IoSliceMut, Read, Write};
+use std::iter::{self, Once};
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use variation::Variation;
+
+#[derive(Variation)]
+#[variation(delegate(Iterator<Item = u32>, DoubleEndedIterator, ExactSizeIterator))]
+enum Numbers {
+    Single(Once<u32>),
+    Range(Range<u32>),
+}
+
+#[test]
+fn delegate_iterator() {
+    assert_eq!(vec![7], Numbers::Single(iter::once(7)).collect::<Vec<_>>());
+    assert_eq!(vec![1, 2, 3], Numbers::Range(1..4).collect::<Vec<_>>());
+    assert_eq!((3, Some(3)), Numbers::Range(1..4).size_hint());
+    assert_eq!((1, Some(1)), Numbers::Single(iter::once(7)).size_hint());
+}
+
+#[test]
+fn delegate_double_ended_iterator() {
+    let mut numbers = Numbers::Range(1..5);
+
+    assert_eq!(Some(4), numbers.next_back());
+    assert_eq!(Some(1), numbers.next());
+    assert_eq!(vec![3, 2], numbers.rev().collect::<Vec<_>>());
+    assert_eq!(Some(7), Numbers::Single(iter::once(7)).next_back());
+}
+
+#[test]
+fn delegate_exact_size_iterator() {
+    let mut numbers = Numbers::Range(0..10);
+    numbers.next();
+
+    assert_eq!(9, numbers.len());
+    assert_eq!(1, Numbers::Single(iter::once(7)).len());
+    assert!(Numbers::Single(iter::once(7)).is_single());
+}
+
+#[derive(Variation)]
+#[variation(delegate(Iterator<Item = u32>, DoubleEndedIterator))]
+enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+#[test]
+fn delegate_generic() {
+    let left: Either<_, Range<u32>> = Either::Left(vec![1, 2].into_iter());
+    let right: Either<std::vec::IntoIter<u32>, _> = Either::Right(3..5);
+
+    assert_eq!(vec![2, 1], left.rev().collect::<Vec<_>>());
+    assert_eq!(vec![3, 4], right.collect::<Vec<_>>());
+    assert!(Either::<u8, u8>::Right(0).is_right());
+}
+
+#[derive(Variation)]
+#[variation(delegate(Read, Write))]
+enum Stream {
+    Memory(Cursor<Vec<u8>>),
+    Fixed(Cursor<Box<[u8]>>),
+}
+
+#[test]
+fn delegate_read() {
+    let mut memory = Stream::Memory(Cursor::new(b"owned".to_vec()));
+    let mut fixed = Stream::Fixed(Cursor::new(b"borrowed".to_vec().into_boxed_slice()));
+    let mut read = String::new();
+
+    memory.read_to_string(&mut read).unwrap();
+    assert_eq!("owned", read);
+
+    let mut first = [0; 3];
+    let mut second = [0; 5];
+    let count = fixed
+        .read_vectored(&mut [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)])
+        .unwrap();
+
+    assert_eq!(8, count);
+    assert_eq!(b"bor", &first);
+    assert_eq!(b"rowed", &second);
+}
+
+#[test]
+fn delegate_write() {
+    let mut stream = Stream::Memory(Cursor::new(Vec::new()));
+
+    stream.write_all(b"hello").unwrap();
+    assert_eq!(6, stream.write_vectored(&[IoSlice::new(b", "), IoSlice::new(b"word")]).unwrap());
+    stream.flush().unwrap();
+
+    assert_eq!(Some(&b"hello, word"[..]), stream.as_memory().map(|cursor| &cursor.get_ref()[..]));
+    let mut fixed = Stream::Fixed(Cursor::new(vec![0; 2].into_boxed_slice()));
+    assert!(fixed.write_all(b"full").is_err());
+    assert!(fixed.is_fixed());
+}
+
+#[derive(Variation)]
+#[variation(delegate(Future<Output = u32>))]
+enum Task<A, B> {
+    Ready(A),
+    Async(B),
+}
+
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    let mut polls = 1;
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return (output, polls),
+            Poll::Pending => polls += 1,
+        }
+    }
+}
+
+#[test]
+fn delegate_future() {
+    let ready: Task<_, Ready<u32>> = Task::Ready(future::ready(1));
+    // Async blocks aren't `Unpin`, so this has to poll through the pin.
+    let pending = Task::<Ready<u32>, _>::Async(async {
+        YieldOnce(false).await;
+        2
+    });
+
+    assert_eq!((1, 1), block_on(ready));
+    assert_eq!((2, 2), block_on(pending));
+    assert!(Task::<u8, u8>::Async(0).is_async());
+}
+
+#[test]
+fn delegate_future_unpin() {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    // Both futures are `Unpin`, so the enum is too.
+    let mut ready: Task<_, Pending<u32>> = Task::Ready(future::ready(1));
+    let mut pending = Task::<Ready<u32>, _>::Async(future::pending());
+
+    assert_eq!(Poll::Ready(1), Pin::new(&mut ready).poll(&mut cx));
+    assert_eq!(Poll::Pending, Pin::new(&mut pending).poll(&mut cx));
+}
+
+#[derive(Variation)]
+#[variation(delegate(Future<Output = u32>), pin_project)]
+enum Projected<F> {
+    Running(#[variation(pin)] F),
+}
+
+#[test]
+fn delegate_future_pin_project() {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    let mut projected = Projected::Running(future::pending::<u32>());
+
+    match Pin::new(&mut projected).project() {
+        ProjectedProj::Running(future) => assert_eq!(Poll::Pending, future.poll(&mut cx)),
+    }
+
+    assert_eq!((3, 1), block_on(Projected::Running(future::ready(3))));
+}
+
+#[derive(Variation)]
+#[variation(delegate(Display))]
+enum Id {
+    Name(String),
+    Numeric(u64),
+}
+
+#[test]
+fn delegate_display() {
+    assert_eq!("root", Id::Name("root".to_owned()).to_string());
+    assert_eq!("42", Id::Numeric(42).to_string());
+    assert_eq!("  42", format!("{:>4}", Id::Numeric(42)));
+    assert!(Id::Numeric(0).is_numeric());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Span(usize, usize);
+
+struct Ident {
+    span: Span,
+    name: String,
+}
+
+impl Ident {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn renamed(self, prefix: &str, index: usize) -> String {
+        format!("{}{}_{}", prefix, self.name, index)
+    }
+}
+
+struct Literal {
+    span: Span,
+    value: i64,
+}
+
+impl Literal {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn renamed(self, prefix: &str, _: usize) -> String {
+        format!("{}{}", prefix, self.value)
+    }
+}
+
+#[derive(Variation)]
+#[variation(delegate_fn(fn span(&self) -> Span))]
+#[variation(delegate_fn(
+    /// Names the node after its inner value.
+    pub fn renamed(self, prefix: &str, index: usize) -> String
+))]
+enum Token {
+    Ident(Ident),
+    Literal(u8, #[variation(delegate_fn)] Literal),
+    #[variation(skip)]
+    Boxed {
+        #[variation(delegate_fn)]
+        inner: Box<Ident>,
+        #[allow(dead_code)]
+        depth: u8,
+    },
+}
+
+#[test]
+fn delegate_fn() {
+    let ident = || Ident { span: Span(0, 1), name: "a".to_owned() };
+    let literal = Token::Literal(0, Literal { span: Span(2, 3), value: 4 });
+
+    assert_eq!(Span(0, 1), Token::Ident(ident()).span());
+    assert_eq!(Span(2, 3), literal.span());
+    assert_eq!("x4", literal.renamed("x", 1));
+    assert_eq!("_a_2", Token::Ident(ident()).renamed("_", 2));
+    assert_eq!("a_3", Token::Boxed { inner: Box::new(ident()), depth: 0 }.renamed("", 3));
+    assert_eq!(Some(&0), Token::Literal(0, Literal { span: Span(0, 0), value: 0 }).as_literal().map(|(v0, _)| v0));
+}
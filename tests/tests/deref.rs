@@ -0,0 +1,55 @@
+use std::path::{Path, PathBuf};
+use variation::Variation;
+
+#[derive(Variation)]
+#[variation(deref)]
+enum Source {
+    File(PathBuf),
+    Override(PathBuf),
+    Default(PathBuf),
+}
+
+fn file_name(path: &Path) -> Option<&str> {
+    path.file_name().and_then(|name| name.to_str())
+}
+
+#[test]
+fn deref() {
+    let sources = [
+        Source::File(PathBuf::from("/etc/a.toml")),
+        Source::Override(PathBuf::from("b.toml")),
+        Source::Default(PathBuf::from("./c.toml")),
+    ];
+    let names = sources.iter().map(|source| file_name(source)).collect::<Vec<_>>();
+
+    assert_eq!(vec![Some("a.toml"), Some("b.toml"), Some("c.toml")], names);
+    assert!(sources[0].is_absolute());
+    assert!(sources[1].is_override());
+    assert!(sources[2].is_default());
+}
+
+#[test]
+fn deref_mut() {
+    let mut source = Source::Override(PathBuf::from("config"));
+
+    source.push("app.toml");
+    source.set_extension("json");
+
+    assert_eq!(Some(&PathBuf::from("config/app.json")), source.as_override());
+}
+
+#[derive(Variation)]
+#[variation(deref)]
+enum Name<T> {
+    Short(T),
+    Long(T),
+}
+
+#[test]
+fn deref_generic() {
+    let name = Name::Long(String::from("variation"));
+
+    assert_eq!(9, name.len());
+    assert_eq!("VARIATION", name.to_uppercase());
+    assert!(!Name::Short(String::from("v")).is_long());
+}
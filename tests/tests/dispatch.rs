@@ -0,0 +1,130 @@
+use variation::Variation;
+
+mod shapes {
+    #[variation::dispatchable]
+    pub trait Shape {
+        fn area(&self) -> u32;
+        fn grow(&mut self, by: u32) -> &mut u32;
+        fn into_label(self) -> String;
+
+        fn describe(&self, prefix: &str) -> String {
+            format!("{}{}", prefix, self.area())
+        }
+    }
+}
+
+use shapes::Shape;
+
+#[derive(Debug, PartialEq)]
+struct Square(u32);
+#[derive(Debug, PartialEq)]
+struct Rect(u32, u32);
+#[derive(Debug, PartialEq)]
+struct Line(u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+
+    fn grow(&mut self, by: u32) -> &mut u32 {
+        self.0 += by;
+        &mut self.0
+    }
+
+    fn into_label(self) -> String {
+        format!("square {}", self.0)
+    }
+}
+
+impl Shape for Rect {
+    fn area(&self) -> u32 {
+        self.0 * self.1
+    }
+
+    fn grow(&mut self, by: u32) -> &mut u32 {
+        self.1 += by;
+        &mut self.1
+    }
+
+    fn into_label(self) -> String {
+        format!("rect {}x{}", self.0, self.1)
+    }
+
+    fn describe(&self, prefix: &str) -> String {
+        format!("{}{}x{}", prefix, self.0, self.1)
+    }
+}
+
+impl Shape for Line {
+    fn area(&self) -> u32 {
+        0
+    }
+
+    fn grow(&mut self, by: u32) -> &mut u32 {
+        self.0 += by;
+        &mut self.0
+    }
+
+    fn into_label(self) -> String {
+        format!("line {}", self.0)
+    }
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(dispatch = "Shape")]
+enum AnyShape {
+    Square(Square),
+    Rect(Rect),
+    Line(Line),
+}
+
+#[test]
+fn dispatch_ref() {
+    assert_eq!(4, AnyShape::Square(Square(2)).area());
+    assert_eq!(6, AnyShape::Rect(Rect(2, 3)).area());
+    assert_eq!(0, AnyShape::Line(Line(5)).area());
+}
+
+#[test]
+fn dispatch_mut() {
+    let mut shape = AnyShape::Rect(Rect(2, 3));
+
+    *shape.grow(1) += 1;
+
+    assert_eq!(AnyShape::Rect(Rect(2, 5)), shape);
+    assert_eq!(Some(&Rect(2, 5)), shape.as_rect());
+}
+
+#[test]
+fn dispatch_owned() {
+    assert_eq!("square 2", AnyShape::Square(Square(2)).into_label());
+    assert_eq!("rect 2x3", AnyShape::Rect(Rect(2, 3)).into_label());
+    assert_eq!("line 5", AnyShape::Line(Line(5)).into_label());
+}
+
+#[test]
+fn dispatch_default_method() {
+    assert_eq!("area 4", AnyShape::Square(Square(2)).describe("area "));
+    assert_eq!("area 2x3", AnyShape::Rect(Rect(2, 3)).describe("area "));
+}
+
+#[derive(Variation)]
+#[variation(dispatch = "shapes::Shape")]
+enum Outline<T> {
+    Shape(T),
+    Fill(Rect),
+    #[variation(skip)]
+    Border { line: Line },
+}
+
+#[test]
+fn dispatch_generic() {
+    let mut outline = Outline::Shape(Square(3));
+
+    assert_eq!(9, outline.area());
+    assert_eq!(4, *outline.grow(1));
+    assert_eq!(6, Outline::<Square>::Fill(Rect(2, 3)).area());
+    assert_eq!("line 1", Outline::<Square>::Border { line: Line(1) }.into_label());
+    assert!(outline.is_shape());
+}
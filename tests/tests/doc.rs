@@ -0,0 +1,33 @@
+//! The generated methods of variants with `#[variation(doc = "...")]` are
+//! documented, which `missing_docs` checks.
+#![deny(missing_docs)]
+
+use variation::Variation;
+
+/// A literal of a small language.
+#[derive(Debug, PartialEq, Variation)]
+pub enum Literal {
+    /// An integer literal.
+    #[variation(doc = "Returns the parsed integer literal, if any.")]
+    Integer(i64),
+    /// A string literal.
+    #[variation(doc = "The **string** literal.\n\n```\nlet quoted = \"a {} b\";\n```", alias = "text")]
+    Str(String),
+}
+
+#[test]
+#[allow(deprecated)]
+fn doc_methods() {
+    let mut literal = Literal::Str("a".to_owned());
+
+    assert!(literal.is_str());
+    assert!(literal.is_text());
+    assert_eq!(None, literal.as_integer());
+
+    if let Some(string) = literal.as_str_mut() {
+        string.push('b');
+    }
+
+    assert_eq!("ab", literal.into_str());
+    assert_eq!(1, Literal::Integer(1).into_integer());
+}
@@ -0,0 +1,46 @@
+//! Hidden items are exempt from `missing_docs`, which checks that the
+//! generated methods without documentation of their own are hidden.
+#![deny(missing_docs)]
+
+use variation::Variation;
+
+/// An enum with all of its generated methods hidden.
+#[derive(Debug, PartialEq, Variation)]
+#[variation(doc_hidden, kind)]
+pub enum Internal {
+    /// A variant.
+    Integer(i64),
+    /// Another variant.
+    Null,
+}
+
+/// An enum with only some families hidden, the methods of `into` are
+/// documented.
+#[derive(Debug, PartialEq, Variation)]
+#[variation(doc_hidden(is, as, as_mut))]
+pub enum Partial {
+    /// A variant.
+    Integer(i64),
+    /// Another variant.
+    Text(String),
+}
+
+#[test]
+fn doc_hidden_usable() {
+    assert!(Internal::Null.is_null());
+    assert_eq!(Some(&1), Internal::Integer(1).as_integer());
+    assert_eq!(InternalKind::Null, Internal::Null.kind());
+}
+
+#[test]
+fn doc_hidden_families() {
+    let mut text = Partial::Text("a".to_owned());
+
+    if let Some(text) = text.as_text_mut() {
+        text.push('b');
+    }
+
+    assert!(Partial::Integer(1).is_integer());
+    assert_eq!(Some(&1), Partial::Integer(1).as_integer());
+    assert_eq!("ab", text.into_text());
+}
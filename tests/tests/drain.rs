@@ -0,0 +1,68 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(drain)]
+enum Event {
+    Click(u32, u32),
+    Key(char),
+    Entry(String),
+    Idle,
+}
+
+fn events() -> Vec<Event> {
+    vec![
+        Event::Key('a'),
+        Event::Click(1, 2),
+        Event::Idle,
+        Event::Key('b'),
+        Event::Entry(String::from("text")),
+        Event::Click(3, 4),
+    ]
+}
+
+#[test]
+fn drain() {
+    let mut events = events();
+
+    assert_eq!(vec!['a', 'b'], Event::drain_keys(&mut events));
+    assert_eq!(
+        vec![
+            Event::Click(1, 2),
+            Event::Idle,
+            Event::Entry(String::from("text")),
+            Event::Click(3, 4),
+        ],
+        events
+    );
+
+    assert_eq!(vec![(1, 2), (3, 4)], Event::drain_clicks(&mut events));
+    assert_eq!(vec![String::from("text")], Event::drain_entries(&mut events));
+    assert_eq!(vec![Event::Idle], events);
+    assert!(Event::drain_keys(&mut events).is_empty());
+    assert_eq!(vec![Event::Idle], events);
+}
+
+#[test]
+fn retain_kind() {
+    let mut events = events();
+    Event::retain_kind(&mut events, EventKind::Key);
+
+    assert_eq!(vec![Event::Key('a'), Event::Key('b')], events);
+
+    Event::retain_kind(&mut events, EventKind::Idle);
+    assert!(events.is_empty());
+}
+
+#[derive(Variation)]
+#[variation(drain)]
+enum Single {
+    Value(i32),
+}
+
+#[test]
+fn drain_single_variant() {
+    let mut values = vec![Single::Value(1), Single::Value(2)];
+
+    assert_eq!(vec![1, 2], Single::drain_values(&mut values));
+    assert!(values.is_empty());
+}
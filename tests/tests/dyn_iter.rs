@@ -0,0 +1,82 @@
+use std::fmt::Debug;
+
+use variation::Variation;
+
+trait Node: Debug {
+    fn label(&self) -> String;
+}
+
+#[derive(Debug, PartialEq)]
+struct Name(&'static str);
+
+impl Node for Name {
+    fn label(&self) -> String {
+        self.0.to_owned()
+    }
+}
+
+impl Node for Box<Expr> {
+    fn label(&self) -> String {
+        let children = self.children().map(|child| child.label()).collect::<Vec<_>>();
+        format!("({})", children.join(" "))
+    }
+}
+
+impl Node for Vec<Name> {
+    fn label(&self) -> String {
+        format!("[{}]", self.len())
+    }
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(dyn_iter = "Node")]
+enum Expr {
+    Unit,
+    Var(Name),
+    Call(Box<Expr>, #[variation(no_dyn_iter)] u32, Vec<Name>),
+    #[variation(skip)]
+    Let { name: Name, value: Box<Self> },
+}
+
+fn labels(value: &Expr) -> Vec<String> {
+    value.children().map(|child| child.label()).collect()
+}
+
+#[test]
+fn dyn_iter_fields() {
+    assert!(labels(&Expr::Unit).is_empty());
+    assert_eq!(vec!["x"], labels(&Expr::Var(Name("x"))));
+    assert_eq!(vec!["()", "[1]"], labels(&Expr::Call(Box::new(Expr::Unit), 3, vec![Name("y")])));
+    assert_eq!(
+        vec!["z", "(w)"],
+        labels(&Expr::Let { name: Name("z"), value: Box::new(Expr::Var(Name("w"))) })
+    );
+}
+
+#[test]
+fn dyn_iter_borrows() {
+    let value = Expr::Var(Name("x"));
+    let child = value.children().next().unwrap();
+
+    assert!(std::ptr::eq(child as *const dyn Node as *const Name, value.as_var().unwrap()));
+}
+
+#[derive(Variation)]
+#[variation(dyn_iter = "Node")]
+enum Decl {
+    Fn(#[variation(dyn_iter)] Name, Vec<u8>, #[variation(dyn_iter)] Box<Expr>),
+    Const(#[variation(dyn_iter)] Name, u64),
+}
+
+#[test]
+fn dyn_iter_marked() {
+    let decl = Decl::Fn(Name("f"), vec![1, 2], Box::new(Expr::Var(Name("a"))));
+
+    assert_eq!(vec!["f", "(a)"], decl.children().map(|child| child.label()).collect::<Vec<_>>());
+    assert_eq!(&vec![1, 2], decl.as_fn().unwrap().1);
+    assert_eq!(
+        vec!["N"],
+        Decl::Const(Name("N"), 1).children().map(|child| child.label()).collect::<Vec<_>>()
+    );
+    assert_eq!(Some((&Name("N"), &1)), Decl::Const(Name("N"), 1).as_const());
+}
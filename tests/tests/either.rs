@@ -0,0 +1,50 @@
+#![cfg(feature = "either")]
+
+use either::Either;
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(either)]
+enum Value {
+    Integer(i32),
+    Text(String),
+}
+
+#[test]
+fn into_either() {
+    assert_eq!(Either::Left(5), Value::Integer(5).into_either());
+    assert_eq!(Either::Right(String::from("text")), Value::Text(String::from("text")).into_either());
+}
+
+#[test]
+fn from_either() {
+    assert_eq!(Value::Integer(5), Value::from_either(Either::Left(5)));
+    assert_eq!(Value::Text(String::new()), Value::from_either(Either::Right(String::new())));
+}
+
+#[test]
+fn as_either() {
+    let mut values = vec![Value::Integer(1), Value::Text(String::from("two")), Value::Integer(3)];
+    let (integers, texts): (Vec<_>, Vec<_>) = values.iter().map(Value::as_either).partition(Either::is_left);
+
+    assert_eq!(vec![Either::Left(&1), Either::Left(&3)], integers);
+    assert_eq!(vec![Either::Right(&String::from("two"))], texts);
+
+    values.truncate(1);
+    assert_eq!(Some(&mut 1), values[0].as_integer_mut());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(either)]
+enum Cache {
+    Miss,
+    Hit(u32, u64),
+}
+
+#[test]
+fn unit_and_tuple_variants() {
+    assert_eq!(Either::Left(()), Cache::Miss.into_either());
+    assert_eq!(Either::Left(&()), Cache::Miss.as_either());
+    assert_eq!(Either::Right((&1, &2)), Cache::Hit(1, 2).as_either());
+    assert_eq!(Cache::Hit(1, 2), Cache::from_either(Either::Right((1, 2))));
+}
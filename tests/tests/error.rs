@@ -0,0 +1,66 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+use variation::Variation;
+
+#[derive(Debug, Variation)]
+#[variation(error)]
+enum ConfigError {
+    Io(io::Error),
+    #[variation(error(msg = "invalid port"))]
+    Port(ParseIntError),
+    #[variation(error(msg = "missing host"))]
+    MissingHost,
+}
+
+#[test]
+fn error_display() {
+    let io = ConfigError::Io(io::Error::new(io::ErrorKind::NotFound, "no config file"));
+    let port = ConfigError::Port("http".parse::<u16>().unwrap_err());
+
+    assert_eq!("no config file", io.to_string());
+    assert_eq!("invalid port: invalid digit found in string", port.to_string());
+    assert_eq!("missing host", ConfigError::MissingHost.to_string());
+}
+
+#[test]
+fn error_source() {
+    let io = ConfigError::Io(io::Error::new(io::ErrorKind::NotFound, "no config file"));
+    let port = ConfigError::Port("http".parse::<u16>().unwrap_err());
+
+    assert_eq!("no config file", io.source().unwrap().to_string());
+    assert!(port.source().unwrap().is::<ParseIntError>());
+    assert!(ConfigError::MissingHost.source().is_none());
+    assert!(port.is_port());
+}
+
+#[derive(Debug)]
+struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("timed out")
+    }
+}
+
+impl Error for Timeout {}
+
+#[derive(Debug, Variation)]
+#[variation(error)]
+enum RequestError<E> {
+    #[variation(error(msg = "request failed"))]
+    Inner(E),
+    Timeout(Timeout),
+}
+
+#[test]
+fn error_generic() {
+    let inner: RequestError<Timeout> = RequestError::Inner(Timeout);
+    let boxed: Box<dyn Error> = Box::new(RequestError::<Timeout>::Timeout(Timeout));
+
+    assert_eq!("request failed: timed out", inner.to_string());
+    assert_eq!("timed out", boxed.to_string());
+    assert!(boxed.source().unwrap().is::<Timeout>());
+    assert!(inner.is_inner());
+}
@@ -0,0 +1,87 @@
+use variation::{augment, Variation};
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(kind, ref_enum)]
+enum Sample {
+    Empty,
+    Metrics(u64, #[cfg(feature = "introspection")] Vec<u64>),
+    Gauge(#[cfg(not(feature = "introspection"))] u8, f64),
+}
+
+#[cfg(feature = "introspection")]
+#[test]
+fn field_cfg_enabled() {
+    let mut value = Sample::Metrics(1, vec![2]);
+
+    if let Some((_, buckets)) = value.as_metrics_mut() {
+        buckets.push(3);
+    }
+
+    assert_eq!(Some((&1, &vec![2, 3])), value.as_metrics());
+    assert_eq!(Err(SampleKind::Metrics), value.try_as_gauge());
+    assert_eq!(0.5, Sample::Gauge(0.5).into_gauge());
+    assert!(Sample::Empty.is_empty());
+}
+
+#[cfg(not(feature = "introspection"))]
+#[test]
+fn field_cfg_disabled() {
+    let mut value = Sample::Metrics(1);
+
+    if let Some(count) = value.as_metrics_mut() {
+        *count += 1;
+    }
+
+    assert_eq!(Some(&2), value.as_metrics());
+    assert_eq!(Err(SampleKind::Metrics), value.try_as_gauge());
+    assert_eq!((1, 0.5), Sample::Gauge(1, 0.5).into_gauge());
+    assert!(Sample::Empty.is_empty());
+}
+
+// `augment!` sees the `#[cfg]` of the fields, which the derive doesn't.
+#[derive(Debug, PartialEq)]
+enum Reading {
+    Missing,
+    Value(u32, #[cfg(feature = "introspection")] &'static str),
+    Range {
+        low: u32,
+        #[cfg(not(feature = "introspection"))]
+        high: u32,
+    },
+}
+
+augment! {
+    #[variation(kind, into_mode = "option")]
+    enum Reading {
+        Missing,
+        Value(u32, #[cfg(feature = "introspection")] &'static str),
+        #[variation(skip)]
+        Range {
+            low: u32,
+            #[cfg(not(feature = "introspection"))]
+            high: u32,
+        },
+    }
+}
+
+#[cfg(feature = "introspection")]
+#[test]
+fn field_cfg_augment_enabled() {
+    let value = Reading::Value(1, "ms");
+
+    assert_eq!(Some((&1, &"ms")), value.as_value());
+    assert_eq!(ReadingKind::Range, Reading::Range { low: 0 }.kind());
+    assert_eq!(Err(ReadingKind::Missing), Reading::Missing.try_as_value());
+    assert_eq!(Some((1, "ms")), value.into_value());
+}
+
+#[cfg(not(feature = "introspection"))]
+#[test]
+fn field_cfg_augment_disabled() {
+    let value = Reading::Value(1);
+
+    assert_eq!(Some(&1), value.as_value());
+    assert_eq!(ReadingKind::Range, Reading::Range { low: 0, high: 1 }.kind());
+    assert_eq!(Err(ReadingKind::Missing), Reading::Missing.try_as_value());
+    assert_eq!(Some(1), value.into_value());
+}
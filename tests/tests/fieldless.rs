@@ -0,0 +1,164 @@
+use variation::Variation;
+
+#[derive(Clone, Copy, Debug, PartialEq, Variation)]
+#[variation(iter, cycle)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[test]
+fn variants() {
+    assert_eq!(
+        [Direction::North, Direction::East, Direction::South, Direction::West],
+        Direction::VARIANTS
+    );
+}
+
+#[test]
+fn iter() {
+    let directions = Direction::iter().collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![Direction::North, Direction::East, Direction::South, Direction::West],
+        directions
+    );
+    assert_eq!(Direction::VARIANTS.len(), Direction::iter().count());
+}
+
+#[test]
+fn next() {
+    assert_eq!(Direction::East, Direction::North.next());
+    assert_eq!(Direction::South, Direction::East.next());
+    assert_eq!(Direction::West, Direction::South.next());
+    assert_eq!(Direction::North, Direction::West.next());
+}
+
+#[test]
+fn prev() {
+    assert_eq!(Direction::West, Direction::North.prev());
+    assert_eq!(Direction::North, Direction::East.prev());
+    assert_eq!(Direction::South, Direction::West.prev());
+}
+
+#[test]
+fn cycle_round_trip() {
+    for direction in Direction::iter() {
+        assert_eq!(direction, direction.next().prev());
+        assert_eq!(direction, direction.prev().next());
+    }
+
+    let mut direction = Direction::North;
+    for _ in 0..Direction::VARIANTS.len() {
+        direction = direction.next();
+    }
+    assert_eq!(Direction::North, direction);
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(iter, cycle)]
+enum Single {
+    Only,
+}
+
+#[test]
+fn iter_single() {
+    assert_eq!(1, Single::VARIANTS.len());
+    assert!(Single::iter().all(|value| value.is_only()));
+}
+
+#[test]
+fn cycle_single() {
+    assert_eq!(Single::Only, Single::Only.next());
+    assert_eq!(Single::Only, Single::Only.prev());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(iter, from_str, as_str)]
+enum Mode {
+    Fast,
+    LowPower,
+    Off,
+}
+
+#[test]
+fn from_str() {
+    assert_eq!(Ok(Mode::Fast), "Fast".parse());
+    assert_eq!(Ok(Mode::Fast), "fast".parse());
+    assert_eq!(Ok(Mode::LowPower), "LowPower".parse());
+    assert_eq!(Ok(Mode::LowPower), "low_power".parse());
+    assert_eq!(Ok(Mode::Off), "off".parse());
+}
+
+#[test]
+fn from_str_error() {
+    let error = "lowPower".parse::<Mode>().unwrap_err();
+
+    assert_eq!("lowPower", error.value());
+    assert_eq!(&["Fast", "fast", "LowPower", "low_power", "Off", "off"], error.accepted());
+    assert_eq!(
+        "unknown `Mode` `lowPower`, expected one of `Fast`, `fast`, `LowPower`, `low_power`, `Off`, `off`",
+        error.to_string()
+    );
+    assert!("".parse::<Mode>().is_err());
+    assert!(" fast".parse::<Mode>().is_err());
+}
+
+#[test]
+fn from_str_round_trip() {
+    for mode in Mode::iter() {
+        let name = mode.variant_name();
+        assert_eq!(Ok(mode), name.parse());
+    }
+}
+
+const MODE_KEYS: [&str; 3] = [Mode::Fast.as_str(), Mode::LowPower.as_str(), Mode::Off.as_str()];
+
+#[test]
+fn as_str() {
+    assert_eq!(["Fast", "LowPower", "Off"], MODE_KEYS);
+    assert_eq!(Mode::LowPower.variant_name(), Mode::LowPower.as_str());
+}
+
+#[test]
+fn as_str_round_trip() {
+    for mode in Mode::iter() {
+        let key = mode.as_str();
+        assert_eq!(Ok(mode), key.parse());
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Variation)]
+#[variation(iter, as_str, from_str)]
+enum Theme {
+    #[variation(str = "light-mode")]
+    Light,
+    #[variation(str = "dark-mode")]
+    Dark,
+    System,
+}
+
+#[test]
+fn str_override() {
+    assert_eq!("light-mode", Theme::Light.as_str());
+    assert_eq!("dark-mode", Theme::Dark.as_str());
+    assert_eq!("System", Theme::System.as_str());
+
+    assert_eq!(Ok(Theme::Light), "light-mode".parse());
+    assert_eq!(Ok(Theme::System), "system".parse());
+    assert!("Light".parse::<Theme>().is_err());
+    assert!("light".parse::<Theme>().is_err());
+    assert_eq!(
+        &["light-mode", "dark-mode", "System", "system"],
+        "Dark".parse::<Theme>().unwrap_err().accepted()
+    );
+}
+
+#[test]
+fn str_override_round_trip() {
+    for theme in Theme::iter() {
+        assert_eq!(Ok(theme), theme.as_str().parse());
+    }
+}
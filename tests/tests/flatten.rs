@@ -0,0 +1,63 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+enum Literal {
+    Text(String),
+    Pair(i64, i64),
+    Null,
+}
+
+#[derive(Debug, PartialEq, Variation)]
+enum Expr {
+    Simple(u32),
+    #[variation(flatten(Text(String), Pair(i64, i64), Null))]
+    Literal(Literal),
+}
+
+#[test]
+fn flattened_is() {
+    assert!(Expr::Literal(Literal::Null).is_null());
+    assert!(Expr::Literal(Literal::Text("a".to_owned())).is_text());
+    assert!(!Expr::Literal(Literal::Null).is_text());
+    assert!(!Expr::Simple(1).is_null());
+    assert!(Expr::Literal(Literal::Null).is_literal());
+}
+
+#[test]
+fn flattened_as() {
+    let mut text = Expr::Literal(Literal::Text("a".to_owned()));
+
+    if let Some(text) = text.as_text_mut() {
+        text.push('b');
+    }
+
+    assert_eq!(Some(&"ab".to_owned()), text.as_text());
+    assert_eq!(Some((&1, &2)), Expr::Literal(Literal::Pair(1, 2)).as_pair());
+    assert_eq!(None, Expr::Simple(1).as_text());
+    assert_eq!(Some(&1), Expr::Simple(1).as_simple());
+}
+
+#[derive(Variation)]
+enum Token {
+    #[variation(rename = "Word")]
+    Text(String),
+    Number(i64),
+}
+
+#[derive(Variation)]
+#[variation(methods(is, as))]
+enum Node {
+    Leaf(u8),
+    #[variation(flatten(#[variation(rename = "Word")] Text(String), #[variation(skip)] Number(i64)))]
+    Token(Token),
+}
+
+#[test]
+fn flattened_options() {
+    let word = Node::Token(Token::Text("a".to_owned()));
+
+    assert!(word.is_word());
+    assert_eq!(Some(&"a".to_owned()), word.as_word());
+    assert!(Node::Token(Token::Number(1)).as_token().and_then(Token::as_number).is_some_and(|&number| number == 1));
+    assert_eq!(Some(&1), Node::Leaf(1).as_leaf());
+}
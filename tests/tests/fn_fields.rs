@@ -0,0 +1,123 @@
+use variation::Variation;
+
+type Closure = Box<dyn Fn(i32) -> i32 + Send>;
+
+#[derive(Variation)]
+#[variation(kind, fold, visitor)]
+enum Handler {
+    Callback(fn(i32) -> i32),
+    Chain(fn(i32) -> i32, fn(i32) -> i32),
+    Boxed(Box<dyn Fn(i32) -> i32 + Send>),
+    Weighted(Closure, u8),
+    Noop,
+}
+
+fn double(n: i32) -> i32 {
+    n * 2
+}
+
+fn increment(n: i32) -> i32 {
+    n + 1
+}
+
+fn call(handler: &Handler, n: i32) -> i32 {
+    handler.fold_ref(
+        |callback| callback(n),
+        |first, second| second(first(n)),
+        |boxed| boxed(n),
+        |closure, weight| closure(n) * i32::from(*weight),
+        || n,
+    )
+}
+
+#[test]
+fn fn_pointer_accessors() {
+    let mut handler = Handler::Callback(double);
+
+    assert_eq!(Some(6), handler.as_callback().map(|callback| callback(3)));
+
+    *handler.as_callback_mut().unwrap() = increment;
+
+    assert_eq!(4, (handler.into_callback())(3));
+    assert_eq!(Ok(7), Handler::Chain(double, increment).try_as_chain().map(|(first, second)| second(first(3))));
+    assert!(Handler::Chain(double, increment).try_as_callback().is_err());
+}
+
+#[test]
+fn boxed_closure_accessors() {
+    let offset = 10;
+    let mut handler = Handler::Boxed(Box::new(move |n| n + offset));
+
+    assert_eq!(Some(11), handler.as_boxed().map(|boxed| boxed(1)));
+
+    *handler.as_boxed_mut().unwrap() = Box::new(|n| -n);
+
+    assert_eq!(-1, (handler.into_boxed())(1));
+    assert_eq!(Err(HandlerKind::Noop), Handler::Noop.try_as_weighted().map(|_| ()));
+}
+
+#[test]
+fn fn_fields_fold() {
+    assert_eq!(6, call(&Handler::Callback(double), 3));
+    assert_eq!(7, call(&Handler::Chain(double, increment), 3));
+    assert_eq!(9, call(&Handler::Weighted(Box::new(increment), 3), 2));
+    assert_eq!(3, call(&Handler::Noop, 3));
+}
+
+struct Apply(i32);
+
+impl HandlerVisitor for Apply {
+    type Output = i32;
+
+    fn visit_callback(&mut self, v0: &fn(i32) -> i32) -> i32 {
+        v0(self.0)
+    }
+
+    fn visit_chain(&mut self, v0: &fn(i32) -> i32, v1: &fn(i32) -> i32) -> i32 {
+        v1(v0(self.0))
+    }
+
+    fn visit_boxed(&mut self, v0: &Box<dyn Fn(i32) -> i32 + Send>) -> i32 {
+        v0(self.0)
+    }
+
+    fn visit_weighted(&mut self, v0: &Closure, v1: &u8) -> i32 {
+        v0(self.0) * i32::from(*v1)
+    }
+
+    fn visit_noop(&mut self) -> i32 {
+        self.0
+    }
+}
+
+#[test]
+fn fn_fields_visitor() {
+    assert_eq!(8, Handler::Chain(increment, double).accept(&mut Apply(3)));
+    assert_eq!(-3, Handler::Boxed(Box::new(|n| -n)).accept(&mut Apply(3)));
+}
+
+#[derive(Debug, Variation)]
+#[variation(ref_enum, mut_enum, inner)]
+enum Op {
+    Unary(fn(i32) -> i32),
+    Fallback(fn(i32) -> i32),
+}
+
+#[test]
+fn fn_pointer_projections() {
+    let mut op = Op::Unary(double);
+
+    if let OpMut::Unary(f) = op.as_mut() {
+        *f = increment;
+    }
+
+    match op.as_ref() {
+        OpRef::Unary(f) => assert_eq!(2, f(1)),
+        OpRef::Fallback(_) => unreachable!(),
+    }
+
+    op.map_inner_mut(|f| *f = double);
+
+    assert_eq!(4, (op.as_inner())(2));
+    assert_eq!(2, (Op::Fallback(increment).into_inner())(1));
+}
@@ -0,0 +1,116 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(fold)]
+enum Value {
+    Integer(i32),
+    Real(i32, u32),
+}
+
+#[test]
+fn either() {
+    let describe = |value: Value| value.either(|i| format!("integer {}", i), |i, f| format!("real {}.{}", i, f));
+
+    assert_eq!("integer 5", describe(Value::Integer(5)));
+    assert_eq!("real 3.14", describe(Value::Real(3, 14)));
+}
+
+#[test]
+fn either_ref() {
+    let values = [Value::Integer(5), Value::Real(3, 14), Value::Integer(-1)];
+    let integers: Vec<&i32> = values.iter().map(|value| value.either_ref(|i| i, |i, _| i)).collect();
+
+    assert_eq!(vec![&5, &3, &-1], integers);
+}
+
+#[derive(Variation)]
+#[variation(fold)]
+enum Cache {
+    Miss,
+    Hit(u32),
+}
+
+// A unit variant means `Cache` has no `either` method, so this inherent
+// method doesn't conflict with a generated one.
+impl Cache {
+    fn either(&self) -> u32 {
+        match self {
+            Cache::Miss => 0,
+            Cache::Hit(n) => *n,
+        }
+    }
+}
+
+#[test]
+fn either_skipped_for_unit_variants() {
+    assert_eq!(0, Cache::Miss.either());
+    assert_eq!(7, Cache::Hit(7).either());
+}
+
+#[derive(Variation)]
+enum Choice {
+    Left(u8),
+    Right(u8),
+}
+
+// Without `#[variation(fold)]`, `Choice` has no `either` method either, so
+// this inherent method doesn't conflict with a generated one.
+impl Choice {
+    fn either(self) -> u8 {
+        match self {
+            Choice::Left(n) | Choice::Right(n) => n,
+        }
+    }
+}
+
+#[test]
+fn either_requires_fold() {
+    assert_eq!(1, Choice::Left(1).either());
+    assert_eq!(2, Choice::Right(2).either());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(fold)]
+enum Type {
+    Unit,
+    Integer(i32),
+    Real(i32, u32),
+}
+
+#[test]
+fn fold() {
+    let to_f64 = |value: Type| value.fold(|| 0.0, f64::from, |i, f| f64::from(i) + f64::from(f) / 100.0);
+
+    assert_eq!(0.0, to_f64(Type::Unit));
+    assert_eq!(5.0, to_f64(Type::Integer(5)));
+    assert_eq!(2.5, to_f64(Type::Real(2, 50)));
+}
+
+#[test]
+fn fold_ref() {
+    let values = [Type::Unit, Type::Integer(5), Type::Real(3, 14)];
+    let names: Vec<&str> = values
+        .iter()
+        .map(|value| value.fold_ref(|| "unit", |_| "integer", |_, _| "real"))
+        .collect();
+    let borrowed: Vec<Option<&i32>> = values
+        .iter()
+        .map(|value| value.fold_ref(|| None, Some, |i, _| Some(i)))
+        .collect();
+
+    assert_eq!(vec!["unit", "integer", "real"], names);
+    assert_eq!(vec![None, Some(&5), Some(&3)], borrowed);
+}
+
+#[derive(Variation)]
+#[variation(fold)]
+enum Pair<L, R> {
+    First(L),
+    Second(R),
+}
+
+#[test]
+fn either_type_param_named_r() {
+    assert_eq!(2, Pair::<u8, &str>::First(1).either(|v| usize::from(v) + 1, str::len));
+    assert_eq!(3, Pair::<u8, &str>::Second("abc").either_ref(|v| usize::from(*v), |v| v.len()));
+}
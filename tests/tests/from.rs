@@ -0,0 +1,82 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+enum Type {
+    Unit,
+    #[variation(from)]
+    Integer(i32),
+    #[variation(from)]
+    Real(i32, u32),
+    Text(String),
+}
+
+#[test]
+fn from_single_field() {
+    let value: Type = 5.into();
+
+    assert_eq!(Type::Integer(5), value);
+    assert_eq!(Type::Integer(-1), Type::from(-1));
+}
+
+#[test]
+fn from_tuple() {
+    let value: Type = (3, 14).into();
+
+    assert_eq!(Type::Real(3, 14), value);
+    assert!(Type::Unit.is_unit());
+    assert_eq!(None, Type::Text(String::from("text")).as_real());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+enum Generic<T> {
+    #[variation(from)]
+    Value(T, bool),
+    Empty,
+}
+
+#[test]
+fn from_generic() {
+    let value: Generic<&str> = ("text", true).into();
+
+    assert_eq!(Generic::Value("text", true), value);
+    assert_ne!(Generic::Empty, value);
+}
+
+#[derive(Debug, PartialEq, Variation)]
+enum Label<'a> {
+    #[variation(from(forward))]
+    Text(String),
+    Borrowed(&'a str),
+}
+
+#[test]
+fn from_forward() {
+    let label: Label = "name".into();
+
+    assert_eq!(Label::Text(String::from("name")), label);
+    assert_eq!(Label::Text(String::from("x")), Label::from('x'));
+    assert_eq!(Label::Text(String::from("owned")), Label::from(String::from("owned")));
+    assert_eq!(Some(&"y"), Label::Borrowed("y").as_borrowed());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+enum Cell {
+    #[variation(from(forward(&str, Box<str>)))]
+    Text(String),
+    #[variation(from(forward([f64; 2])))]
+    Point(f64, f64),
+    #[variation(from)]
+    Number(i64),
+}
+
+#[test]
+fn from_forward_types() {
+    let text: Cell = "a".into();
+
+    assert_eq!(Cell::Text(String::from("a")), text);
+    assert_eq!(Cell::Text(String::from("b")), Cell::from(Box::<str>::from("b")));
+    assert_eq!(Cell::Text(String::from("c")), Cell::from(String::from("c")));
+    assert_eq!(Cell::Point(1.0, 2.0), [1.0, 2.0].into());
+    assert_eq!(Cell::Point(3.0, 4.0), (3.0, 4.0).into());
+    assert_eq!(Cell::Number(5), 5.into());
+}
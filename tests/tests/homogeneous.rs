@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+
+use variation::Variation;
+
+#[derive(Variation)]
+#[variation(into_ok_or_err)]
+enum Side {
+    Left(String),
+    Right(String),
+}
+
+#[test]
+fn into_ok_or_err() {
+    assert_eq!("left", Side::Left(String::from("left")).into_ok_or_err());
+    assert_eq!("right", Side::Right(String::from("right")).into_ok_or_err());
+    assert!(Side::Right(String::new()).is_right());
+    assert_eq!(Some(&String::new()), Side::Left(String::new()).as_left());
+}
+
+#[derive(Variation)]
+enum Pair {
+    First(u8),
+    Second(u8),
+}
+
+// Without `#[variation(into_ok_or_err)]`, this inherent method doesn't
+// conflict with a generated one.
+impl Pair {
+    fn into_ok_or_err(self) -> Result<u8, u8> {
+        match self {
+            Pair::First(value) => Ok(value),
+            Pair::Second(value) => Err(value),
+        }
+    }
+}
+
+#[test]
+fn into_ok_or_err_opt_in() {
+    assert_eq!(Ok(1), Pair::First(1).into_ok_or_err());
+    assert_eq!(Err(2), Pair::Second(2).into_ok_or_err());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(inner)]
+enum Source {
+    File(PathBuf),
+    Override(PathBuf),
+    Default(PathBuf),
+}
+
+#[test]
+fn into_inner() {
+    assert_eq!(PathBuf::from("a"), Source::File(PathBuf::from("a")).into_inner());
+    assert_eq!(PathBuf::from("b"), Source::Override(PathBuf::from("b")).into_inner());
+    assert_eq!(PathBuf::from("c"), Source::Default(PathBuf::from("c")).into_inner());
+    assert!(Source::Default(PathBuf::new()).is_default());
+}
+
+#[test]
+fn as_inner() {
+    let sources = [
+        Source::File(PathBuf::from("a")),
+        Source::Override(PathBuf::from("b")),
+        Source::Default(PathBuf::from("c")),
+    ];
+    let paths = sources.iter().map(Source::as_inner).collect::<Vec<_>>();
+
+    assert_eq!(vec![&PathBuf::from("a"), &PathBuf::from("b"), &PathBuf::from("c")], paths);
+}
+
+#[test]
+fn as_inner_mut() {
+    let mut sources = vec![
+        Source::File(PathBuf::from("a")),
+        Source::Override(PathBuf::from("b")),
+        Source::Default(PathBuf::from("c")),
+    ];
+
+    for source in &mut sources {
+        source.as_inner_mut().push("config.toml");
+    }
+
+    assert_eq!(
+        vec![
+            Source::File(PathBuf::from("a/config.toml")),
+            Source::Override(PathBuf::from("b/config.toml")),
+            Source::Default(PathBuf::from("c/config.toml")),
+        ],
+        sources
+    );
+}
+
+#[test]
+fn map_inner() {
+    let normalize = |path: PathBuf| PathBuf::from("/root").join(path);
+
+    assert_eq!(Source::File(PathBuf::from("/root/a")), Source::File(PathBuf::from("a")).map_inner(normalize));
+    assert_eq!(
+        Source::Override(PathBuf::from("/root/b")),
+        Source::Override(PathBuf::from("b")).map_inner(normalize)
+    );
+    assert_eq!(
+        Source::Default(PathBuf::from("/root/c")),
+        Source::Default(PathBuf::from("c")).map_inner(normalize)
+    );
+}
+
+#[test]
+fn map_inner_mut() {
+    let mut sources = vec![
+        Source::File(PathBuf::from("a.toml")),
+        Source::Override(PathBuf::from("b.toml")),
+        Source::Default(PathBuf::from("c.toml")),
+    ];
+
+    for source in &mut sources {
+        source.map_inner_mut(|path| {
+            path.set_extension("json");
+        });
+    }
+
+    assert_eq!(
+        vec![
+            Source::File(PathBuf::from("a.json")),
+            Source::Override(PathBuf::from("b.json")),
+            Source::Default(PathBuf::from("c.json")),
+        ],
+        sources
+    );
+}
+
+#[derive(Variation)]
+#[variation(inner)]
+enum Single<T> {
+    Only(T),
+}
+
+#[test]
+fn into_inner_generic() {
+    let mut single = Single::Only(5);
+    *single.as_inner_mut() += 1;
+
+    assert_eq!(&6, single.as_inner());
+    assert_eq!(12, single.map_inner(|value| value * 2).into_inner());
+}
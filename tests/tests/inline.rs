@@ -0,0 +1,46 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(inline(always))]
+enum Opcode {
+    Push(u8),
+    #[variation(inline(never), alias = "trace")]
+    Debug(String),
+    #[variation(inline)]
+    Jump(u16),
+    Halt,
+}
+
+#[test]
+#[allow(deprecated)]
+fn inline_methods() {
+    let mut jump = Opcode::Jump(3);
+
+    assert!(Opcode::Halt.is_halt());
+    assert_eq!(Some(&1), Opcode::Push(1).as_push());
+    assert!(Opcode::Debug("x".to_owned()).is_trace());
+
+    if let Some(target) = jump.as_jump_mut() {
+        *target += 1;
+    }
+
+    assert_eq!(4, jump.into_jump());
+    assert_eq!("x", Opcode::Debug("x".to_owned()).into_debug());
+}
+
+// The methods with an `#[inline]` of their own don't get the default one.
+#[derive(Debug, PartialEq, Variation)]
+#[variation(attr(is = "inline(always)"))]
+enum Register {
+    #[variation(attr(inline(never)), skip(is))]
+    Accumulator(u8),
+    Index(u16),
+}
+
+#[test]
+fn inline_attrs() {
+    assert!(Register::Index(1).is_index());
+    assert_eq!(Some(&1), Register::Index(1).as_index());
+    assert_eq!(1, Register::Accumulator(1).into_accumulator());
+    assert_eq!(None, Register::Index(1).as_accumulator());
+}
@@ -0,0 +1,155 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(into_mode = "option")]
+enum Setting {
+    Flag(bool),
+    #[variation(alias = "level")]
+    Number(i64, u8),
+    Unset,
+}
+
+#[test]
+#[allow(deprecated)]
+fn into_option() {
+    assert_eq!(Some(true), Setting::Flag(true).into_flag());
+    assert_eq!(None, Setting::Unset.into_flag());
+    assert_eq!(Some((3, 1)), Setting::Number(3, 1).into_number());
+    assert_eq!(None, Setting::Flag(false).into_number());
+    assert_eq!(Some((4, 2)), Setting::Number(4, 2).into_level());
+}
+
+// Large on purpose, as the values are meant to be kept boxed.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Variation)]
+#[variation(into_mode = "result", boxed)]
+enum Message {
+    Text(String),
+    Blob([u8; 512]),
+    Empty,
+}
+
+#[test]
+fn into_result() {
+    assert_eq!(Ok("hi".to_owned()), Message::Text("hi".to_owned()).into_text());
+    assert_eq!(Err(Message::Empty), Message::Empty.into_text());
+
+    let blob = Message::Blob([1; 512]).into_text().unwrap_err();
+    assert_eq!(Ok([1; 512]), blob.into_blob());
+}
+
+#[test]
+fn into_result_boxed() {
+    assert_eq!(Ok("hi".to_owned()), Box::new(Message::Text("hi".to_owned())).into_text_boxed());
+
+    let empty = Box::new(Message::Empty).into_blob_boxed().unwrap_err();
+    assert_eq!(Message::Empty, *empty);
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(into_mode = "option")]
+enum Reading {
+    Celsius(f64),
+    #[variation(into_mode = "panic")]
+    Checked(u32),
+    #[variation(into_mode = "result", into = "take_raw")]
+    Raw(Vec<u8>),
+}
+
+#[test]
+fn into_variant_mode() {
+    assert_eq!(Some(1.5), Reading::Celsius(1.5).into_celsius());
+    assert_eq!(None, Reading::Checked(1).into_celsius());
+    assert_eq!(2, Reading::Checked(2).into_checked());
+    assert_eq!(Ok(vec![1]), Reading::Raw(vec![1]).take_raw());
+    assert_eq!(Err(Reading::Checked(3)), Reading::Checked(3).take_raw());
+}
+
+#[test]
+#[should_panic]
+fn into_variant_mode_panics() {
+    Reading::Celsius(1.5).into_checked();
+}
+
+#[derive(Debug, PartialEq, Variation)]
+enum Computation {
+    // Names the method, the mode being set with `into_mode`.
+    #[variation(into = "result")]
+    Done(u32),
+    #[variation(into = "option", into_mode = "option")]
+    Partial(u32),
+}
+
+#[test]
+fn into_named_like_mode() {
+    assert_eq!(1, Computation::Done(1).result());
+    assert_eq!(Some(2), Computation::Partial(2).option());
+    assert_eq!(None, Computation::Done(3).option());
+}
+
+mod aliased {
+    use variation::Variation;
+
+    // A local alias taking fewer parameters than `std`'s.
+    type Result<T> = std::result::Result<T, String>;
+
+    #[derive(Debug, PartialEq, Variation)]
+    #[variation(into_mode = "result")]
+    pub enum Frame {
+        #[variation(into_mode = "option")]
+        Data(u8),
+        Close(u16),
+    }
+
+    pub fn code(frame: Frame) -> Result<u16> {
+        match frame.into_close() {
+            Ok(code) => Ok(code),
+            Err(frame) => Err(format!("data {:?}", frame.into_data())),
+        }
+    }
+}
+
+#[test]
+fn into_variant_mode_aliased() {
+    assert_eq!(Ok(1000), aliased::code(aliased::Frame::Close(1000)));
+    assert_eq!(Err("data Some(1)".to_owned()), aliased::code(aliased::Frame::Data(1)));
+}
+
+mod glob {
+    use variation::Variation;
+
+    // Shadows `Option::None` and `Result::{Ok, Err}` in the generated code.
+    use self::Cache::*;
+    use self::Outcome::*;
+
+    // Large on purpose, as the values are meant to be kept boxed.
+    #[allow(clippy::large_enum_variant)]
+    #[derive(Debug, PartialEq, Variation)]
+    #[variation(into_mode = "option", boxed)]
+    pub enum Cache {
+        Hit(u32),
+        Page([u8; 512]),
+        None,
+    }
+
+    #[derive(Debug, PartialEq, Variation)]
+    #[variation(into_mode = "result", boxed)]
+    pub enum Outcome {
+        Ok(u8),
+        Err(String),
+    }
+
+    #[test]
+    fn into_glob_imported() {
+        assert_eq!(Some(1), Hit(1).into_hit());
+        assert_eq!(Option::None, None.into_hit());
+        assert_eq!(Some(2), Box::new(Hit(2)).into_hit_boxed());
+        assert_eq!(Option::None, Box::new(Page([0; 512])).into_hit_boxed());
+        assert_eq!(Some(&3), Hit(3).as_hit());
+        assert_eq!(Option::None, None.as_hit_mut());
+        assert_eq!(Result::Ok(4), Ok(4).into_ok());
+        assert_eq!(Result::Err(Ok(5)), Ok(5).into_err());
+        assert_eq!(Result::Err(Box::new(Ok(6))), Box::new(Ok(6)).into_err_boxed());
+        assert_eq!(Result::Ok("lost".to_owned()), Err("lost".to_owned()).into_err());
+    }
+}
@@ -0,0 +1,53 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(iterator_ext)]
+enum Type {
+    Unit,
+    Integer(i32),
+    Real(i32, u32),
+    Entry(String),
+    Property(bool),
+}
+
+fn values() -> Vec<Type> {
+    vec![
+        Type::Integer(1),
+        Type::Unit,
+        Type::Real(3, 14),
+        Type::Entry(String::from("entry")),
+        Type::Integer(2),
+        Type::Property(true),
+    ]
+}
+
+#[test]
+fn owned_adapters() {
+    assert_eq!(vec![1, 2], values().into_iter().integers().collect::<Vec<_>>());
+    assert_eq!(vec![(3, 14)], values().into_iter().reals().collect::<Vec<_>>());
+    assert_eq!(vec![String::from("entry")], values().into_iter().entries().collect::<Vec<_>>());
+    assert_eq!(vec![true], values().into_iter().properties().collect::<Vec<_>>());
+}
+
+#[test]
+fn borrowed_adapters() {
+    let values = values();
+
+    assert_eq!(vec![&1, &2], values.iter().integers().collect::<Vec<_>>());
+    assert_eq!(vec![(&3, &14)], values.iter().reals().collect::<Vec<_>>());
+    assert!(values.iter().any(Type::is_unit));
+}
+
+#[test]
+fn adapter_after_map() {
+    let doubled: Vec<i32> = values()
+        .into_iter()
+        .map(|value| match value {
+            Type::Integer(i) => Type::Integer(i * 2),
+            other => other,
+        })
+        .integers()
+        .collect();
+
+    assert_eq!(vec![2, 4], doubled);
+}
@@ -0,0 +1,166 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(kind)]
+enum Type {
+    Unit,
+    Integer(i32),
+    Real(i32, u32),
+}
+
+#[test]
+fn kind() {
+    assert_eq!(TypeKind::Unit, Type::Unit.kind());
+    assert_eq!(TypeKind::Integer, Type::Integer(5).kind());
+    assert_eq!(TypeKind::Real, Type::Real(3, 14).kind());
+    assert!(TypeKind::Unit < TypeKind::Real);
+    assert_eq!(Some((&3, &14)), Type::Real(3, 14).as_real());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(group_by_kind)]
+enum Event {
+    Click(u32, u32),
+    Key(char),
+    Resize,
+}
+
+#[test]
+fn group_by_kind() {
+    let events = vec![
+        Event::Key('a'),
+        Event::Click(1, 2),
+        Event::Key('b'),
+        Event::Resize,
+        Event::Key('c'),
+    ];
+    let groups = Event::group_by_kind(events);
+
+    assert_eq!(3, groups.len());
+    assert_eq!(vec![Event::Key('a'), Event::Key('b'), Event::Key('c')], groups[&EventKind::Key]);
+    assert_eq!(vec![Event::Click(1, 2)], groups[&EventKind::Click]);
+    assert_eq!(vec![Event::Resize], groups[&EventKind::Resize]);
+}
+
+#[test]
+fn group_by_kind_empty() {
+    assert!(Event::group_by_kind(Vec::new()).is_empty());
+    assert_eq!(Some((&1, &2)), Event::Click(1, 2).as_click());
+}
+
+#[derive(Variation)]
+#[variation(count_kinds)]
+enum Metric {
+    Counter(u64),
+    Gauge(f64),
+    Histogram(Vec<f64>),
+    Unknown,
+}
+
+#[test]
+fn count_kinds() {
+    let metrics = vec![Metric::Counter(1), Metric::Gauge(0.5), Metric::Counter(2), Metric::Unknown];
+    let counts = MetricKindCounts::from_iter(&metrics);
+
+    assert_eq!(2, counts.counter());
+    assert_eq!(1, counts.gauge());
+    assert_eq!(0, counts.histogram());
+    assert_eq!(1, counts.unknown());
+    assert_eq!(4, counts.total());
+    assert_eq!(MetricKindCounts::default(), MetricKindCounts::from_iter(&[]));
+}
+
+#[test]
+fn count_kinds_merge_shards() {
+    let first = [Metric::Counter(1), Metric::Histogram(vec![1.0])];
+    let second = [Metric::Counter(2), Metric::Gauge(2.0), Metric::Histogram(Vec::new())];
+    let first_counts: MetricKindCounts = first.iter().collect();
+    let mut merged = first_counts + second.iter().collect();
+
+    assert_eq!(2, merged.counter());
+    assert_eq!(1, merged.gauge());
+    assert_eq!(2, merged.histogram());
+    assert_eq!(5, merged.total());
+
+    merged += MetricKindCounts::from_iter(&[Metric::Unknown]);
+    assert_eq!(6, merged.total());
+    assert_eq!(Some(&1), first[0].as_counter());
+    assert_eq!(Some(&2.0), second[1].as_gauge());
+    assert_eq!(Some(&vec![1.0]), first[1].as_histogram());
+}
+
+#[derive(Variation)]
+#[variation(count_kinds)]
+enum Line {
+    Item(u32),
+    #[variation(rename = "sum")]
+    Total(u32),
+}
+
+#[test]
+fn count_kinds_renamed() {
+    let counts = LineKindCounts::from_iter(&[Line::Item(1), Line::Item(2), Line::Total(3)]);
+
+    assert_eq!(2, counts.item());
+    assert_eq!(1, counts.sum());
+    assert_eq!(3, counts.total());
+    assert_eq!(Some(&3), Line::Total(3).as_sum());
+    assert_eq!(Some(&1), Line::Item(1).as_item());
+}
+
+#[derive(Variation)]
+#[variation(count_kinds)]
+enum Generic<T> {
+    Boxed(Box<T>),
+    Owned(T),
+}
+
+#[test]
+fn count_kinds_generic() {
+    let values = vec![Generic::Boxed(Box::new(1)), Generic::Owned(2), Generic::Owned(3)];
+    let counts = GenericKindCounts::from_iter(&values);
+
+    assert_eq!(1, counts.boxed());
+    assert_eq!(2, counts.owned());
+    assert_eq!(Some(&Box::new(1)), values[0].as_boxed());
+    assert_eq!(Some(&2), values[1].as_owned());
+}
+
+#[test]
+fn try_as() {
+    assert_eq!(Ok(&5), Type::Integer(5).try_as_integer());
+    assert_eq!(Ok((&3, &14)), Type::Real(3, 14).try_as_real());
+    assert_eq!(Err(TypeKind::Real), Type::Real(3, 14).try_as_integer());
+    assert_eq!(Err(TypeKind::Unit), Type::Unit.try_as_real());
+}
+
+#[test]
+fn try_as_mut() {
+    let mut value = Type::Real(3, 14);
+
+    if let Ok((_, fraction)) = value.try_as_real_mut() {
+        *fraction = 15;
+    }
+
+    assert_eq!(Type::Real(3, 15), value);
+    assert_eq!(Err(TypeKind::Real), value.try_as_integer_mut());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(kind, prefix(as = "get_"))]
+enum Setting {
+    #[variation(as_mut = "flag_mut")]
+    Flag(bool),
+    Level(u8),
+}
+
+#[test]
+fn try_as_renamed() {
+    let mut setting = Setting::Flag(true);
+
+    assert_eq!(Ok(&true), setting.try_get_flag());
+    assert_eq!(Err(SettingKind::Flag), setting.try_get_level());
+    assert_eq!(Ok(&mut true), setting.try_flag_mut());
+    assert_eq!(Err(SettingKind::Flag), setting.try_as_level_mut());
+    assert_eq!(Ok(&mut 2), Setting::Level(2).try_as_level_mut());
+}
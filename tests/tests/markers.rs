@@ -0,0 +1,76 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(markers)]
+enum Type {
+    Integer(i64),
+    Text(String),
+    Pair(u8, u8),
+    Null,
+}
+
+use type_markers::Variant;
+
+#[test]
+fn get_by_marker() {
+    let mut value = Type::Integer(1);
+
+    assert_eq!(Some(&1), value.get::<type_markers::Integer>());
+    assert_eq!(None, value.get::<type_markers::Text>());
+
+    *value.get_mut::<type_markers::Integer>().unwrap() += 1;
+
+    assert_eq!(Ok(2), value.into_variant::<type_markers::Integer>());
+    assert_eq!(Err(Type::Pair(1, 2)), Type::Pair(1, 2).into_variant::<type_markers::Text>());
+    assert_eq!(None, Type::Null.get::<type_markers::Integer>());
+}
+
+fn collect<V: Variant<Type>>(values: Vec<Type>) -> Vec<V::Output> {
+    values.into_iter().filter_map(|value| value.into_variant::<V>().ok()).collect()
+}
+
+#[test]
+fn generic_over_markers() {
+    let values = || vec![Type::Integer(1), Type::Text("a".to_owned()), Type::Null, Type::Integer(2)];
+
+    assert_eq!(vec![1, 2], collect::<type_markers::Integer>(values()));
+    assert_eq!(vec!["a".to_owned()], collect::<type_markers::Text>(values()));
+}
+
+#[derive(Variation)]
+#[variation(markers)]
+enum Wrapper<T> {
+    Value(T),
+    Empty,
+}
+
+#[test]
+fn generic_enum_markers() {
+    let mut wrapper = Wrapper::Value(vec![1]);
+
+    wrapper.get_mut::<wrapper_markers::Value>().unwrap().push(2);
+
+    assert_eq!(Some(&vec![1, 2]), wrapper.get::<wrapper_markers::Value>());
+    assert!(Wrapper::<u8>::Empty.is_empty());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(markers)]
+enum Token<'a> {
+    Word(&'a str),
+    Number(u32),
+}
+
+#[test]
+fn borrowing_enum_markers() {
+    let text = String::from("variation");
+    let mut token = Token::Word(&text[..4]);
+
+    assert_eq!(Some(&"vari"), token.get::<token_markers::Word>());
+    assert_eq!(None, token.get::<token_markers::Number>());
+
+    *token.get_mut::<token_markers::Word>().unwrap() = &text;
+
+    assert_eq!(Ok(&text[..]), token.into_variant::<token_markers::Word>());
+    assert_eq!(Some(&1), Token::Number(1).get::<token_markers::Number>());
+}
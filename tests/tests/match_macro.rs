@@ -0,0 +1,74 @@
+mod types {
+    use variation::Variation;
+
+    #[derive(Debug, PartialEq, Variation)]
+    #[variation(match_macro)]
+    pub enum Type {
+        Unit,
+        Integer(i64),
+        Real(i64, u64),
+        #[variation(skip)]
+        Point { x: i32, y: i32 },
+    }
+
+    #[derive(Debug, PartialEq, Variation)]
+    #[variation(match_macro = "on_shape", rename_all = "lowercase")]
+    pub enum Shape<T> {
+        Circle(T),
+        #[variation(rename = "poly")]
+        Polygon(Vec<T>),
+    }
+}
+
+mod describe {
+    use crate::types::{match_type, on_shape, Shape, Type};
+
+    pub fn describe(value: &Type) -> String {
+        match_type!(value, {
+            unit => "unit".to_owned(),
+            integer(n) => n.to_string(),
+            real(a, b) => format!("{}.{}", a, b),
+            point(x, y) => format!("({}, {})", x, y),
+        })
+    }
+
+    pub fn points<T>(shape: Shape<T>) -> usize {
+        on_shape!(shape, {
+            circle(_) => 1,
+            poly(points) => points.len(),
+        })
+    }
+}
+
+#[test]
+fn match_macro_arms() {
+    use types::Type;
+
+    assert_eq!("unit", describe::describe(&Type::Unit));
+    assert_eq!("2", describe::describe(&Type::Integer(2)));
+    assert_eq!("3.14", describe::describe(&Type::Real(3, 14)));
+    assert_eq!("(1, 0)", describe::describe(&Type::Point { x: 1, y: 0 }));
+}
+
+#[test]
+fn match_macro_named() {
+    use types::Shape;
+
+    assert_eq!(1, describe::points(Shape::Circle(2.0)));
+    assert_eq!(3, describe::points(Shape::Polygon(vec![1, 2, 3])));
+}
+
+#[test]
+fn match_macro_by_path() {
+    use types::Type;
+
+    let value = Type::Integer(-1);
+    let sign = crate::types::match_type!(value, {
+        unit => 0,
+        integer(n) => n.signum(),
+        real(a, _) => a.signum(),
+        point(_, _) => 0,
+    });
+
+    assert_eq!(-1, sign);
+}
@@ -0,0 +1,42 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(methods(is, as), option_ext)]
+enum Shape {
+    Circle(f64),
+    #[variation(skip(as))]
+    Square(f64),
+    Empty,
+}
+
+#[test]
+fn methods_listed() {
+    let circle = Shape::Circle(1.0);
+
+    assert!(circle.is_circle());
+    assert!(Shape::Square(2.0).is_square());
+    assert!(Shape::Empty.is_empty());
+    assert_eq!(Some(&1.0), circle.as_circle());
+    assert_eq!(Some(&1.0), Some(Shape::Circle(1.0)).as_circle());
+    assert!(Some(Shape::Square(2.0)).is_square());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(methods(into), drain)]
+enum Job {
+    #[variation(alias = "task")]
+    Queued(u32),
+    #[variation(skip(into))]
+    Running(u32),
+}
+
+#[test]
+#[allow(deprecated)]
+fn methods_into_only() {
+    let mut jobs = vec![Job::Queued(1), Job::Running(2), Job::Queued(3)];
+
+    assert_eq!(1, Job::Queued(1).into_queued());
+    assert_eq!(4, Job::Queued(4).into_task());
+    assert_eq!(vec![1, 3], Job::drain_queueds(&mut jobs));
+    assert_eq!(vec![Job::Running(2)], jobs);
+}
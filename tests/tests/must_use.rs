@@ -0,0 +1,33 @@
+#![deny(warnings)]
+
+use variation::Variation;
+
+// Large on purpose, as the values are meant to be kept boxed.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Variation)]
+#[variation(must_use, boxed)]
+enum Node {
+    #[variation(alias = "value")]
+    Leaf(i32),
+    Branch([u8; 512]),
+    Empty,
+}
+
+#[test]
+#[allow(deprecated)]
+fn must_use_results_used() {
+    let mut leaf = Node::Leaf(1);
+
+    assert!(leaf.is_leaf());
+    assert!(leaf.is_value());
+    assert_eq!(Some(&1), leaf.as_leaf());
+
+    if let Some(value) = leaf.as_leaf_mut() {
+        *value += 1;
+    }
+
+    assert_eq!("Leaf", leaf.variant_name());
+    assert_eq!(2, leaf.into_leaf());
+    assert_eq!([1; 512], Box::new(Node::Branch([1; 512])).into_branch_boxed());
+    assert!(Node::Empty.is_empty());
+}
@@ -0,0 +1,78 @@
+use variation::Variation;
+
+#[derive(Variation)]
+#[variation(display)]
+enum Message {
+    Ping,
+    Text(String),
+    Move(i32, i32),
+}
+
+#[test]
+fn variant_name() {
+    assert_eq!("Ping", Message::Ping.variant_name());
+    assert_eq!("Text", Message::Text(String::from("hello")).variant_name());
+    assert_eq!("Move", Message::Move(1, 2).variant_name());
+    assert_eq!(Some((&1, &2)), Message::Move(1, 2).as_move());
+}
+
+#[test]
+fn display() {
+    let text = Message::Text(String::from("hello"));
+
+    assert_eq!("Text", text.to_string());
+    assert_eq!("Ping", format!("{}", Message::Ping));
+    assert_eq!(text.variant_name(), format!("{}", text));
+    assert_eq!(Some(&String::from("hello")), text.as_text());
+}
+
+#[derive(Variation)]
+#[allow(non_camel_case_types)]
+enum Raw<T> {
+    r#type(T),
+}
+
+#[test]
+fn raw_variant_name() {
+    assert_eq!("type", Raw::r#type(1).variant_name());
+}
+
+#[derive(Variation)]
+#[variation(tag)]
+enum Event {
+    #[variation(tag = "http.request")]
+    Request(String),
+    #[variation(tag = "http.request")]
+    Retry(String),
+    #[variation(tag = "cache-miss")]
+    CacheMiss,
+    ConnectionClosed,
+}
+
+#[test]
+fn tag() {
+    let request = Event::Request(String::from("/"));
+
+    assert_eq!("http.request", request.tag());
+    let retry = Event::Retry(String::from("/"));
+
+    assert_eq!("http.request", retry.tag());
+    assert_eq!("cache-miss", Event::CacheMiss.tag());
+    assert_eq!("connection_closed", Event::ConnectionClosed.tag());
+    assert_eq!(request.as_request(), retry.as_retry());
+}
+
+#[derive(Variation)]
+#[variation(tag, rename_all = "lowercase")]
+enum Protocol {
+    IPv4,
+    #[variation(tag = "ip.v6")]
+    IPv6,
+}
+
+#[test]
+fn tag_rename_all() {
+    assert!(Protocol::IPv4.is_ipv4());
+    assert_eq!("ipv4", Protocol::IPv4.tag());
+    assert_eq!("ip.v6", Protocol::IPv6.tag());
+}
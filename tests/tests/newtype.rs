@@ -0,0 +1,70 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+enum Inner {
+    Success(String),
+    Failure(u16, String),
+    Pending,
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(variants(Success(String), Failure(u16, String), Pending))]
+struct Response(Inner);
+
+#[test]
+fn forwarded_accessors() {
+    let mut response = Response(Inner::Failure(404, "not found".to_owned()));
+
+    if let Some((_, message)) = response.as_failure_mut() {
+        message.push('!');
+    }
+
+    assert!(response.is_failure());
+    assert!(!response.is_pending());
+    assert_eq!(None, response.as_success());
+    assert_eq!(Some(&"ok".to_owned()), Response(Inner::Success("ok".to_owned())).as_success());
+    assert_eq!(Some((&404, &"not found!".to_owned())), response.as_failure());
+    assert_eq!((404, "not found!".to_owned()), response.into_failure());
+}
+
+#[test]
+#[should_panic(expected = "`into_success` called on the wrong variant, expected `Success`, found `Pending`")]
+fn forwarded_into_wrong_variant() {
+    Response(Inner::Pending).into_success();
+}
+
+mod options {
+    use variation::Variation;
+
+    #[derive(Debug, PartialEq, Variation)]
+    #[variation(into_mode = "result", prefix(as = "get_"))]
+    pub enum Token<T> {
+        Word(T),
+        #[variation(rename = "Num")]
+        Number(i64),
+        #[variation(skip)]
+        End,
+    }
+
+    #[derive(Debug, PartialEq, Variation)]
+    #[variation(
+        into_mode = "result",
+        prefix(as = "get_"),
+        vis(as_mut = "pub(crate)"),
+        variants(Word(T), #[variation(rename = "Num")] Number(i64), #[variation(skip)] End)
+    )]
+    pub struct Wrapped<T>(pub Token<T>);
+}
+
+use options::{Token, Wrapped};
+
+#[test]
+fn forwarded_options() {
+    let mut word = Wrapped(Token::Word("a"));
+
+    assert_eq!(Some(&"a"), word.get_word());
+    assert!(word.as_word_mut().is_some());
+    assert!(Wrapped::<&str>(Token::Number(1)).is_num());
+    assert_eq!(Ok("a"), word.into_word());
+    assert_eq!(Err(Wrapped(Token::End)), Wrapped::<&str>(Token::End).into_num());
+}
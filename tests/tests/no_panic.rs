@@ -0,0 +1,37 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(no_panic, into_mode = "result")]
+enum Type {
+    Integer(i32),
+    #[variation(into_mode = "option")]
+    Real(f64),
+    Unit,
+}
+
+#[test]
+fn no_panic_modes() {
+    assert_eq!(Ok(1), Type::Integer(1).into_integer());
+    assert_eq!(Err(Type::Unit), Type::Unit.into_integer());
+    assert_eq!(Some(1.5), Type::Real(1.5).into_real());
+    assert_eq!(None, Type::Integer(1).into_real());
+}
+
+#[derive(Variation)]
+#[variation(no_panic)]
+enum Partial {
+    Integer(i32),
+    #[variation(into_mode = "result")]
+    Text(String),
+}
+
+#[test]
+fn no_panic_without_mode() {
+    let mut value = Partial::Integer(1);
+
+    *value.as_integer_mut().unwrap() += 1;
+
+    assert!(value.is_integer());
+    assert_eq!(Some(&2), value.as_integer());
+    assert_eq!(Ok("a".to_owned()), Partial::Text("a".to_owned()).into_text().map_err(|_| ()));
+}
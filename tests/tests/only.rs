@@ -0,0 +1,48 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(only(Integer, Text, Flag), kind, drain)]
+enum Value {
+    Integer(i64),
+    Real(f64),
+    #[variation(skip(into))]
+    Text(String),
+    #[variation(skip)]
+    Flag(bool),
+    Null,
+}
+
+// Not listed, so these don't clash with generated methods.
+impl Value {
+    fn is_real(&self) -> bool {
+        true
+    }
+
+    fn is_null(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn only_listed() {
+    let mut text = Value::Text("a".to_owned());
+
+    if let Some(text) = text.as_text_mut() {
+        text.push('b');
+    }
+
+    assert!(Value::Integer(1).is_integer());
+    assert_eq!(Some(&"ab".to_owned()), text.as_text());
+    assert_eq!(1, Value::Integer(1).into_integer());
+}
+
+#[test]
+fn only_whole_enum() {
+    let mut values = vec![Value::Integer(1), Value::Real(1.5), Value::Flag(true), Value::Null];
+
+    assert!(Value::Integer(1).is_real());
+    assert!(Value::Integer(1).is_null());
+    assert_eq!(ValueKind::Real, values[1].kind());
+    assert_eq!(vec![1], Value::drain_integers(&mut values));
+    assert_eq!(vec![Value::Real(1.5), Value::Flag(true), Value::Null], values);
+}
@@ -0,0 +1,61 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(option_ext)]
+enum Type {
+    Unit,
+    Integer(i32),
+    Real(i32, u32),
+}
+
+#[test]
+fn option_is() {
+    assert!(Some(Type::Unit).is_unit());
+    assert!(Some(Type::Integer(1)).is_integer());
+    assert!(!Some(Type::Integer(1)).is_real());
+    assert!(!None::<Type>.is_integer());
+    assert!(Some(&Type::Real(3, 14)).is_real());
+    assert!(!None::<&Type>.is_unit());
+}
+
+#[test]
+fn option_as() {
+    let value = Some(Type::Real(3, 14));
+
+    assert_eq!(Some((&3, &14)), value.as_real());
+    assert_eq!(None, value.as_integer());
+    assert_eq!(None, None::<Type>.as_real());
+    assert_eq!(Some(&1), Some(&Type::Integer(1)).as_integer());
+    assert_eq!(None, None::<&Type>.as_integer());
+}
+
+#[test]
+fn option_into() {
+    assert_eq!(Some(1), Some(Type::Integer(1)).into_integer());
+    assert_eq!(None, Some(Type::Unit).into_integer());
+    assert_eq!(None, None::<Type>.into_real());
+
+    let values = [Type::Integer(1), Type::Real(3, 14)];
+
+    assert_eq!(Some(&1), values.first().into_integer());
+    assert_eq!(Some((&3, &14)), values.last().into_real());
+    assert_eq!(None, values.get(2).into_real());
+}
+
+mod private {
+    use variation::Variation;
+
+    #[derive(Variation)]
+    #[variation(option_ext)]
+    pub enum Public<T> {
+        Value(T),
+    }
+}
+
+#[test]
+fn option_visibility() {
+    use private::PublicOptionExt;
+
+    assert_eq!(Some(5), Some(private::Public::Value(5)).into_value());
+    assert_eq!(Some(&5), Some(private::Public::Value(5)).as_value());
+}
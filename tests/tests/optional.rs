@@ -0,0 +1,55 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+enum Cache {
+    #[variation(some)]
+    Hit(String),
+    #[variation(none)]
+    Miss,
+}
+
+#[test]
+fn option_round_trip() {
+    for option in [Some(String::from("value")), None] {
+        assert_eq!(option.clone(), Cache::from(option).into_option());
+    }
+
+    assert_eq!(Cache::Hit(String::from("a")), Cache::from(Cache::Hit(String::from("a")).into_option()));
+    assert_eq!(Cache::Miss, Cache::from(Cache::Miss.into_option()));
+}
+
+#[test]
+fn as_option() {
+    let hit: Cache = Some(String::from("value")).into();
+
+    assert_eq!(Some(&String::from("value")), hit.as_option());
+    assert_eq!(None, Cache::Miss.as_option());
+    assert!(hit.is_hit());
+}
+
+#[test]
+fn unwrap_or() {
+    assert_eq!("value", Cache::Hit(String::from("value")).unwrap_or(String::from("default")));
+    assert_eq!("default", Cache::Miss.unwrap_or(String::from("default")));
+}
+
+#[derive(Debug, PartialEq, Variation)]
+enum Lookup<T> {
+    #[variation(some)]
+    Found(T, usize),
+    Ambiguous(usize),
+    #[variation(none)]
+    Missing,
+}
+
+#[test]
+fn option_other_variants() {
+    let found: Lookup<char> = Some(('a', 3)).into();
+
+    assert_eq!(Lookup::Found('a', 3), found);
+    assert_eq!(Some((&'a', &3)), found.as_option());
+    assert_eq!(None, Lookup::<char>::Ambiguous(2).into_option());
+    assert_eq!(('z', 0), Lookup::Ambiguous(2).unwrap_or(('z', 0)));
+    assert_eq!(Lookup::<char>::Missing, None.into());
+    assert_eq!(Some(&2), Lookup::<char>::Ambiguous(2).as_ambiguous());
+}
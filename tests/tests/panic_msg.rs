@@ -0,0 +1,58 @@
+use std::panic;
+
+use variation::Variation;
+
+// Large on purpose, as the values are meant to be kept boxed.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Variation)]
+#[variation(boxed)]
+enum Literal {
+    #[variation(panic_msg = "expected an integer literal")]
+    Integer(i64),
+    Text(String),
+    Bytes([u8; 512]),
+}
+
+fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+    let payload = panic::catch_unwind(f).unwrap_err();
+
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast::<&str>().unwrap().to_string(),
+    }
+}
+
+#[test]
+fn panic_msg_into() {
+    let message = panic_message(|| {
+        Literal::Text("x".to_owned()).into_integer();
+    });
+
+    assert_eq!("expected an integer literal, found `Text`", message);
+}
+
+#[test]
+fn panic_msg_into_boxed() {
+    let message = panic_message(|| {
+        Box::new(Literal::Bytes([0; 512])).into_integer_boxed();
+    });
+
+    assert_eq!("expected an integer literal, found `Bytes`", message);
+}
+
+#[test]
+fn panic_msg_default() {
+    let message = panic_message(|| {
+        Literal::Integer(1).into_text();
+    });
+
+    assert_eq!("`into_text` called on the wrong variant, expected `Text`, found `Integer`", message);
+
+    let message = panic_message(|| {
+        Box::new(Literal::Text("x".to_owned())).into_bytes_boxed();
+    });
+
+    assert_eq!("`into_bytes_boxed` called on the wrong variant, expected `Bytes`, found `Text`", message);
+    assert_eq!(1, Literal::Integer(1).into_integer());
+    assert_eq!([0; 512], Box::new(Literal::Bytes([0; 512])).into_bytes_boxed());
+}
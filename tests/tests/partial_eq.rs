@@ -0,0 +1,56 @@
+use variation::Variation;
+
+#[derive(Debug, Variation)]
+enum Type {
+    Unit,
+    #[variation(partial_eq)]
+    Integer(i32),
+    #[variation(partial_eq)]
+    Text(String),
+    Real(i32, u32),
+}
+
+#[test]
+fn partial_eq_inner() {
+    assert_eq!(Type::Integer(5), 5);
+    assert_ne!(Type::Integer(4), 5);
+    assert_ne!(Type::Unit, 5);
+    assert_ne!(Type::Real(5, 0), 5);
+    assert_eq!(Type::Text(String::from("text")), String::from("text"));
+}
+
+#[test]
+fn partial_eq_reflected() {
+    assert_eq!(5, Type::Integer(5));
+    assert_ne!(5, Type::Text(String::from("5")));
+    assert_ne!(String::new(), Type::Unit);
+    assert_eq!(Some((&1, &2)), Type::Real(1, 2).as_real());
+}
+
+#[derive(Debug, Variation)]
+enum Generic<T> {
+    #[variation(partial_eq)]
+    Value(T),
+    Empty,
+}
+
+#[test]
+fn partial_eq_generic() {
+    assert_eq!(Generic::Value(1.5), 1.5);
+    assert_ne!(Generic::Empty, 1.5);
+}
+
+#[derive(Debug, Variation)]
+enum Wrapped<'a, T> {
+    #[variation(partial_eq)]
+    Boxed(Box<T>),
+    #[variation(partial_eq)]
+    Borrowed(&'a T),
+}
+
+#[test]
+fn partial_eq_generic_wrapped() {
+    assert_eq!(Wrapped::Boxed(Box::new(1)), Box::new(1));
+    assert_ne!(Wrapped::Borrowed(&1), Box::new(1));
+    assert_eq!(Wrapped::Borrowed(&2), &2);
+}
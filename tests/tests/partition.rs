@@ -0,0 +1,55 @@
+use variation::Variation;
+
+#[derive(Debug, Variation)]
+#[variation(partition)]
+enum Token {
+    Unit,
+    Integer(i32),
+    Real(i32, u32),
+    Type(String),
+}
+
+#[test]
+fn partition_variants() {
+    let values = vec![
+        Token::Real(3, 14),
+        Token::Integer(1),
+        Token::Unit,
+        Token::Type(String::from("type")),
+        Token::Integer(2),
+        Token::Unit,
+    ];
+    let partition = Token::partition_variants(values);
+
+    assert_eq!(2, partition.unit);
+    assert_eq!(vec![1, 2], partition.integer);
+    assert_eq!(vec![(3, 14)], partition.real);
+    assert_eq!(vec![String::from("type")], partition.r#type);
+}
+
+#[test]
+fn partition_default() {
+    let partition = TokenPartition::default();
+
+    assert_eq!(0, partition.unit);
+    assert!(partition.integer.is_empty());
+    assert_eq!(
+        r#"TokenPartition { unit: 0, integer: [], real: [], type: [] }"#,
+        format!("{:?}", partition)
+    );
+}
+
+#[derive(Variation)]
+#[variation(partition)]
+enum Generic<T> {
+    Value(T),
+    Empty,
+}
+
+#[test]
+fn partition_generic() {
+    let partition = Generic::partition_variants(vec![Generic::Value("a"), Generic::Empty, Generic::Value("b")]);
+
+    assert_eq!(vec!["a", "b"], partition.value);
+    assert_eq!(1, partition.empty);
+}
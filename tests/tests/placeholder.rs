@@ -0,0 +1,85 @@
+use variation::Variation;
+
+#[variation::placeholder]
+#[derive(Debug, PartialEq, Variation)]
+#[variation(kind, count_kinds)]
+enum Connection {
+    Idle,
+    Open(String),
+    Pair(u8, u8),
+}
+
+#[test]
+fn take() {
+    let mut open = Connection::Open("a".to_owned());
+    let mut pair = Connection::Pair(1, 2);
+
+    assert_eq!(Some("a".to_owned()), open.take_open());
+    assert_eq!(None, open.take_open());
+    assert_eq!(Some((1, 2)), pair.take_pair());
+}
+
+#[test]
+fn take_wrong_variant() {
+    let mut idle = Connection::Idle;
+
+    assert_eq!(None, idle.take_open());
+    assert_eq!(Connection::Idle, idle);
+}
+
+#[test]
+fn taken_value_has_no_variant() {
+    let mut open = Connection::Open("a".to_owned());
+    open.take_open();
+
+    assert!(!open.is_idle() && !open.is_open() && !open.is_pair());
+    assert_eq!(None, open.as_open());
+    assert_eq!(None, open.as_pair_mut());
+}
+
+#[test]
+fn placeholder_left_out_of_kinds() {
+    let mut taken = Connection::Pair(1, 2);
+    taken.take_pair();
+    let values = [Connection::Idle, Connection::Open("a".to_owned()), taken];
+    let counts = ConnectionKindCounts::from_iter(&values);
+
+    // Only compiles without a kind for the placeholder.
+    let named = |kind: ConnectionKind| match kind {
+        ConnectionKind::Idle | ConnectionKind::Open | ConnectionKind::Pair => true,
+    };
+
+    assert_eq!(2, counts.total());
+    assert_eq!(0, counts.pair());
+    assert!(named(Connection::Idle.kind()));
+}
+
+#[test]
+#[should_panic(expected = "`kind` called on a value taken by `take_*`")]
+fn kind_of_taken_value() {
+    let mut open = Connection::Open("a".to_owned());
+    open.take_open();
+    open.kind();
+}
+
+#[test]
+#[should_panic(expected = "`variant_name` called on a value taken by `take_*`")]
+fn variant_name_of_taken_value() {
+    let mut open = Connection::Open("a".to_owned());
+    open.take_open();
+    open.variant_name();
+}
+
+#[variation::placeholder]
+#[derive(Debug, PartialEq, Variation)]
+#[variation(iter)]
+enum Mode {
+    Read,
+    Write,
+}
+
+#[test]
+fn placeholder_left_out_of_iter() {
+    assert_eq!([Mode::Read, Mode::Write], Mode::VARIANTS);
+    assert_eq!(2, Mode::iter().count());
+}
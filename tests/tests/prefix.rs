@@ -0,0 +1,54 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(prefix(as = "get_", as_mut = "get_mut_", into = "take_"), option_ext)]
+enum Slot {
+    #[variation(alias = "full")]
+    Filled(String),
+    #[variation(into = "into_count")]
+    Counted(u32),
+    Empty,
+}
+
+#[test]
+#[allow(deprecated)]
+fn prefixes() {
+    let mut slot = Slot::Filled("a".to_owned());
+
+    assert!(slot.is_filled());
+    assert!(Slot::Empty.is_empty());
+
+    if let Some(value) = slot.get_mut_filled() {
+        value.push('b');
+    }
+
+    assert_eq!(Some(&"ab".to_owned()), slot.get_filled());
+    assert_eq!(Some(&"ab".to_owned()), slot.get_full());
+    assert_eq!(None, Slot::Empty.get_counted());
+    assert_eq!(2, Slot::Counted(2).into_count());
+    assert_eq!("ab", slot.take_full());
+}
+
+#[test]
+fn prefixes_option_ext() {
+    assert_eq!(Some(&1), Some(Slot::Counted(1)).get_counted());
+    assert_eq!(Some("a".to_owned()), Some(Slot::Filled("a".to_owned())).take_filled());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(prefix(as = ""))]
+enum Value {
+    Text(String),
+    Number(i64),
+}
+
+#[test]
+fn empty_prefix() {
+    let text = Value::Text("a".to_owned());
+
+    assert_eq!(Some(&"a".to_owned()), text.text());
+    assert_eq!(None, text.number());
+    assert_eq!(Some(&1), Value::Number(1).number());
+    assert!(text.is_text());
+    assert_eq!(1, Value::Number(1).into_number());
+}
@@ -0,0 +1,215 @@
+use std::future::{self, Future};
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(ref_enum, mut_enum, companion_derive(Debug))]
+enum Type {
+    Unit,
+    Integer(i32),
+    Real(i32, u32),
+    Entry(String),
+}
+
+fn describe(value: &Type) -> String {
+    match value.as_ref() {
+        TypeRef::Unit => String::from("unit"),
+        TypeRef::Integer(i) => format!("integer {}", i),
+        TypeRef::Real(i, f) => format!("real {}.{}", i, f),
+        TypeRef::Entry(entry) => format!("entry {}", entry),
+    }
+}
+
+#[test]
+fn ref_enum_match() {
+    assert_eq!("unit", describe(&Type::Unit));
+    assert_eq!("integer 1", describe(&Type::Integer(1)));
+    assert_eq!("real 3.14", describe(&Type::Real(3, 14)));
+    assert_eq!("entry key", describe(&Type::Entry(String::from("key"))));
+}
+
+#[test]
+fn ref_enum_borrows() {
+    let value = Type::Entry(String::from("key"));
+
+    match value.as_ref() {
+        TypeRef::Entry(entry) => assert!(std::ptr::eq(entry, value.as_entry().unwrap())),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn ref_enum_traits() {
+    let value = Type::Real(3, 14);
+    let projected = value.as_ref();
+    let copied = projected;
+
+    assert_eq!("Real(3, 14)", format!("{:?}", projected));
+    assert_eq!(format!("{:?}", projected.clone()), format!("{:?}", copied));
+}
+
+struct NotClone(u8);
+
+// `Clone` is implemented by hand, without requiring `T: Clone`.
+#[derive(Variation)]
+#[variation(ref_enum, companion_derive(Clone))]
+enum Generic<T> {
+    Value(T),
+    Empty,
+}
+
+#[test]
+fn ref_enum_generic() {
+    let value = Generic::Value(NotClone(1));
+    let projected = value.as_ref();
+    let copied = projected;
+
+    assert!(matches!(copied, GenericRef::Value(&NotClone(1))));
+    assert!(matches!(Generic::<NotClone>::Empty.as_ref(), GenericRef::Empty));
+    assert!(value.is_value());
+}
+
+fn increment(value: &mut Type) {
+    match value.as_mut() {
+        TypeMut::Unit => {}
+        TypeMut::Integer(i) => *i += 1,
+        TypeMut::Real(i, f) => {
+            *i += 1;
+            *f += 1;
+        }
+        TypeMut::Entry(entry) => entry.push('\''),
+    }
+}
+
+#[test]
+fn mut_enum_mutates() {
+    let mut values = [Type::Unit, Type::Integer(1), Type::Real(3, 14), Type::Entry(String::from("x"))];
+
+    for value in &mut values {
+        increment(value);
+    }
+
+    assert_eq!(
+        [Type::Unit, Type::Integer(2), Type::Real(4, 15), Type::Entry(String::from("x'"))],
+        values
+    );
+}
+
+#[test]
+fn mut_enum_debug() {
+    let mut value = Type::Integer(5);
+
+    assert_eq!("Integer(5)", format!("{:?}", value.as_mut()));
+    assert!(matches!(Type::Unit.as_mut(), TypeMut::Unit));
+}
+
+#[derive(Variation)]
+#[variation(pin_project)]
+enum State<F> {
+    Running(#[variation(pin)] F, u32),
+    Done(u32),
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// Polls the future in `state` until it's done, counting the polls.
+fn run<F: Future<Output = u32>>(state: State<F>) -> (u32, u32) {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    let mut state = Box::pin(state);
+
+    loop {
+        let output = match state.as_mut().project() {
+            StateProj::Running(future, polls) => {
+                *polls += 1;
+
+                match future.poll(&mut cx) {
+                    Poll::Ready(output) => (output, *polls),
+                    Poll::Pending => continue,
+                }
+            }
+            StateProj::Done(output) => return (*output, 0),
+        };
+
+        state.set(State::Done(output.0));
+        return output;
+    }
+}
+
+#[test]
+fn pin_project_polls() {
+    let future = async {
+        let mut yielded = false;
+
+        future::poll_fn(|cx| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await;
+
+        7
+    };
+
+    assert_eq!((7, 2), run(State::Running(future, 0)));
+    assert_eq!((3, 0), run(State::<future::Ready<u32>>::Done(3)));
+    assert!(State::<()>::Done(0).is_done());
+}
+
+#[derive(Variation)]
+#[variation(pin_project)]
+enum Guarded<F> {
+    Pinned(#[variation(pin)] F),
+    Unpinned(PhantomPinned),
+}
+
+#[test]
+fn pin_project_unpin() {
+    // Only the pinned fields have to be `Unpin` for `Pin::new`.
+    let mut value = Guarded::Pinned(1_u8);
+
+    match Pin::new(&mut value).project() {
+        GuardedProj::Pinned(inner) => *inner.get_mut() += 1,
+        GuardedProj::Unpinned(_) => unreachable!(),
+    }
+
+    assert!(matches!(value, Guarded::Pinned(2)));
+    assert!(!Guarded::<u8>::Unpinned(PhantomPinned).is_pinned());
+}
+
+struct Opaque(u8);
+
+#[derive(Variation)]
+#[variation(ref_enum, mut_enum, pin_project)]
+enum Handle {
+    Opaque(#[variation(pin)] Opaque),
+    Closed,
+}
+
+#[test]
+fn projections_without_debug() {
+    let mut handle = Handle::Opaque(Opaque(1));
+
+    if let HandleMut::Opaque(opaque) = handle.as_mut() {
+        opaque.0 += 1;
+    }
+
+    match Pin::new(&mut handle).project() {
+        HandleProj::Opaque(opaque) => opaque.get_mut().0 += 1,
+        HandleProj::Closed => unreachable!(),
+    }
+
+    assert!(matches!(handle.as_ref(), HandleRef::Opaque(&Opaque(3))));
+    assert!(matches!(Handle::Closed.as_ref(), HandleRef::Closed));
+}
@@ -0,0 +1,50 @@
+use variation::Variation;
+
+#[derive(Debug, Variation)]
+enum Widget {
+    #[variation(props(label = "Name", width = "20"))]
+    Text(String),
+    #[variation(props(label = "Enabled"), props(r#type = "checkbox"))]
+    Toggle(bool),
+    Separator,
+}
+
+#[test]
+fn props_present() {
+    let text = Widget::Text(String::from("x"));
+
+    assert_eq!(Some("Name"), text.get_property("label"));
+    assert_eq!(Some("20"), text.get_property("width"));
+    assert_eq!(Some("checkbox"), Widget::Toggle(true).get_property("type"));
+    assert_eq!(Some("x"), text.as_text().map(String::as_str));
+    assert_eq!(Some(&true), Widget::Toggle(true).as_toggle());
+}
+
+#[test]
+fn props_absent() {
+    assert_eq!(None, Widget::Text(String::new()).get_property("type"));
+    assert_eq!(None, Widget::Toggle(false).get_property("width"));
+    assert_eq!(None, Widget::Separator.get_property("label"));
+    assert_eq!(None, Widget::Toggle(false).get_property("Enabled"));
+}
+
+#[test]
+fn props_in_order() {
+    assert_eq!(&[("label", "Name"), ("width", "20")], Widget::Text(String::new()).properties());
+    assert_eq!(&[("label", "Enabled"), ("type", "checkbox")], Widget::Toggle(false).properties());
+    assert!(Widget::Separator.properties().is_empty());
+}
+
+#[derive(Variation)]
+enum Level {
+    #[variation(props(rank = "1"))]
+    Low,
+    #[variation(props(rank = "2"))]
+    High,
+}
+
+#[test]
+fn props_every_variant() {
+    assert_eq!(Some("1"), Level::Low.get_property("rank"));
+    assert_eq!(Some("2"), Level::High.get_property("rank"));
+}
@@ -0,0 +1,169 @@
+use std::convert::TryFrom;
+use std::pin::Pin;
+
+use variation::Variation;
+
+use expr_markers::Variant;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(ref_enum, mut_enum, pin_project, visitor, markers, iterator_ext, option_ext, partition)]
+#[variation(subset(name = "Compound", variants(Neg, Add, List), derive(Debug, PartialEq)))]
+enum Expr {
+    Number(i64),
+    Neg(Box<Self>),
+    Add(Box<Self>, Box<Self>),
+    List(Vec<Self>),
+}
+
+fn number(n: i64) -> Box<Expr> {
+    Box::new(Expr::Number(n))
+}
+
+fn values() -> Vec<Expr> {
+    vec![
+        Expr::Number(1),
+        Expr::Neg(number(2)),
+        Expr::Add(number(3), number(4)),
+        Expr::List(vec![Expr::Number(5)]),
+    ]
+}
+
+#[test]
+fn recursive_ref_enum() {
+    let sum: i64 = values()
+        .iter()
+        .map(|value| match value.as_ref() {
+            ExprRef::Number(n) => *n,
+            ExprRef::Neg(inner) => inner.as_number().map_or(0, |n| -n),
+            ExprRef::Add(left, right) => left.as_number().unwrap() + right.as_number().unwrap(),
+            ExprRef::List(values) => values.len() as i64,
+        })
+        .sum();
+
+    assert_eq!(7, sum);
+}
+
+#[test]
+fn recursive_mut_enum() {
+    let mut value = Expr::Neg(number(2));
+
+    if let ExprMut::Neg(inner) = value.as_mut() {
+        **inner = Expr::Number(3);
+    }
+
+    assert_eq!(Expr::Neg(number(3)), value);
+}
+
+#[test]
+fn recursive_pin_project() {
+    let mut value = Expr::List(Vec::new());
+
+    if let ExprProj::List(values) = Pin::new(&mut value).project() {
+        values.push(Expr::Number(1));
+    }
+
+    assert_eq!(Expr::List(vec![Expr::Number(1)]), value);
+}
+
+struct Evaluate;
+
+impl ExprVisitor for Evaluate {
+    type Output = i64;
+
+    fn visit_number(&mut self, v0: &i64) -> i64 {
+        *v0
+    }
+
+    fn visit_neg(&mut self, v0: &Box<Expr>) -> i64 {
+        -v0.accept(self)
+    }
+
+    fn visit_add(&mut self, v0: &Box<Expr>, v1: &Box<Expr>) -> i64 {
+        v0.accept(self) + v1.accept(self)
+    }
+
+    fn visit_list(&mut self, v0: &Vec<Expr>) -> i64 {
+        v0.iter().map(|value| value.accept(self)).sum()
+    }
+}
+
+#[test]
+fn recursive_visitor() {
+    let value = Expr::List(values());
+
+    assert_eq!(11, value.accept(&mut Evaluate));
+}
+
+#[test]
+fn recursive_markers() {
+    let mut value = Expr::Neg(number(2));
+
+    assert_eq!(Some(&number(2)), value.get::<expr_markers::Neg>());
+    assert_eq!(None, value.get::<expr_markers::List>());
+
+    *value.get_mut::<expr_markers::Neg>().unwrap() = number(3);
+
+    assert_eq!(Ok(number(3)), value.into_variant::<expr_markers::Neg>());
+    assert_eq!(Some(&Vec::new()), expr_markers::List::get(&Expr::List(Vec::new())));
+}
+
+#[test]
+fn recursive_iterator_ext() {
+    assert_eq!(vec![number(2)], values().into_iter().negs().collect::<Vec<_>>());
+    assert_eq!(vec![(&number(3), &number(4))], values().iter().adds().collect::<Vec<_>>());
+}
+
+#[test]
+fn recursive_option_ext() {
+    assert_eq!(Some(&number(2)), Some(Expr::Neg(number(2))).as_neg());
+    assert_eq!(Some(vec![Expr::Number(5)]), values().pop().into_list());
+}
+
+#[test]
+fn recursive_partition() {
+    let partition = Expr::partition_variants(values());
+
+    assert_eq!(vec![1], partition.number);
+    assert_eq!(vec![number(2)], partition.neg);
+    assert_eq!(vec![(number(3), number(4))], partition.add);
+    assert_eq!(vec![vec![Expr::Number(5)]], partition.list);
+}
+
+#[test]
+fn recursive_subset() {
+    let compound = Compound::Add(number(3), number(4));
+
+    assert_eq!(Expr::Add(number(3), number(4)), Expr::from(compound));
+    assert_eq!(Ok(Compound::Neg(number(2))), Compound::try_from(Expr::Neg(number(2))));
+    assert_eq!(Err(Expr::Number(1)), Compound::try_from(Expr::Number(1)));
+}
+
+trait Weighted {
+    type Weight;
+}
+
+impl<T> Weighted for Tree<T> {
+    type Weight = u32;
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(ref_enum, markers, partition)]
+#[variation(subset(name = "Branch", variants(Node, Weight), derive(Debug, PartialEq)))]
+enum Tree<T> {
+    Leaf(T),
+    Node(Vec<Self>),
+    Weight(<Self as Weighted>::Weight),
+}
+
+#[test]
+fn recursive_generic() {
+    let tree = Tree::Node(vec![Tree::Leaf('a'), Tree::Weight(2)]);
+
+    match tree.as_ref() {
+        TreeRef::Node(children) => assert_eq!(Some(&2), children[1].get::<tree_markers::Weight>()),
+        _ => unreachable!(),
+    }
+
+    assert_eq!(Ok(Branch::Node(vec![Tree::Leaf('a'), Tree::Weight(2)])), Branch::try_from(tree));
+    assert_eq!(vec![1], Tree::<char>::partition_variants(vec![Tree::Weight(1)]).weight);
+}
@@ -0,0 +1,61 @@
+use std::io;
+
+use remote::{Status, Tree};
+use variation::variation_remote;
+
+variation_remote! {
+    #[variation(remote = "remote::Status")]
+    enum Status {
+        Ready,
+        Failed(io::Error),
+    }
+}
+
+variation_remote! {
+    #[variation(remote = "remote::Tree", trait = "TreeExt", into_mode = "result")]
+    enum Tree<T> {
+        Leaf(T),
+        #[variation(rename = "Branch")]
+        Node(Box<Tree<T>>, Box<Tree<T>>),
+    }
+}
+
+#[test]
+fn remote_accessors() {
+    let mut failed = Status::Failed(io::Error::other("failed"));
+
+    assert!(Status::Ready.is_ready());
+    assert!(failed.is_failed());
+    assert_eq!(Some(io::ErrorKind::Other), failed.as_failed().map(io::Error::kind));
+    assert!(failed.as_failed_mut().is_some());
+    assert_eq!("failed", failed.into_failed().to_string());
+}
+
+#[test]
+#[should_panic(expected = "`into_failed` called on the wrong variant, expected `Failed`, found `Ready`")]
+fn remote_into_wrong_variant() {
+    Status::Ready.into_failed();
+}
+
+#[test]
+fn remote_options() {
+    let leaf = Tree::Leaf(1);
+    let node = Tree::Node(Box::new(Tree::Leaf(1)), Box::new(Tree::Leaf(2)));
+
+    assert_eq!(Some(&1), leaf.as_leaf());
+    assert!(node.is_branch());
+    assert_eq!(Ok(1), leaf.into_leaf());
+    assert_eq!(Err(Tree::Leaf(2)), Tree::Leaf(2).into_branch());
+    assert_eq!(Some(&Tree::Leaf(2)), node.as_branch().map(|(_, right)| &**right));
+}
+
+fn leaves<T: TreeExt<u8>>(trees: &[T]) -> usize {
+    trees.iter().filter(|tree| tree.is_leaf()).count()
+}
+
+#[test]
+fn remote_generic_code() {
+    let trees = [Tree::Leaf(1), Tree::Node(Box::new(Tree::Leaf(2)), Box::new(Tree::Leaf(3)))];
+
+    assert_eq!(1, leaves(&trees));
+}
@@ -0,0 +1,126 @@
+use std::str::FromStr;
+
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(kind, count_kinds, tag, drain, visitor)]
+enum Status {
+    #[variation(rename = "tls_up")]
+    HTTPSConnectionEstablished(u16),
+    #[variation(rename = "fallback", tag = "down")]
+    Plain(u16),
+    Down,
+}
+
+#[test]
+fn rename_methods() {
+    let mut status = Status::HTTPSConnectionEstablished(443);
+
+    assert!(status.is_tls_up());
+    assert!(!status.is_fallback());
+
+    if let Some(port) = status.as_tls_up_mut() {
+        *port += 1;
+    }
+
+    assert_eq!(Some(&444), status.as_tls_up());
+    assert_eq!(444, status.into_tls_up());
+    assert_eq!(80, Status::Plain(80).into_fallback());
+}
+
+#[test]
+fn rename_names() {
+    let status = Status::HTTPSConnectionEstablished(443);
+    let statuses = vec![Status::Plain(80), Status::Down, Status::Plain(8080)];
+    let counts = StatusKindCounts::from_iter(&statuses);
+
+    assert_eq!(StatusKind::TlsUp, status.kind());
+    assert_eq!(StatusKind::Fallback, Status::Plain(80).kind());
+    assert_eq!("tls_up", status.variant_name());
+    assert_eq!("tls_up", status.tag());
+    assert_eq!("down", Status::Plain(80).tag());
+    assert_eq!(2, counts.fallback());
+    assert_eq!(0, counts.tls_up());
+}
+
+struct Port;
+
+impl StatusVisitor for Port {
+    type Output = u16;
+
+    fn visit_tls_up(&mut self, v0: &u16) -> u16 {
+        *v0
+    }
+
+    fn visit_fallback(&mut self, v0: &u16) -> u16 {
+        *v0
+    }
+
+    fn visit_down(&mut self) -> u16 {
+        0
+    }
+}
+
+#[test]
+fn rename_derived_methods() {
+    let mut statuses = vec![Status::Plain(80), Status::Down, Status::HTTPSConnectionEstablished(443)];
+
+    assert_eq!(vec![80], Status::drain_fallbacks(&mut statuses));
+    assert_eq!(vec![0, 443], statuses.iter().map(|status| status.accept(&mut Port)).collect::<Vec<_>>());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Variation)]
+#[variation(as_str, from_str)]
+enum Level {
+    #[variation(rename = "low")]
+    Minimum,
+    #[variation(rename = "high", str = "max")]
+    Maximum,
+}
+
+#[test]
+fn rename_strings() {
+    assert_eq!("low", Level::Minimum.as_str());
+    assert_eq!("max", Level::Maximum.as_str());
+    assert_eq!(Ok(Level::Minimum), Level::from_str("low"));
+    assert!(Level::from_str("Minimum").is_err());
+    assert!(Level::Maximum.is_high());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(option_ext)]
+enum Message {
+    #[variation(is = "has_payload", into = "take_payload", alias = "data")]
+    Payload(Vec<u8>),
+    #[variation(rename = "heartbeat", as_mut = "interval_mut")]
+    Ping(u32),
+}
+
+#[test]
+#[allow(deprecated)]
+fn method_names() {
+    let mut ping = Message::Ping(5);
+    let payload = Message::Payload(vec![1, 2]);
+
+    assert!(payload.has_payload());
+    assert!(payload.is_data());
+    assert!(!ping.has_payload());
+    assert_eq!(Some(&vec![1, 2]), payload.as_payload());
+
+    if let Some(interval) = ping.interval_mut() {
+        *interval += 1;
+    }
+
+    assert!(ping.is_heartbeat());
+    assert_eq!(Some(&6), ping.as_heartbeat());
+    assert_eq!(6, ping.into_heartbeat());
+    assert_eq!(vec![1, 2], payload.take_payload());
+    assert_eq!(vec![3], Message::Payload(vec![3]).into_data());
+}
+
+#[test]
+fn method_names_option_ext() {
+    assert!(Some(Message::Payload(Vec::new())).has_payload());
+    assert_eq!(Some(vec![1]), Some(Message::Payload(vec![1])).take_payload());
+    assert_eq!(Some(&2), Some(Message::Ping(2)).as_heartbeat());
+}
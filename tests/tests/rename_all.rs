@@ -0,0 +1,67 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(kind, count_kinds)]
+enum Snake {
+    IPv4(u32),
+    HttpsPort(u16),
+}
+
+#[test]
+fn rename_all_default() {
+    let counts = SnakeKindCounts::from_iter(&[Snake::IPv4(1)]);
+
+    assert!(Snake::IPv4(1).is_i_pv4());
+    assert_eq!(Some(&443), Snake::HttpsPort(443).as_https_port());
+    assert_eq!(1, counts.i_pv4());
+    assert_eq!(0, counts.https_port());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(rename_all = "snake_case")]
+enum ExplicitSnake {
+    HttpsPort(u16),
+}
+
+#[test]
+fn rename_all_snake_case() {
+    assert_eq!(443, ExplicitSnake::HttpsPort(443).into_https_port());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(rename_all = "lowercase", drain)]
+enum Lower {
+    IPv4(u32),
+    #[variation(rename = "V6Address")]
+    IPv6(u128),
+    HttpsPort(u16),
+}
+
+#[test]
+fn rename_all_lowercase() {
+    let mut values = vec![Lower::IPv4(1), Lower::HttpsPort(443)];
+
+    assert!(Lower::IPv4(1).is_ipv4());
+    assert_eq!(Some(&2), Lower::IPv6(2).as_v6address());
+    assert_eq!(443, Lower::HttpsPort(443).into_httpsport());
+    assert_eq!(vec![1], Lower::drain_ipv4s(&mut values));
+    assert_eq!(vec![Lower::HttpsPort(443)], values);
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Variation)]
+#[variation(rename_all = "verbatim", partition)]
+enum Verbatim {
+    utf8_bom(u8),
+    x86_64(u64),
+}
+
+#[test]
+fn rename_all_verbatim() {
+    let partition = Verbatim::partition_variants(vec![Verbatim::utf8_bom(1), Verbatim::x86_64(2)]);
+
+    assert!(Verbatim::utf8_bom(1).is_utf8_bom());
+    assert_eq!(Some(&2), Verbatim::x86_64(2).as_x86_64());
+    assert_eq!(vec![1], partition.utf8_bom);
+    assert_eq!(vec![2], partition.x86_64);
+}
@@ -0,0 +1,126 @@
+use variation::Variation;
+
+#[derive(Clone, Copy, Debug, PartialEq, Variation)]
+#[repr(u8)]
+#[variation(repr)]
+enum Opcode {
+    Add = 1,
+    Sub = 2,
+    Jump = 0x10,
+    Halt,
+}
+
+#[test]
+fn from_repr() {
+    assert_eq!(Some(Opcode::Add), Opcode::from_repr(1));
+    assert_eq!(Some(Opcode::Sub), Opcode::from_repr(2));
+    assert_eq!(Some(Opcode::Jump), Opcode::from_repr(0x10));
+    assert_eq!(Some(Opcode::Halt), Opcode::from_repr(0x11));
+    assert_eq!(None, Opcode::from_repr(0));
+    assert_eq!(None, Opcode::from_repr(3));
+    assert_eq!(None, Opcode::from_repr(u8::MAX));
+}
+
+#[test]
+fn to_repr() {
+    assert_eq!(1, Opcode::Add.to_repr());
+    assert_eq!(2, Opcode::Sub.to_repr());
+    assert_eq!(0x10, Opcode::Jump.to_repr());
+    assert_eq!(0x11, Opcode::Halt.to_repr());
+}
+
+#[test]
+fn repr_round_trip() {
+    for &opcode in &[Opcode::Add, Opcode::Sub, Opcode::Jump, Opcode::Halt] {
+        assert_eq!(Some(opcode), Opcode::from_repr(opcode.to_repr()));
+    }
+
+    for value in 0..=u8::MAX {
+        if let Some(opcode) = Opcode::from_repr(value) {
+            assert_eq!(value, opcode.to_repr());
+        }
+    }
+}
+
+const BASE: i16 = -300;
+
+#[derive(Debug, PartialEq, Variation)]
+#[repr(i16)]
+#[variation(repr)]
+enum Offset {
+    Low = BASE,
+    Zero = 0,
+    High = 300,
+}
+
+#[test]
+fn from_repr_signed() {
+    assert_eq!(Some(Offset::Low), Offset::from_repr(-300));
+    assert_eq!(Some(Offset::Zero), Offset::from_repr(0));
+    assert_eq!(Some(Offset::High), Offset::from_repr(300));
+    assert_eq!(None, Offset::from_repr(1));
+}
+
+#[test]
+fn repr_round_trip_signed() {
+    for offset in [Offset::Low, Offset::Zero, Offset::High] {
+        let value = offset.to_repr();
+        assert_eq!(Some(offset), Offset::from_repr(value));
+    }
+
+    assert_eq!(-300, Offset::Low.to_repr());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[repr(u16)]
+#[variation(try_from_repr)]
+enum Status {
+    Ok = 200,
+    NotFound = 404,
+    Teapot = 418,
+}
+
+#[test]
+fn try_from_repr() {
+    use std::convert::{TryFrom, TryInto};
+
+    assert_eq!(Ok(Status::Ok), Status::try_from(200));
+    assert_eq!(Ok(Status::NotFound), 404u16.try_into());
+    assert_eq!(Ok(Status::Teapot), Status::try_from(418));
+    assert_eq!(Some(Status::Teapot), Status::from_repr(Status::Teapot.to_repr()));
+
+    let error = Status::try_from(201).unwrap_err();
+    assert_eq!(201, error.value());
+    assert_eq!("`201` is not a discriminant of `Status`", error.to_string());
+
+    let error: Box<dyn std::error::Error> = Box::new(Status::try_from(0).unwrap_err());
+    assert_eq!("`0` is not a discriminant of `Status`", error.to_string());
+}
+
+fn parse<T: std::convert::TryFrom<u16>>(value: u16) -> Option<T> {
+    T::try_from(value).ok()
+}
+
+#[test]
+fn try_from_repr_generic_bound() {
+    assert_eq!(Some(Status::NotFound), parse(404));
+    assert_eq!(None, parse::<Status>(405));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Variation)]
+#[repr(u8)]
+#[variation(repr)]
+enum Presence {
+    None,
+    Some,
+    r#Maybe,
+}
+
+#[test]
+fn variants_named_like_option() {
+    assert_eq!(Some(Presence::None), Presence::from_repr(0));
+    assert_eq!(Some(Presence::Some), Presence::from_repr(1));
+    assert_eq!(Some(Presence::Maybe), Presence::from_repr(2));
+    assert_eq!(None, Presence::from_repr(3));
+    assert_eq!(1, Presence::Some.to_repr());
+}
@@ -0,0 +1,184 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+enum Outcome {
+    #[variation(ok)]
+    Success(u32),
+    #[variation(err)]
+    Failure(String),
+    Pending,
+}
+
+#[test]
+fn ok_and_err() {
+    assert_eq!(Some(1), Outcome::Success(1).ok());
+    assert_eq!(None, Outcome::Success(1).err());
+    assert_eq!(Some(String::from("error")), Outcome::Failure(String::from("error")).err());
+    assert_eq!(None, Outcome::Failure(String::from("error")).ok());
+    assert_eq!(None, Outcome::Pending.ok());
+    assert_eq!(None, Outcome::Pending.err());
+}
+
+#[test]
+fn as_ok_and_as_err() {
+    let success = Outcome::Success(1);
+    let failure = Outcome::Failure(String::from("error"));
+
+    assert_eq!(Some(&1), success.as_ok());
+    assert_eq!(None, success.as_err());
+    assert_eq!(Some(&String::from("error")), failure.as_err());
+    assert_eq!(None, failure.as_ok());
+}
+
+#[test]
+fn map_ok_and_map_err() {
+    let double = |value| value * 2;
+    let shout = |error: String| error.to_uppercase();
+
+    assert_eq!(Outcome::Success(2), Outcome::Success(1).map_ok(double));
+    assert_eq!(Outcome::Success(1), Outcome::Success(1).map_err(shout));
+    assert_eq!(
+        Outcome::Failure(String::from("ERROR")),
+        Outcome::Failure(String::from("error")).map_err(shout)
+    );
+    assert_eq!(
+        Outcome::Failure(String::from("error")),
+        Outcome::Failure(String::from("error")).map_ok(double)
+    );
+    assert_eq!(Outcome::Pending, Outcome::Pending.map_ok(double).map_err(shout));
+}
+
+#[derive(Debug, PartialEq, Variation)]
+enum Parsed {
+    #[variation(ok)]
+    Value(i32, usize),
+    #[variation(err)]
+    Error(usize),
+}
+
+#[test]
+fn multiple_fields() {
+    let value = Parsed::Value(5, 1).map_ok(|(value, position)| (value + 1, position));
+
+    assert_eq!(Some((&6, &1)), value.as_ok());
+    assert_eq!(Some((6, 1)), value.ok());
+    assert_eq!(Some(3), Parsed::Error(3).err());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+enum Std {
+    #[variation(ok)]
+    Ok(u8),
+    #[variation(err)]
+    Err(()),
+}
+
+#[test]
+fn named_like_result() {
+    assert!(Std::Ok(1).is_ok());
+    assert_eq!(Some(&1), Std::Ok(1).as_ok());
+    assert_eq!(Some(()), Std::Err(()).err());
+}
+
+#[derive(Debug, PartialEq, Variation)]
+enum Response {
+    #[variation(ok)]
+    Data(Vec<u8>),
+    #[variation(err)]
+    Error(u16),
+}
+
+#[test]
+fn result_round_trip() {
+    let ok: Result<Vec<u8>, u16> = Ok(vec![1]);
+    let err: Result<Vec<u8>, u16> = Err(404);
+
+    assert_eq!(Response::Data(vec![1]), Response::from(ok.clone()));
+    assert_eq!(Response::Error(404), err.clone().into());
+    assert_eq!(ok, Response::from(ok.clone()).into_result());
+    assert_eq!(err, Response::from(err.clone()).into_result());
+}
+
+#[test]
+fn as_result() {
+    assert_eq!(Ok(&vec![1]), Response::Data(vec![1]).as_result());
+    assert_eq!(Err(&404), Response::Error(404).as_result());
+}
+
+fn total(responses: Vec<Response>) -> Result<usize, u16> {
+    let mut total = 0;
+
+    for response in responses {
+        total += response.into_result()?.len();
+    }
+
+    Ok(total)
+}
+
+#[test]
+fn into_result_question_mark_and_collect() {
+    let responses = vec![Response::Data(vec![1, 2]), Response::Data(vec![3])];
+    let collected: Result<Vec<_>, _> = responses.into_iter().map(Response::into_result).collect();
+
+    assert_eq!(Ok(vec![vec![1, 2], vec![3]]), collected);
+    assert_eq!(Ok(3), total(vec![Response::Data(vec![1, 2]), Response::Data(vec![3])]));
+    assert_eq!(Err(500), total(vec![Response::Data(vec![1]), Response::Error(500)]));
+    assert_eq!(Outcome::Failure(String::from("e")), Outcome::from(Err(String::from("e"))));
+}
+
+mod aliased {
+    use variation::Variation;
+
+    // A local alias taking fewer parameters than `std`'s.
+    type Result<T> = std::result::Result<T, super::Status>;
+
+    #[derive(Debug, PartialEq, Variation)]
+    pub enum Lookup {
+        #[variation(ok)]
+        Found(u32),
+        #[variation(err)]
+        Failed(super::Status),
+    }
+
+    pub fn found(lookup: &Lookup) -> Result<&u32> {
+        lookup.as_result().map_err(|status| *status)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Status(u16);
+
+#[test]
+fn result_under_alias() {
+    use aliased::Lookup;
+
+    assert_eq!(Ok(&1), aliased::found(&Lookup::Found(1)));
+    assert_eq!(Err(Status(404)), aliased::found(&Lookup::Failed(Status(404))));
+    assert_eq!(Ok(2), Lookup::Found(2).into_result());
+}
+
+mod glob {
+    use variation::Variation;
+
+    // Shadows `Result::{Ok, Err}` in the generated code.
+    use self::Verdict::*;
+
+    #[derive(Debug, PartialEq, Variation)]
+    pub enum Verdict {
+        #[variation(ok)]
+        Ok(u8),
+        #[variation(err)]
+        Err(()),
+    }
+
+    #[test]
+    fn result_glob_imported() {
+        assert_eq!(Some(1), Ok(1).ok());
+        assert_eq!(None, Ok(1).err());
+        assert_eq!(Some(&()), Err(()).as_err());
+        assert_eq!(Result::Ok(2), Ok(2).into_result());
+        assert_eq!(Result::Err(&()), Err(()).as_result());
+        assert_eq!(Ok(3), Verdict::from(Result::Ok(3)));
+        assert_eq!(Ok(4), Ok(2).map_ok(|value| value * 2));
+    }
+}
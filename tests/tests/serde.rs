@@ -0,0 +1,45 @@
+#![cfg(feature = "serde")]
+
+use variation::Variation;
+
+#[derive(Variation)]
+#[variation(kind(serde))]
+enum Event {
+    Click(u32),
+    #[variation(str = "key_press")]
+    Key(char),
+    #[variation(rename = "closed")]
+    Close,
+}
+
+#[test]
+fn kind_round_trip() {
+    let events = [Event::Click(1), Event::Key('a'), Event::Close];
+
+    for event in &events {
+        let json = serde_json::to_string(&event.kind()).unwrap();
+
+        assert_eq!(event.kind(), serde_json::from_str::<EventKind>(&json).unwrap());
+    }
+
+    assert_eq!(Some(&1), events[0].as_click());
+    assert_eq!(Some(&'a'), events[1].as_key());
+}
+
+#[test]
+fn kind_strings() {
+    assert_eq!("\"Click\"", serde_json::to_string(&EventKind::Click).unwrap());
+    assert_eq!("\"key_press\"", serde_json::to_string(&EventKind::Key).unwrap());
+    assert_eq!("\"closed\"", serde_json::to_string(&EventKind::Closed).unwrap());
+    assert_eq!(
+        vec![EventKind::Key, EventKind::Click],
+        serde_json::from_str::<Vec<EventKind>>(r#"["key_press", "Click"]"#).unwrap()
+    );
+}
+
+#[test]
+fn unknown_kind() {
+    let error = serde_json::from_str::<EventKind>("\"Key\"").unwrap_err().to_string();
+
+    assert!(error.starts_with("unknown variant `Key`, expected one of `Click`, `key_press`, `closed`"));
+}
@@ -0,0 +1,108 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(kind, count_kinds, drain)]
+enum Token {
+    Word(String),
+    Number(i64),
+    #[variation(skip)]
+    Whitespace(usize),
+}
+
+#[test]
+fn skip_keeps_other_accessors() {
+    let mut token = Token::Number(4);
+
+    assert!(token.is_number());
+    assert!(!Token::Whitespace(1).is_word());
+    assert_eq!(None, Token::Whitespace(1).as_number());
+
+    if let Some(number) = token.as_number_mut() {
+        *number += 1;
+    }
+
+    assert_eq!(5, token.into_number());
+    assert_eq!("hi", Token::Word("hi".to_owned()).into_word());
+}
+
+#[test]
+fn skip_counts_as_variant() {
+    let tokens = vec![Token::Word("a".to_owned()), Token::Whitespace(1), Token::Whitespace(2)];
+    let counts = TokenKindCounts::from_iter(&tokens);
+
+    assert_eq!(TokenKind::Whitespace, Token::Whitespace(3).kind());
+    assert_eq!("Whitespace", Token::Whitespace(3).variant_name());
+    assert_eq!(2, counts.whitespace());
+    assert_eq!(3, counts.total());
+}
+
+#[test]
+fn skip_drain() {
+    let mut tokens = vec![Token::Whitespace(1), Token::Number(2), Token::Whitespace(3)];
+
+    assert_eq!(vec![2], Token::drain_numbers(&mut tokens));
+    assert_eq!(vec![Token::Whitespace(1), Token::Whitespace(3)], tokens);
+
+    let widths = tokens
+        .iter()
+        .map(|token| match *token {
+            Token::Whitespace(width) => width,
+            _ => 0,
+        })
+        .sum::<usize>();
+
+    assert_eq!(4, widths);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Variation)]
+#[variation(iter)]
+enum Level {
+    Low,
+    High,
+    #[variation(skip)]
+    Unknown,
+}
+
+#[test]
+fn skip_fieldless() {
+    assert!(Level::High.is_high());
+    assert!(!Level::Unknown.is_low());
+    assert_eq!([Level::Low, Level::High, Level::Unknown], Level::VARIANTS);
+}
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(drain, iterator_ext, option_ext)]
+enum Resource {
+    #[variation(skip(as_mut, into), alias = "file")]
+    Handle(u32),
+    #[variation(skip(is))]
+    Buffer(Vec<u8>),
+    Closed,
+}
+
+#[test]
+#[allow(deprecated)]
+fn skip_families() {
+    let handle = Resource::Handle(3);
+    let buffer = Resource::Buffer(vec![1, 2]);
+
+    assert!(handle.is_handle());
+    assert!(handle.is_file());
+    assert_eq!(Some(&3), handle.as_handle());
+    assert_eq!(Some(&3), handle.as_file());
+    assert_eq!(Some(&vec![1, 2]), buffer.as_buffer());
+    assert!(!buffer.is_closed());
+    assert_eq!(vec![1, 2], buffer.into_buffer());
+}
+
+#[test]
+fn skip_families_extensions() {
+    let mut resources = vec![Resource::Buffer(vec![1]), Resource::Handle(2), Resource::Closed];
+
+    assert!(Some(Resource::Handle(1)).is_handle());
+    assert_eq!(Some(&1), Some(Resource::Handle(1)).as_handle());
+    assert_eq!(Some(vec![1]), Some(Resource::Buffer(vec![1])).into_buffer());
+    assert_eq!(vec![&vec![1]], resources.iter().buffers().collect::<Vec<_>>());
+    assert_eq!(vec![vec![1]], Resource::drain_buffers(&mut resources));
+    assert_eq!(vec![Resource::Handle(2), Resource::Closed], resources);
+}
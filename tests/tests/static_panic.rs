@@ -0,0 +1,54 @@
+use std::panic;
+
+use variation::Variation;
+
+// Large on purpose, as the values are meant to be kept boxed.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Variation)]
+#[variation(static_panic, boxed)]
+enum Reading {
+    Celsius(f32),
+    #[variation(panic_msg = "expected a raw reading")]
+    Raw(u16),
+    Samples([u16; 512]),
+    Missing,
+}
+
+fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+    let payload = panic::catch_unwind(f).unwrap_err();
+
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast::<&str>().unwrap().to_string(),
+    }
+}
+
+#[test]
+fn static_panic_into() {
+    let message = panic_message(|| {
+        Reading::Missing.into_celsius();
+    });
+
+    assert_eq!("`into_celsius` called on the wrong variant of `Reading`, expected `Celsius`", message);
+    assert_eq!(1.5, Reading::Celsius(1.5).into_celsius());
+}
+
+#[test]
+fn static_panic_into_boxed() {
+    let message = panic_message(|| {
+        Box::new(Reading::Raw(3)).into_celsius_boxed();
+    });
+
+    assert_eq!("`into_celsius_boxed` called on the wrong variant of `Reading`, expected `Celsius`", message);
+    assert_eq!([0; 512], Box::new(Reading::Samples([0; 512])).into_samples_boxed());
+}
+
+#[test]
+fn static_panic_msg() {
+    let message = panic_message(|| {
+        Reading::Celsius(1.5).into_raw();
+    });
+
+    assert_eq!("expected a raw reading", message);
+    assert_eq!(3, Box::new(Reading::Raw(3)).into_raw_boxed());
+}
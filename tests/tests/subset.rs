@@ -0,0 +1,53 @@
+use std::convert::TryFrom;
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(subset(name = "Scalar", variants(Integer, Bool), derive(Clone, Debug, PartialEq)))]
+enum Type {
+    Integer(i64),
+    Bool(bool),
+    Text(String),
+    Null,
+}
+
+#[test]
+fn subset_conversions() {
+    for scalar in [Scalar::Integer(1), Scalar::Bool(true)] {
+        assert_eq!(Ok(scalar.clone()), Scalar::try_from(Type::from(scalar)));
+    }
+
+    assert_eq!(Type::Bool(false), Type::from(Scalar::Bool(false)));
+    assert_eq!(Err(Type::Null), Scalar::try_from(Type::Null));
+    assert_eq!(Err(Type::Text("a".to_owned())), Scalar::try_from(Type::Text("a".to_owned())));
+}
+
+#[derive(Debug, PartialEq)]
+struct NotScalar;
+
+impl<'a, T> From<Value<'a, T>> for NotScalar {
+    fn from(_: Value<'a, T>) -> Self {
+        NotScalar
+    }
+}
+
+#[derive(Debug, Variation)]
+#[variation(subset(
+    name = "Borrowed",
+    variants(Text, Empty),
+    vis = "pub(crate)",
+    derive(Debug, PartialEq),
+    error = "NotScalar"
+))]
+enum Value<'a, T> {
+    Owned(T),
+    Text(&'a str),
+    Empty,
+}
+
+#[test]
+fn subset_options() {
+    assert_eq!(Ok(Borrowed::Text("a")), Borrowed::try_from(Value::<u8>::Text("a")));
+    assert_eq!(Err(NotScalar), Borrowed::try_from(Value::Owned(1)));
+    assert!(Value::<u8>::from(Borrowed::Empty).is_empty());
+    assert_eq!(Some(&"b"), Value::<u8>::from(Borrowed::Text("b")).as_text());
+}
@@ -0,0 +1,47 @@
+use std::convert::TryFrom;
+use variation::Variation;
+
+mod token {
+    #[derive(Debug, PartialEq)]
+    pub enum Token<T> {
+        Number(T),
+        Word(String),
+        Pair(T, T),
+        Comma,
+        Comment(String),
+    }
+}
+
+use token::Token;
+
+#[derive(Clone, Debug, PartialEq, Variation)]
+#[variation(subset_of = "token::Token<T>")]
+enum Value<T> {
+    Number(T),
+    Word(String),
+    Pair(T, T),
+    Comma,
+}
+
+#[test]
+fn subset_round_trip() {
+    let values = vec![
+        Value::Number(1),
+        Value::Word("a".to_owned()),
+        Value::Pair(2, 3),
+        Value::Comma,
+    ];
+
+    for value in values {
+        let token = Token::from(value.clone());
+
+        assert_eq!(Ok(value), Value::try_from(token));
+    }
+}
+
+#[test]
+fn superset_only_variant() {
+    let comment = Token::<i32>::Comment("todo".to_owned());
+
+    assert_eq!(Err(Token::Comment("todo".to_owned())), Value::try_from(comment));
+}
@@ -0,0 +1,53 @@
+use std::any::Any;
+use std::borrow::BorrowMut;
+use std::error::Error;
+use std::fmt;
+
+use variation::Variation;
+
+#[derive(Debug)]
+struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("timed out")
+    }
+}
+
+impl Error for Timeout {}
+
+#[derive(Variation)]
+enum Failure {
+    #[variation(as_ref(dyn Error + Send + Sync), borrow(dyn Error + Send + Sync))]
+    Boxed(Box<dyn Error + Send + Sync>),
+}
+
+fn message<E: AsRef<dyn Error + Send + Sync>>(failure: E) -> String {
+    failure.as_ref().to_string()
+}
+
+#[test]
+fn multi_bound_error_reference() {
+    let failure = Failure::Boxed(Box::new(Timeout));
+    let error: &(dyn Error + Send + Sync) = failure.as_ref();
+
+    assert!(error.is::<Timeout>());
+    assert!(BorrowMut::<dyn Error + Send + Sync>::borrow_mut(&mut Failure::Boxed(Box::new(Timeout))).is::<Timeout>());
+    assert_eq!("timed out", message(failure));
+}
+
+#[derive(Variation)]
+enum Slot {
+    #[variation(as_ref(dyn Any + Send))]
+    Value(Box<dyn Any + Send>),
+}
+
+#[test]
+fn multi_bound_any_mutable_reference() {
+    let mut slot = Slot::Value(Box::new(1_u32));
+    let value: &mut (dyn Any + Send) = slot.as_mut();
+
+    *value.downcast_mut::<u32>().unwrap() += 1;
+
+    assert_eq!(Some(&2), AsRef::<dyn Any + Send>::as_ref(&slot).downcast_ref::<u32>());
+}
@@ -0,0 +1,73 @@
+use std::convert::{TryFrom, TryInto};
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+enum Type {
+    Unit,
+    #[variation(try_into)]
+    Integer(i32),
+    #[variation(try_into)]
+    Real(i32, u32),
+    #[variation(from, try_into)]
+    Text(String),
+}
+
+#[test]
+fn try_from_single_field() {
+    assert_eq!(Ok(5), i32::try_from(Type::Integer(5)));
+    assert_eq!(Err(Type::Unit), i32::try_from(Type::Unit));
+    assert_eq!(Err(Type::Real(3, 14)), i32::try_from(Type::Real(3, 14)));
+}
+
+#[test]
+fn try_from_tuple() {
+    let real: Result<(i32, u32), _> = Type::Real(3, 14).try_into();
+
+    assert_eq!(Ok((3, 14)), real);
+    assert_eq!(Err(Type::Integer(1)), <(i32, u32)>::try_from(Type::Integer(1)));
+}
+
+fn extract<T: TryFrom<Type>>(values: Vec<Type>) -> Vec<T> {
+    values.into_iter().filter_map(|value| T::try_from(value).ok()).collect()
+}
+
+#[test]
+fn try_from_generic_bound() {
+    let values = vec![
+        Type::Integer(1),
+        Type::Text(String::from("one")),
+        Type::Integer(2),
+        Type::from(String::from("two")),
+    ];
+
+    assert_eq!(vec![String::from("one"), String::from("two")], extract::<String>(values));
+}
+
+fn borrow_all<'a, T>(values: &'a [Type]) -> Vec<T>
+where
+    T: TryFrom<&'a Type>,
+{
+    values.iter().filter_map(|value| T::try_from(value).ok()).collect()
+}
+
+#[test]
+fn try_from_ref() {
+    let values = vec![Type::Integer(1), Type::Real(3, 14), Type::Integer(2), Type::Unit];
+
+    assert_eq!(vec![&1, &2], borrow_all::<&i32>(&values));
+    assert_eq!(vec![(&3, &14)], borrow_all::<(&i32, &u32)>(&values));
+    assert_eq!(Err(&Type::Unit), <&String>::try_from(&values[3]));
+}
+
+#[test]
+fn try_from_ref_mut() {
+    let mut value = Type::Real(3, 14);
+
+    {
+        let (integer, _) = <(&mut i32, &mut u32)>::try_from(&mut value).unwrap();
+        *integer = 4;
+    }
+
+    assert_eq!(Type::Real(4, 14), value);
+    assert_eq!(Err(&mut Type::Real(4, 14)), <&mut i32>::try_from(&mut value));
+}
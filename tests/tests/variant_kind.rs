@@ -0,0 +1,56 @@
+#![cfg(feature = "variant_kind")]
+
+use std::collections::HashMap;
+use variation::Variation;
+use variation_kind::VariantKind;
+
+#[derive(Variation)]
+#[variation(kind(variant_kind))]
+enum Event {
+    Click(u32),
+    Key(char),
+    Close,
+}
+
+fn histogram<E: VariantKind>(values: &[E]) -> HashMap<E::Kind, usize> {
+    let mut counts = HashMap::with_capacity(E::VARIANT_COUNT);
+
+    for value in values {
+        *counts.entry(value.kind()).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+fn names<E: VariantKind>(values: &[E]) -> Vec<&'static str> {
+    values.iter().map(VariantKind::variant_name).collect()
+}
+
+#[test]
+fn generic_over_kinds() {
+    let events = [Event::Click(1), Event::Key('a'), Event::Click(2)];
+    let counts = histogram(&events);
+
+    assert_eq!(3, Event::VARIANT_COUNT);
+    assert_eq!(2, counts[&EventKind::Click]);
+    assert_eq!(1, counts[&EventKind::Key]);
+    assert!(!counts.contains_key(&EventKind::Close));
+    assert_eq!(vec!["Click", "Key", "Click"], names(&events));
+    assert_eq!(Some(&1), events[0].as_click());
+    assert_eq!(Some(&'a'), events[1].as_key());
+    assert!(Event::Close.is_close());
+}
+
+#[derive(Variation)]
+#[variation(kind(variant_kind))]
+enum Wrapper<T> {
+    Value(T),
+    Empty,
+}
+
+#[test]
+fn generic_enum_kind() {
+    assert_eq!(WrapperKind::Value, VariantKind::kind(&Wrapper::Value(1)));
+    assert_eq!("Empty", VariantKind::variant_name(&Wrapper::<u8>::Empty));
+    assert_eq!(Some(&1), Wrapper::Value(1).as_value());
+}
@@ -0,0 +1,34 @@
+mod model {
+    use variation::Variation;
+
+    #[derive(Variation)]
+    #[variation(vis(as_mut = "pub(crate)", into = "pub(super)"))]
+    pub enum Value {
+        Integer(i32),
+        Text(String),
+    }
+
+    pub(crate) fn restricted(value: Value) -> String {
+        value.into_text()
+    }
+}
+
+use model::Value;
+
+#[test]
+fn default_visibility() {
+    let value = Value::Integer(1);
+
+    assert!(value.is_integer());
+    assert_eq!(Some(&1), value.as_integer());
+}
+
+#[test]
+fn restricted_visibility() {
+    let mut value = Value::Integer(1);
+
+    *value.as_integer_mut().unwrap() += 1;
+
+    assert_eq!(2, value.into_integer());
+    assert_eq!("text", model::restricted(Value::Text(String::from("text"))));
+}
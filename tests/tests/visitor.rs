@@ -0,0 +1,140 @@
+use variation::Variation;
+
+#[derive(Debug, PartialEq, Variation)]
+#[variation(visitor)]
+enum Expr {
+    Zero,
+    Number(i64),
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+}
+
+struct Evaluate;
+
+impl ExprVisitor for Evaluate {
+    type Output = i64;
+
+    fn visit_zero(&mut self) -> i64 {
+        0
+    }
+
+    fn visit_number(&mut self, v0: &i64) -> i64 {
+        *v0
+    }
+
+    fn visit_neg(&mut self, v0: &Box<Expr>) -> i64 {
+        -v0.accept(self)
+    }
+
+    fn visit_add(&mut self, v0: &Box<Expr>, v1: &Box<Expr>) -> i64 {
+        v0.accept(self) + v1.accept(self)
+    }
+}
+
+/// Counts the nodes it has visited across every call.
+#[derive(Default)]
+struct Count(usize);
+
+impl ExprVisitor for Count {
+    type Output = ();
+
+    fn visit_zero(&mut self) {
+        self.0 += 1;
+    }
+
+    fn visit_number(&mut self, _: &i64) {
+        self.0 += 1;
+    }
+
+    fn visit_neg(&mut self, v0: &Box<Expr>) {
+        self.0 += 1;
+        v0.accept(self);
+    }
+
+    fn visit_add(&mut self, v0: &Box<Expr>, v1: &Box<Expr>) {
+        self.0 += 1;
+        v0.accept(self);
+        v1.accept(self);
+    }
+}
+
+/// Rewrites `Neg(Number(n))` into `Number(-n)`.
+struct Fold;
+
+impl ExprOwnedVisitor for Fold {
+    type Output = Expr;
+
+    fn visit_zero(&mut self) -> Expr {
+        Expr::Zero
+    }
+
+    fn visit_number(&mut self, v0: i64) -> Expr {
+        Expr::Number(v0)
+    }
+
+    fn visit_neg(&mut self, v0: Box<Expr>) -> Expr {
+        match v0.accept_owned(self) {
+            Expr::Number(n) => Expr::Number(-n),
+            other => Expr::Neg(Box::new(other)),
+        }
+    }
+
+    fn visit_add(&mut self, v0: Box<Expr>, v1: Box<Expr>) -> Expr {
+        Expr::Add(Box::new(v0.accept_owned(self)), Box::new(v1.accept_owned(self)))
+    }
+}
+
+fn expr() -> Expr {
+    Expr::Add(
+        Box::new(Expr::Neg(Box::new(Expr::Number(2)))),
+        Box::new(Expr::Add(Box::new(Expr::Number(5)), Box::new(Expr::Zero))),
+    )
+}
+
+#[test]
+fn accept() {
+    let mut count = Count::default();
+    expr().accept(&mut count);
+
+    assert_eq!(3, expr().accept(&mut Evaluate));
+    assert_eq!(6, count.0);
+}
+
+#[test]
+fn accept_owned() {
+    let folded = expr().accept_owned(&mut Fold);
+
+    assert_eq!(
+        Expr::Add(
+            Box::new(Expr::Number(-2)),
+            Box::new(Expr::Add(Box::new(Expr::Number(5)), Box::new(Expr::Zero))),
+        ),
+        folded
+    );
+    assert_eq!(3, folded.accept(&mut Evaluate));
+}
+
+mod private {
+    use variation::Variation;
+
+    #[derive(Variation)]
+    #[variation(visitor)]
+    pub(crate) enum Token {
+        Word(String),
+    }
+}
+
+struct Length;
+
+impl private::TokenVisitor for Length {
+    type Output = usize;
+
+    fn visit_word(&mut self, v0: &String) -> usize {
+        v0.len()
+    }
+}
+
+#[test]
+fn visibility_follows_enum() {
+    assert_eq!(4, private::Token::Word(String::from("word")).accept(&mut Length));
+}
@@ -0,0 +1,49 @@
+//! The [`VariantKind`] trait, implemented by enums deriving
+//! `variation::Variation` with `#[variation(kind(variant_kind))]`, for code
+//! generic over such enums.
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use variation::Variation;
+//! use variation_kind::VariantKind;
+//!
+//! fn histogram<E: VariantKind>(values: &[E]) -> HashMap<E::Kind, usize> {
+//!     let mut counts = HashMap::with_capacity(E::VARIANT_COUNT);
+//!
+//!     for value in values {
+//!         *counts.entry(value.kind()).or_insert(0) += 1;
+//!     }
+//!
+//!     counts
+//! }
+//!
+//! #[derive(Variation)]
+//! #[variation(kind(variant_kind))]
+//! enum Event {
+//!     Click(u32),
+//!     Key(char),
+//! }
+//!
+//! fn main() {
+//!     let counts = histogram(&[Event::Click(1), Event::Key('a'), Event::Click(2)]);
+//!
+//!     assert_eq!(2, counts[&EventKind::Click]);
+//! }
+//! ```
+
+use std::hash::Hash;
+
+/// An enum with a fieldless kind enum naming its variants.
+pub trait VariantKind {
+    /// The kind enum, with a variant for each variant of the enum.
+    type Kind: Copy + Eq + Hash;
+
+    /// The number of variants of the enum.
+    const VARIANT_COUNT: usize;
+
+    /// The kind of variant the enum holds.
+    fn kind(&self) -> Self::Kind;
+
+    /// The name of the variant the enum holds.
+    fn variant_name(&self) -> &'static str;
+}
@@ -0,0 +1,114 @@
+//! The `is_*`, `as_*` and `into_*` methods of the variants, generated in the
+//! inherent impl or in the trait named with `#[variation(trait = "...")]`.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::*;
+
+use crate::attr::{ContainerAttrs, Inline};
+
+/// An accessor method, kept in parts so it can be split into the declaration
+/// of a trait method and its implementation.
+pub(crate) struct Accessor {
+    /// The `#[cfg]` of the family, which goes on both the declaration and the
+    /// implementation.
+    pub cfg: TokenStream,
+    /// The documentation and `#[must_use]`, which go on the declaration.
+    pub attrs: TokenStream,
+    /// The `#[inline]` hint and the attributes of `#[variation(attr(...))]`,
+    /// which go on the implementation along with a plain `#[inline]` if they
+    /// have none.
+    pub impl_attrs: TokenStream,
+    /// The visibility of the method in the inherent impl.
+    pub vis: TokenStream,
+    /// Everything from `fn` up to the body, such as
+    /// `fn is_integer(&self) -> bool`.
+    pub signature: TokenStream,
+    pub body: TokenStream,
+    /// Whether the method consumes the enum, which keeps the trait from being
+    /// used as a trait object.
+    pub consuming: bool,
+}
+
+/// The accessors as methods of the inherent impl.
+pub(crate) fn inherent(accessors: &[Accessor]) -> TokenStream {
+    let mut methods = TokenStream::new();
+
+    for Accessor { cfg, attrs, impl_attrs, vis, signature, body, .. } in accessors {
+        let impl_attrs = Inline::with_default(impl_attrs.clone());
+
+        methods.extend(quote! {
+            #cfg
+            #attrs
+            #impl_attrs
+            #vis #signature {
+                #body
+            }
+        });
+    }
+
+    methods
+}
+
+/// Generates the trait named with `#[variation(trait = "...")]` declaring
+/// the accessors and its impl for the enum, or for the remote enum of
+/// `variation_remote!`. The consuming methods are left out with
+/// `#[variation(trait_object_safe)]`.
+pub(crate) fn accessor_trait(
+    ast: &DeriveInput,
+    container: &ContainerAttrs,
+    trait_name: &Ident,
+    accessors: &[Accessor],
+) -> TokenStream {
+    let name = match container.remote {
+        Some((_, ref path)) => quote!(#path),
+        None => ast.ident.clone().into_token_stream(),
+    };
+    let vis = match container.trait_vis {
+        Some((_, ref vis)) => vis,
+        None => &ast.vis,
+    };
+    let mut generics = ast.generics.clone();
+    generics.make_where_clause().predicates.extend(container.bounds.iter().cloned());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let doc = format!("The accessor methods of [`{}`].", name.to_string().replace(' ', ""));
+    // `into_mode = "result"` hands back `Self`, which trait methods can only do
+    // for sized types. The consuming methods keep the trait from being used
+    // as a trait object anyway.
+    let sized = if container.trait_object_safe.is_some() { quote!() } else { quote!(: Sized) };
+    let mut declarations = TokenStream::new();
+    let mut methods = TokenStream::new();
+
+    for accessor in accessors {
+        if accessor.consuming && container.trait_object_safe.is_some() {
+            continue;
+        }
+
+        let Accessor { cfg, attrs, impl_attrs, signature, body, .. } = accessor;
+        let impl_attrs = Inline::with_default(impl_attrs.clone());
+
+        declarations.extend(quote! {
+            #cfg
+            #attrs
+            #signature;
+        });
+        methods.extend(quote! {
+            #cfg
+            #impl_attrs
+            #signature {
+                #body
+            }
+        });
+    }
+
+    quote! {
+        #[doc = #doc]
+        #vis trait #trait_name #impl_generics #sized #where_clause {
+            #declarations
+        }
+
+        impl #impl_generics #trait_name #ty_generics for #name #ty_generics #where_clause {
+            #methods
+        }
+    }
+}
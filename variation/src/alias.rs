@@ -0,0 +1,141 @@
+//! Deprecated aliases of the `is_*`, `as_*` and `into_*` methods for renamed
+//! variants, given with `#[variation(alias = "...")]`.
+
+use heck::SnakeCase;
+use proc_macro2::Span;
+use quote::quote;
+use syn::*;
+
+use crate::accessor::Accessor;
+use crate::attr::{method_where_clause, ContainerAttrs, Families, MethodVis, VariantAttrs};
+use crate::names::{family_names, method_names, method_stem, Case};
+use crate::{inner_ref_type, inner_type};
+
+/// Generates the methods of each alias, deprecated and forwarding to the
+/// methods named after the variant, with the same visibility, bounds and
+/// prefixes. They go wherever the accessors do, in the inherent impl or the
+/// trait of `#[variation(trait = "...")]`. Families skipped on the variant
+/// don't get aliases either, and those given another name keep it.
+pub(crate) fn methods(
+    variants: &[(&Variant, VariantAttrs)],
+    container: &ContainerAttrs,
+    case: Case,
+) -> Result<Vec<Accessor>> {
+    let MethodVis { is: is_vis, as_ref: as_vis, as_mut: as_mut_vis, into: into_vis } = &container.method_vis;
+    let bounds = &container.method_bounds;
+    let is_where = method_where_clause(&bounds.is);
+    let as_where = method_where_clause(&bounds.as_ref);
+    let as_mut_where = method_where_clause(&bounds.as_mut);
+    let into_where = method_where_clause(&bounds.into);
+    let constness = container.constness();
+    let Families { is: is_must_use, as_ref: as_must_use, as_mut: as_mut_must_use, into: into_must_use } =
+        container.method_must_use();
+    let Families { is: is_cfg, as_ref: as_cfg, as_mut: as_mut_cfg, into: into_cfg } = container.method_cfg_attrs();
+    let Families { is: is_attrs, as_ref: as_attrs, as_mut: as_mut_attrs, into: into_attrs } =
+        container.method_extra_attrs();
+    let mut methods = Vec::new();
+
+    for (variant, attrs) in variants {
+        let snake_case = method_stem(variant, case);
+        let targets = method_names(variant, attrs, case, &container.method_prefixes);
+        let inline = attrs.inline(container);
+        let extra_attrs = attrs.extra_attrs();
+        let doc = attrs.doc();
+
+        for alias in &attrs.aliases {
+            let alias_name = alias.value().to_snake_case();
+            let aliases = family_names(&alias_name, &container.method_prefixes);
+            let names = [&aliases.is, &aliases.as_ref, &aliases.as_mut, &aliases.into];
+
+            // The names with the family affixes would be valid even for an
+            // empty alias, as `is_`, `as_` and the like.
+            if alias_name.is_empty() {
+                return Err(Error::new(alias.span(), format!("the alias of `{}` can't be empty", variant.ident)));
+            }
+
+            if names.iter().any(|name| syn::parse_str::<Ident>(name).is_err()) || alias_name == snake_case {
+                return Err(Error::new(
+                    alias.span(),
+                    format!("`{}` is not a valid alias of `{}`", alias.value(), variant.ident),
+                ));
+            }
+
+            let is_fn = &targets.is;
+            let is_alias = Ident::new(&aliases.is, Span::call_site());
+            let is_note = format!("use `{}`", is_fn);
+
+            if attrs.skip.is.is_none() {
+                methods.push(Accessor {
+                    cfg: is_cfg.clone(),
+                    attrs: quote!(#doc #[deprecated(note = #is_note)] #is_must_use),
+                    impl_attrs: quote!(#inline #extra_attrs #is_attrs),
+                    vis: quote!(#is_vis),
+                    signature: quote!(#constness fn #is_alias(&self) -> bool #is_where),
+                    body: quote!(self.#is_fn()),
+                    consuming: false,
+                });
+            }
+
+            if variant.fields.iter().count() == 0 {
+                continue;
+            }
+
+            let as_fn = &targets.as_ref;
+            let as_alias = Ident::new(&aliases.as_ref, Span::call_site());
+            let as_note = format!("use `{}`", as_fn);
+            let as_mut_fn = &targets.as_mut;
+            let as_mut_alias = Ident::new(&aliases.as_mut, Span::call_site());
+            let as_mut_note = format!("use `{}`", as_mut_fn);
+            let into_fn = &targets.into;
+            let into_alias = Ident::new(&aliases.into, Span::call_site());
+            let into_note = format!("use `{}`", into_fn);
+            let inner = inner_type(variant);
+            let inner_ref = inner_ref_type(variant, quote!(&));
+            let inner_mut = inner_ref_type(variant, quote!(&mut));
+
+            if attrs.skip.as_ref.is_none() {
+                methods.push(Accessor {
+                    cfg: as_cfg.clone(),
+                    attrs: quote!(#doc #[deprecated(note = #as_note)] #as_must_use),
+                    impl_attrs: quote!(#inline #extra_attrs #as_attrs),
+                    vis: quote!(#as_vis),
+                    signature: quote! {
+                        #constness fn #as_alias(&self) -> ::core::option::Option<#inner_ref> #as_where
+                    },
+                    body: quote!(self.#as_fn()),
+                    consuming: false,
+                });
+            }
+
+            if attrs.skip.as_mut.is_none() {
+                methods.push(Accessor {
+                    cfg: as_mut_cfg.clone(),
+                    attrs: quote!(#doc #[deprecated(note = #as_mut_note)] #as_mut_must_use),
+                    impl_attrs: quote!(#inline #extra_attrs #as_mut_attrs),
+                    vis: quote!(#as_mut_vis),
+                    signature: quote! {
+                        fn #as_mut_alias(&mut self) -> ::core::option::Option<#inner_mut> #as_mut_where
+                    },
+                    body: quote!(self.#as_mut_fn()),
+                    consuming: false,
+                });
+            }
+
+            if attrs.skip.into.is_none() {
+                let into_output = attrs.mode_of_into(container).output(&inner, quote!(Self));
+
+                methods.push(Accessor {
+                    cfg: into_cfg.clone(),
+                    attrs: quote!(#doc #[deprecated(note = #into_note)] #into_must_use),
+                    impl_attrs: quote!(#inline #extra_attrs #into_attrs),
+                    vis: quote!(#into_vis),
+                    signature: quote!(fn #into_alias(self) -> #into_output #into_where),
+                    body: quote!(self.#into_fn()),
+                    consuming: true,
+                });
+            }
+        }
+    }
+
+    Ok(methods)
+}
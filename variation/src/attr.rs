@@ -0,0 +1,1786 @@
+//! Parsing of the `#[variation(...)]` helper attributes.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::*;
+
+use crate::delegate::{Delegate, DelegateFn};
+use crate::names::Case;
+use crate::subset::Subset;
+
+/// A single entry of a `#[variation(...)]` attribute, either `name`,
+/// `name(...)` or `name = "..."`.
+pub(crate) struct Item {
+    pub name: Ident,
+    pub value: Value,
+}
+
+pub(crate) enum Value {
+    Word,
+    List(TokenStream),
+    Str(LitStr),
+}
+
+impl Item {
+    pub(crate) fn span(&self) -> Span {
+        self.name.span()
+    }
+
+    pub(crate) fn expect_word(&self) -> Result<()> {
+        match self.value {
+            Value::Word => Ok(()),
+            _ => Err(Error::new(self.span(), format!("`{}` does not take a value", self.name))),
+        }
+    }
+
+    pub(crate) fn expect_str(&self) -> Result<&LitStr> {
+        match self.value {
+            Value::Str(ref value) => Ok(value),
+            _ => Err(Error::new(self.span(), format!("expected `{} = \"...\"`", self.name))),
+        }
+    }
+
+    pub(crate) fn expect_paths(&self) -> Result<Vec<Path>> {
+        match self.value {
+            Value::List(ref tokens) => {
+                Ok(Punctuated::<Path, Token![,]>::parse_terminated.parse2(tokens.clone())?.into_iter().collect())
+            }
+            _ => Err(Error::new(self.span(), format!("expected `{}(Trait, ...)`", self.name))),
+        }
+    }
+
+    pub(crate) fn unknown(&self) -> Error {
+        Error::new(self.span(), format!("unknown variation attribute `{}`", self.name))
+    }
+}
+
+impl Parse for Item {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.call(Ident::parse_any)?;
+
+        let value = if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Value::List(content.parse()?)
+        } else if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Value::Str(input.parse()?)
+        } else {
+            Value::Word
+        };
+
+        Ok(Item { name, value })
+    }
+}
+
+struct Items(Punctuated<Item, Token![,]>);
+
+impl Parse for Items {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Items(content.parse_terminated(Item::parse)?))
+    }
+}
+
+/// Collects the entries of every `#[variation(...)]` attribute in `attrs`.
+pub(crate) fn items(attrs: &[Attribute]) -> Result<Vec<Item>> {
+    let mut items = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("variation")) {
+        let parsed: Items = syn::parse2(attr.tokens.clone())?;
+        items.extend(parsed.0);
+    }
+
+    Ok(items)
+}
+
+fn set_flag(flag: &mut Option<Span>, item: &Item) -> Result<()> {
+    item.expect_word()?;
+
+    if flag.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *flag = Some(item.span());
+    Ok(())
+}
+
+fn add_delegates(delegates: &mut Vec<(Span, Delegate)>, item: &Item) -> Result<()> {
+    let paths = match item.value {
+        Value::List(ref tokens) => Punctuated::<Path, Token![,]>::parse_terminated.parse2(tokens.clone())?,
+        _ => return Err(Error::new(item.span(), "expected `delegate(Trait, ...)`")),
+    };
+
+    for path in &paths {
+        let (span, delegate) = Delegate::parse(path)?;
+
+        if delegates.iter().any(|(_, other)| other.name() == delegate.name()) {
+            return Err(Error::new(span, format!("duplicate `delegate({})`", delegate.name())));
+        }
+
+        delegates.push((span, delegate));
+    }
+
+    Ok(())
+}
+
+/// An option for each of the `is_*`, `as_*`, `as_*_mut` and `into_*` method
+/// families, given as `name(is = "...", as = "...", as_mut = "...",
+/// into = "...")`.
+pub(crate) struct Families<T> {
+    pub is: T,
+    pub as_ref: T,
+    pub as_mut: T,
+    pub into: T,
+}
+
+/// The names of the method families in attributes, in the order of the
+/// fields of `Families`. A new family has to be added to both, and to
+/// `Families::get_mut`.
+const FAMILIES: &[&str] = &["is", "as", "as_mut", "into"];
+
+/// The visibilities set with `#[variation(vis(...))]`, `pub` unless given.
+pub(crate) type MethodVis = Families<Visibility>;
+
+impl Default for MethodVis {
+    fn default() -> Self {
+        let public: Visibility = parse_quote!(pub);
+
+        Families {
+            is: public.clone(),
+            as_ref: public.clone(),
+            as_mut: public.clone(),
+            into: public,
+        }
+    }
+}
+
+/// The extra where predicates of each family, set with
+/// `#[variation(bound(...))]`.
+pub(crate) type MethodBounds = Families<Vec<WherePredicate>>;
+
+impl Default for MethodBounds {
+    fn default() -> Self {
+        Families {
+            is: Vec::new(),
+            as_ref: Vec::new(),
+            as_mut: Vec::new(),
+            into: Vec::new(),
+        }
+    }
+}
+
+/// The families a variant doesn't get methods of, set with
+/// `#[variation(skip)]` for all of them or `#[variation(skip(...))]`.
+pub(crate) type Skipped = Families<Option<Span>>;
+
+impl Skipped {
+    /// Every family skipped, such as with `#[variation(skip)]`.
+    pub(crate) fn all(span: Span) -> Self {
+        Families {
+            is: Some(span),
+            as_ref: Some(span),
+            as_mut: Some(span),
+            into: Some(span),
+        }
+    }
+
+    /// Also skips the families skipped by `other`, such as those left out of
+    /// `#[variation(methods(...))]` on the enum.
+    pub(crate) fn extend(&mut self, other: &Skipped) {
+        self.is = self.is.or(other.is);
+        self.as_ref = self.as_ref.or(other.as_ref);
+        self.as_mut = self.as_mut.or(other.as_mut);
+        self.into = self.into.or(other.into);
+    }
+}
+
+/// The method names given on a variant with `#[variation(is = "...")]` and so
+/// on, in place of those derived from its name.
+pub(crate) type MethodNames = Families<Option<LitStr>>;
+
+/// The prefixes of the method names set with `#[variation(prefix(...))]`, in
+/// place of `is_`, `as_`, `as_` with the `_mut` suffix and `into_`.
+pub(crate) type MethodPrefixes = Families<Option<LitStr>>;
+
+/// The attributes of the method families set with
+/// `#[variation(attr(is = "...", ...))]`.
+pub(crate) type MethodAttrs = Families<Vec<TokenStream>>;
+
+impl Default for MethodAttrs {
+    fn default() -> Self {
+        Families {
+            is: Vec::new(),
+            as_ref: Vec::new(),
+            as_mut: Vec::new(),
+            into: Vec::new(),
+        }
+    }
+}
+
+/// The `cfg` predicates of the method families set with
+/// `#[variation(cfg(is = "...", ...))]`.
+pub(crate) type MethodCfg = Families<Option<TokenStream>>;
+
+impl<T> Default for Families<Option<T>> {
+    fn default() -> Self {
+        Families {
+            is: None,
+            as_ref: None,
+            as_mut: None,
+            into: None,
+        }
+    }
+}
+
+impl<T> Families<T> {
+    /// The option of the family called `name` in attributes.
+    fn get_mut(&mut self, name: &str) -> Option<&mut T> {
+        match name {
+            "is" => Some(&mut self.is),
+            "as" => Some(&mut self.as_ref),
+            "as_mut" => Some(&mut self.as_mut),
+            "into" => Some(&mut self.into),
+            _ => None,
+        }
+    }
+
+    /// Calls `set` with the option and the entry of each family given in the
+    /// list of `item`, such as `vis(is = "...")` or `skip(into)`.
+    fn parse(&mut self, item: &Item, mut set: impl FnMut(&mut T, &Item) -> Result<()>) -> Result<()> {
+        let families = match item.value {
+            Value::List(ref tokens) => Punctuated::<Item, Token![,]>::parse_terminated.parse2(tokens.clone())?,
+            _ => return Err(Error::new(item.span(), format!("expected `{}(is = \"...\", ...)`", item.name))),
+        };
+        let mut seen = Vec::new();
+
+        for family in &families {
+            let name = family.name.to_string();
+            let target = match self.get_mut(&name) {
+                Some(target) => target,
+                None => {
+                    let expected = FAMILIES.iter().map(|family| format!("`{}`", family)).collect::<Vec<_>>();
+
+                    return Err(Error::new(
+                        family.span(),
+                        format!(
+                            "unknown method family `{}`, expected {} or {}",
+                            name,
+                            expected[..expected.len() - 1].join(", "),
+                            expected[expected.len() - 1]
+                        ),
+                    ));
+                }
+            };
+
+            if seen.contains(&name) {
+                return Err(Error::new(family.span(), format!("duplicate `{}` attribute", name)));
+            }
+
+            set(target, family)?;
+            seen.push(name);
+        }
+
+        Ok(())
+    }
+}
+
+fn set_vis(vis: &mut MethodVis, seen: &mut Option<Span>, item: &Item) -> Result<()> {
+    if seen.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    vis.parse(item, |vis, family| {
+        *vis = family.expect_str()?.parse()?;
+        Ok(())
+    })?;
+
+    *seen = Some(item.span());
+    Ok(())
+}
+
+fn set_prefixes(prefixes: &mut MethodPrefixes, seen: &mut Option<Span>, item: &Item) -> Result<()> {
+    if seen.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    prefixes.parse(item, |prefix, family| {
+        *prefix = Some(family.expect_str()?.clone());
+        Ok(())
+    })?;
+
+    *seen = Some(item.span());
+    Ok(())
+}
+
+/// Parses `T: Clone + Send, U: Copy` in a string.
+fn where_predicates(value: &LitStr) -> Result<Vec<WherePredicate>> {
+    let predicates = value.parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?;
+    Ok(predicates.into_iter().collect())
+}
+
+/// Adds the predicates of `bound = "..."` to `bounds` or, for
+/// `bound(into = "...", ...)`, to those of the families in `method_bounds`.
+fn add_bounds(bounds: &mut Vec<WherePredicate>, method_bounds: &mut MethodBounds, item: &Item) -> Result<()> {
+    match item.value {
+        Value::Str(ref value) => bounds.extend(where_predicates(value)?),
+        _ => method_bounds.parse(item, |bounds, family| {
+            bounds.extend(where_predicates(family.expect_str()?)?);
+            Ok(())
+        })?,
+    }
+
+    Ok(())
+}
+
+/// Parses `#[variation(cfg(...))]`, either a predicate gating everything
+/// generated, or predicates of the method families like
+/// `cfg(is = "feature = \"checks\"")`.
+fn set_cfg(cfg: &mut Option<TokenStream>, method_cfg: &mut MethodCfg, seen: &mut Option<Span>, item: &Item) -> Result<()> {
+    let tokens = list_tokens(item)?;
+
+    if !lists_families(tokens) {
+        if cfg.is_some() {
+            return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+        }
+
+        *cfg = Some(tokens.clone());
+        return Ok(());
+    }
+
+    if seen.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    method_cfg.parse(item, |cfg, family| {
+        *cfg = Some(family.expect_str()?.parse()?);
+        Ok(())
+    })?;
+
+    *seen = Some(item.span());
+    Ok(())
+}
+
+/// The tokens in the parentheses of `item`, such as `feature = "x"` of
+/// `cfg(feature = "x")`.
+fn list_tokens(item: &Item) -> Result<&TokenStream> {
+    match item.value {
+        Value::List(ref tokens) => Ok(tokens),
+        _ => Err(Error::new(item.span(), format!("expected `{}(...)`", item.name))),
+    }
+}
+
+/// Whether `tokens` assign strings to the method families like
+/// `is = "..."`, rather than being a `cfg` predicate or an attribute.
+fn lists_families(tokens: &TokenStream) -> bool {
+    match Punctuated::<Item, Token![,]>::parse_terminated.parse2(tokens.clone()) {
+        Ok(families) => {
+            !families.is_empty()
+                && families.iter().all(|family| match family.value {
+                    Value::Str(_) => FAMILIES.contains(&&*family.name.to_string()),
+                    _ => false,
+                })
+        }
+        Err(_) => false,
+    }
+}
+
+/// Parses `#[variation(attr(...))]` on the enum, either an attribute put on
+/// everything generated, or attributes of the method families like
+/// `attr(is = "inline")`.
+fn add_attrs(attrs: &mut Vec<TokenStream>, method_attrs: &mut MethodAttrs, item: &Item) -> Result<()> {
+    let tokens = list_tokens(item)?;
+
+    if !lists_families(tokens) {
+        attrs.push(tokens.clone());
+        return Ok(());
+    }
+
+    method_attrs.parse(item, |attrs, family| {
+        attrs.push(family.expect_str()?.parse()?);
+        Ok(())
+    })
+}
+
+/// Parses `#[variation(doc_hidden)]` on the enum, hiding everything
+/// generated, or `doc_hidden(is, ...)` hiding the methods of the listed
+/// families.
+fn set_doc_hidden(
+    attrs: &mut Vec<TokenStream>,
+    method_attrs: &mut MethodAttrs,
+    seen: &mut Option<Span>,
+    item: &Item,
+) -> Result<()> {
+    if seen.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    let hidden = quote::quote!(doc(hidden));
+
+    match item.value {
+        Value::Word => attrs.push(hidden),
+        Value::List(_) => {
+            let listed = listed(item)?;
+            let families = [
+                (listed.is, &mut method_attrs.is),
+                (listed.as_ref, &mut method_attrs.as_ref),
+                (listed.as_mut, &mut method_attrs.as_mut),
+                (listed.into, &mut method_attrs.into),
+            ];
+
+            for (listed, attrs) in families {
+                if listed.is_some() {
+                    attrs.push(hidden.clone());
+                }
+            }
+        }
+        Value::Str(_) => return Err(Error::new(item.span(), "expected `doc_hidden` or `doc_hidden(is, ...)`")),
+    }
+
+    *seen = Some(item.span());
+    Ok(())
+}
+
+/// Sets every family for `skip`, or those listed for `skip(into, ...)`.
+fn set_skip(skipped: &mut Skipped, item: &Item) -> Result<()> {
+    if skipped.is.is_some() || skipped.as_ref.is_some() || skipped.as_mut.is_some() || skipped.into.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    match item.value {
+        Value::Word => *skipped = Skipped::all(item.span()),
+        Value::List(_) => *skipped = listed(item)?,
+        Value::Str(_) => return Err(Error::new(item.span(), "expected `skip` or `skip(into, ...)`")),
+    }
+
+    Ok(())
+}
+
+/// The families named in a list like `skip(into, as_mut)`.
+fn listed(item: &Item) -> Result<Skipped> {
+    let mut listed = Skipped::default();
+
+    listed.parse(item, |listed, family| {
+        family.expect_word()?;
+        *listed = Some(family.span());
+        Ok(())
+    })?;
+
+    Ok(listed)
+}
+
+/// Parses `#[variation(methods(is, as))]`, skipping the families that aren't
+/// listed on every variant.
+fn set_methods(skipped: &mut Skipped, seen: &mut Option<Span>, item: &Item) -> Result<()> {
+    if seen.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    if let Value::Word | Value::Str(_) = item.value {
+        return Err(Error::new(item.span(), "expected `methods(is, as, ...)`"));
+    }
+
+    *seen = Some(item.span());
+    let listed = listed(item)?;
+    let unlisted = |family: Option<Span>| match family {
+        Some(_) => None,
+        None => Some(item.span()),
+    };
+
+    *skipped = Families {
+        is: unlisted(listed.is),
+        as_ref: unlisted(listed.as_ref),
+        as_mut: unlisted(listed.as_mut),
+        into: unlisted(listed.into),
+    };
+
+    Ok(())
+}
+
+fn set_case(case: &mut Option<Case>, item: &Item) -> Result<()> {
+    let value = item.expect_str()?;
+
+    if case.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *case = Some(Case::parse(value)?);
+    Ok(())
+}
+
+/// The where clause of a generated method with `predicates`, if it has any.
+pub(crate) fn method_where_clause(predicates: &[WherePredicate]) -> TokenStream {
+    if predicates.is_empty() {
+        TokenStream::new()
+    } else {
+        quote::quote!(where #(#predicates),*)
+    }
+}
+
+/// Options set on the enum itself.
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    /// `#[variation(boxed)]`, generate `into_*_boxed` taking `Box<Self>`.
+    pub boxed: Option<Span>,
+    /// `#[variation(either)]`, generate conversions to and from
+    /// `either::Either`.
+    pub either: Option<Span>,
+    /// `#[variation(fold)]`, generate `fold` and `fold_ref`, along with
+    /// `either` and `either_ref` for two variants with data.
+    pub fold: Option<Span>,
+    /// `#[variation(visitor)]`, generate visitor traits and `accept`.
+    pub visitor: Option<Span>,
+    /// `#[variation(iterator_ext)]`, generate an iterator extension trait.
+    pub iterator_ext: Option<Span>,
+    /// `#[variation(option_ext)]`, generate an extension trait for optional
+    /// values of the enum.
+    pub option_ext: Option<Span>,
+    /// `#[variation(partition)]`, generate `partition_variants`.
+    pub partition: Option<Span>,
+    /// `#[variation(dyn_iter = "...")]`, the trait `children` yields the
+    /// inner values as.
+    pub dyn_iter: Option<(Span, Path)>,
+    /// `#[variation(markers)]`, generate the `{enum}_markers` module and the
+    /// `get` methods.
+    pub markers: Option<Span>,
+    /// `#[variation(companion_derive(...))]`, the traits the generated
+    /// structs and projection enums derive along with their own.
+    pub companion_derive: Option<(Span, Vec<Path>)>,
+    /// `#[variation(match_macro)]` or `#[variation(match_macro = "...")]`,
+    /// generate a macro matching every variant, named `match_{enum}` unless
+    /// a name is given.
+    pub match_macro: Option<(Span, Option<Ident>)>,
+    /// `#[variation(kind)]`, generate the `{Enum}Kind` enum and `kind`.
+    pub kind: Option<Span>,
+    /// `#[variation(kind(variant_kind))]`, implement the `VariantKind` trait
+    /// of `variation-kind` as well.
+    pub variant_kind: Option<Span>,
+    /// `#[variation(kind(serde))]`, implement `Serialize` and `Deserialize`
+    /// of `serde` for the kind enum.
+    pub kind_serde: Option<Span>,
+    /// `#[variation(group_by_kind)]`, generate `group_by_kind`, implies `kind`.
+    pub group_by_kind: Option<Span>,
+    /// `#[variation(count_kinds)]`, generate the `{Enum}KindCounts` struct.
+    pub count_kinds: Option<Span>,
+    /// `#[variation(drain)]`, generate `drain_*` and `retain_kind`, implies
+    /// `kind`.
+    pub drain: Option<Span>,
+    /// `#[variation(const_fn)]`, generate `is_*`, `as_*` and `variant_name` as
+    /// `const fn`.
+    pub const_fn: Option<Span>,
+    /// `#[variation(must_use)]`, mark the methods returning values without
+    /// other effects `#[must_use]`.
+    pub must_use: Option<Span>,
+    /// `#[variation(inline)]`, `inline(always)` or `inline(never)`, the
+    /// `#[inline]` hint of the accessor methods.
+    pub inline: Option<Inline>,
+    /// `#[variation(display)]`, generate `Display` writing the variant name.
+    pub display: Option<Span>,
+    /// `#[variation(debug = "redacted")]`, generate `Debug` writing only the
+    /// variant name.
+    pub debug_redacted: Option<Span>,
+    /// `#[variation(repr)]`, generate `from_repr` and `to_repr` for fieldless
+    /// enums.
+    pub repr: Option<Span>,
+    /// `#[variation(try_from_repr)]`, generate `TryFrom<Repr>` for fieldless
+    /// enums, implies `repr`.
+    pub try_from_repr: Option<Span>,
+    /// `#[variation(iter)]`, generate `VARIANTS` and `iter` for fieldless
+    /// enums.
+    pub iter: Option<Span>,
+    /// `#[variation(from_str)]`, generate `FromStr` for fieldless enums.
+    pub from_str: Option<Span>,
+    /// `#[variation(as_str)]`, generate `as_str` for fieldless enums.
+    pub as_str: Option<Span>,
+    /// `#[variation(cycle)]`, generate `next` and `prev` for fieldless enums.
+    pub cycle: Option<Span>,
+    /// `#[variation(inner)]`, generate `into_inner`, `as_inner`, `map_inner`
+    /// and their variations for enums whose variants all wrap the same type.
+    pub inner: Option<Span>,
+    /// `#[variation(into_ok_or_err)]`, generate `into_ok_or_err` for enums
+    /// whose two variants wrap the same type.
+    pub into_ok_or_err: Option<Span>,
+    /// `#[variation(tag)]`, generate `tag`.
+    pub tag: Option<Span>,
+    /// `#[variation(error)]`, generate `Display` and `Error` for error enums.
+    pub error: Option<Span>,
+    /// `#[variation(ref_enum)]`, generate the `{Enum}Ref` enum and `as_ref`.
+    pub ref_enum: Option<Span>,
+    /// `#[variation(mut_enum)]`, generate the `{Enum}Mut` enum and `as_mut`.
+    pub mut_enum: Option<Span>,
+    /// `#[variation(pin_project)]`, generate the `{Enum}Proj` enum and
+    /// `project`.
+    pub pin_project: Option<Span>,
+    /// `#[variation(deref)]`, generate `Deref` and `DerefMut` for enums whose
+    /// variants all wrap the same type.
+    pub deref: Option<Span>,
+    /// `#[variation(delegate(...))]`, the traits to implement by forwarding
+    /// to the inner value.
+    pub delegate: Vec<(Span, Delegate)>,
+    /// `#[variation(delegate_fn(...))]`, the methods to generate by calling
+    /// them on the inner value.
+    pub delegate_fns: Vec<DelegateFn>,
+    /// `#[variation(dispatch = "...")]`, the traits marked with
+    /// `#[variation::dispatchable]` to implement by forwarding every method
+    /// to the inner value.
+    pub dispatch: Vec<(Span, Path)>,
+    /// `#[variation(vis(...))]`, the visibilities of the accessor methods.
+    pub method_vis: MethodVis,
+    method_vis_span: Option<Span>,
+    /// `#[variation(bound = "...")]`, the extra where predicates of the
+    /// inherent impl.
+    pub bounds: Vec<WherePredicate>,
+    /// `#[variation(bound(...))]`, the extra where predicates of the
+    /// accessor methods.
+    pub method_bounds: MethodBounds,
+    /// `#[variation(rename_all = "...")]`.
+    pub rename_all: Option<Case>,
+    /// `#[variation(prefix(...))]`.
+    pub method_prefixes: MethodPrefixes,
+    method_prefixes_span: Option<Span>,
+    /// `#[variation(into_mode = "...")]`, what `into_*` returns for the wrong
+    /// variant instead of panicking.
+    pub into_mode: Option<IntoMode>,
+    /// `#[variation(no_panic)]`, generate no `into_*` that panics and reject
+    /// the options asking for one.
+    pub no_panic: Option<Span>,
+    /// `#[variation(static_panic)]`, panic in `into_*` with a message fixed
+    /// at expansion, leaving out the variant the enum holds.
+    pub static_panic: Option<Span>,
+    /// `#[variation(trait = "...")]`, the trait to generate the accessor
+    /// methods in instead of the inherent impl.
+    pub accessor_trait: Option<Ident>,
+    /// `#[variation(trait_vis = "...")]`, the visibility of the trait in place
+    /// of the enum's.
+    pub trait_vis: Option<(Span, Visibility)>,
+    /// `#[variation(trait_object_safe)]`, leave the consuming methods out of
+    /// the trait so it can be used as a trait object.
+    pub trait_object_safe: Option<Span>,
+    /// `#[variation(remote = "...")]` in `variation_remote!`, the path of the
+    /// enum the mirror definition stands for.
+    pub remote: Option<(Span, Path)>,
+    /// `#[variation(variants(...))]` on a newtype struct, the variants of the
+    /// enum it wraps.
+    pub variants: Option<(Span, Vec<Variant>)>,
+    /// `#[variation(subset_of = "...")]`, the path of the enum holding all of
+    /// the variants, to convert from and to.
+    pub subset_of: Option<(Span, Path)>,
+    /// `#[variation(subset(...))]`, the enums to generate with some of the
+    /// variants.
+    pub subsets: Vec<Subset>,
+    /// `#[variation(crate = "...")]`, the path of the `variation-kind` crate
+    /// in place of `::variation_kind` for `kind(variant_kind)`.
+    pub crate_path: Option<(Span, Path)>,
+    /// `#[variation(cfg(...))]`, the predicate gating everything generated.
+    pub cfg: Option<TokenStream>,
+    /// `#[variation(cfg(is = "...", ...))]`, the predicates gating the
+    /// accessor methods.
+    pub method_cfg: MethodCfg,
+    method_cfg_span: Option<Span>,
+    /// `#[variation(attr(...))]`, the attributes put on everything generated,
+    /// including `doc(hidden)` from `#[variation(doc_hidden)]`.
+    pub attrs: Vec<TokenStream>,
+    /// `#[variation(attr(is = "...", ...))]`, the attributes of the accessor
+    /// methods, including `doc(hidden)` from `#[variation(doc_hidden(...))]`.
+    pub method_attrs: MethodAttrs,
+    doc_hidden_span: Option<Span>,
+    /// `#[variation(only(...))]`, the variants that get methods of their own,
+    /// the others are skipped.
+    pub only: Option<(Span, Vec<Ident>)>,
+    /// `#[variation(methods(...))]`, the families left out for every variant.
+    pub skip: Skipped,
+    skip_span: Option<Span>,
+}
+
+impl ContainerAttrs {
+    /// Whether the `{Enum}Kind` enum is generated, either requested directly
+    /// or by a feature that uses it.
+    pub(crate) fn kind(&self) -> bool {
+        self.kind.is_some() || self.group_by_kind.is_some() || self.drain.is_some()
+    }
+
+    /// The path of the `variation-kind` crate in the generated code.
+    pub(crate) fn kind_crate(&self) -> TokenStream {
+        match self.crate_path {
+            Some((_, ref path)) => quote::quote!(#path),
+            None => quote::quote!(::variation_kind),
+        }
+    }
+
+    /// The first option generating items next to the enum, such as the
+    /// `{Enum}Kind` enum or a trait, rather than only methods and impls.
+    pub(crate) fn own_items(&self) -> Option<(Span, &'static str)> {
+        let options = [
+            (self.kind, "kind"),
+            (self.group_by_kind, "group_by_kind"),
+            (self.drain, "drain"),
+            (self.count_kinds, "count_kinds"),
+            (self.visitor, "visitor"),
+            (self.iterator_ext, "iterator_ext"),
+            (self.option_ext, "option_ext"),
+            (self.partition, "partition"),
+            (self.markers, "markers"),
+            (self.match_macro.as_ref().map(|&(span, _)| span), "match_macro"),
+            (self.ref_enum, "ref_enum"),
+            (self.mut_enum, "mut_enum"),
+            (self.pin_project, "pin_project"),
+            (self.accessor_trait.as_ref().map(Ident::span), "trait"),
+            (self.subsets.first().map(|subset| subset.span), "subset"),
+        ];
+
+        options.iter().find_map(|&(span, option)| span.map(|span| (span, option)))
+    }
+
+    /// `const` for the methods that can be `const fn` with
+    /// `#[variation(const_fn)]`, nothing otherwise.
+    pub(crate) fn constness(&self) -> TokenStream {
+        match self.const_fn {
+            Some(_) => quote::quote!(const),
+            None => TokenStream::new(),
+        }
+    }
+
+    /// `#[must_use = "..."]` with `reason` with `#[variation(must_use)]`,
+    /// nothing otherwise.
+    pub(crate) fn must_use(&self, reason: &str) -> TokenStream {
+        match self.must_use {
+            Some(_) => quote::quote!(#[must_use = #reason]),
+            None => TokenStream::new(),
+        }
+    }
+
+    /// The `#[cfg]` attributes of the method families.
+    pub(crate) fn method_cfg_attrs(&self) -> Families<TokenStream> {
+        let attr = |cfg: &Option<TokenStream>| match cfg {
+            Some(predicate) => quote::quote!(#[cfg(#predicate)]),
+            None => TokenStream::new(),
+        };
+
+        Families {
+            is: attr(&self.method_cfg.is),
+            as_ref: attr(&self.method_cfg.as_ref),
+            as_mut: attr(&self.method_cfg.as_mut),
+            into: attr(&self.method_cfg.into),
+        }
+    }
+
+    /// The attributes of `#[variation(attr(is = "...", ...))]` of the method
+    /// families.
+    pub(crate) fn method_extra_attrs(&self) -> Families<TokenStream> {
+        let attrs = |attrs: &Vec<TokenStream>| quote::quote!(#(#[#attrs])*);
+
+        Families {
+            is: attrs(&self.method_attrs.is),
+            as_ref: attrs(&self.method_attrs.as_ref),
+            as_mut: attrs(&self.method_attrs.as_mut),
+            into: attrs(&self.method_attrs.into),
+        }
+    }
+
+    /// The `#[must_use]` attributes of the method families.
+    pub(crate) fn method_must_use(&self) -> Families<TokenStream> {
+        Families {
+            is: self.must_use("this only checks the variant of the enum"),
+            as_ref: self.must_use("this only borrows the inner values"),
+            as_mut: self.must_use("this only borrows the inner values"),
+            into: self.must_use("this consumes the enum, dropping the inner values if they aren't used"),
+        }
+    }
+
+    /// The attribute generating `from_repr` and `to_repr`, if any.
+    pub(crate) fn repr(&self) -> Option<(Span, &'static str)> {
+        match (self.repr, self.try_from_repr) {
+            (_, Some(span)) => Some((span, "try_from_repr")),
+            (Some(span), None) => Some((span, "repr")),
+            (None, None) => None,
+        }
+    }
+
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = ContainerAttrs::default();
+        let mut compat = None;
+
+        for item in items(attrs)? {
+            match &*item.name.to_string() {
+                "boxed" => set_flag(&mut parsed.boxed, &item)?,
+                "either" if cfg!(feature = "either") => set_flag(&mut parsed.either, &item)?,
+                "either" => {
+                    return Err(Error::new(
+                        item.span(),
+                        "`#[variation(either)]` requires the `either` feature of `variation`",
+                    ))
+                }
+                "fold" => set_flag(&mut parsed.fold, &item)?,
+                "visitor" => set_flag(&mut parsed.visitor, &item)?,
+                "iterator_ext" => set_flag(&mut parsed.iterator_ext, &item)?,
+                "option_ext" => set_flag(&mut parsed.option_ext, &item)?,
+                "partition" => set_flag(&mut parsed.partition, &item)?,
+                "dyn_iter" => set_path(&mut parsed.dyn_iter, &item)?,
+                "markers" => set_flag(&mut parsed.markers, &item)?,
+                "companion_derive" => set_paths(&mut parsed.companion_derive, &item)?,
+                "match_macro" => set_match_macro(&mut parsed.match_macro, &item)?,
+                "kind" => set_kind(&mut parsed, &item)?,
+                "group_by_kind" => set_flag(&mut parsed.group_by_kind, &item)?,
+                "count_kinds" => set_flag(&mut parsed.count_kinds, &item)?,
+                "drain" => set_flag(&mut parsed.drain, &item)?,
+                "const_fn" => set_flag(&mut parsed.const_fn, &item)?,
+                "must_use" => set_flag(&mut parsed.must_use, &item)?,
+                "inline" => set_inline(&mut parsed.inline, &item)?,
+                "display" => set_flag(&mut parsed.display, &item)?,
+                "debug" => set_debug(&mut parsed.debug_redacted, &item)?,
+                "repr" => set_flag(&mut parsed.repr, &item)?,
+                "try_from_repr" => set_flag(&mut parsed.try_from_repr, &item)?,
+                "iter" => set_flag(&mut parsed.iter, &item)?,
+                "from_str" => set_flag(&mut parsed.from_str, &item)?,
+                "as_str" => set_flag(&mut parsed.as_str, &item)?,
+                "cycle" => set_flag(&mut parsed.cycle, &item)?,
+                "inner" => set_flag(&mut parsed.inner, &item)?,
+                "into_ok_or_err" => set_flag(&mut parsed.into_ok_or_err, &item)?,
+                "ref_enum" => set_flag(&mut parsed.ref_enum, &item)?,
+                "mut_enum" => set_flag(&mut parsed.mut_enum, &item)?,
+                "pin_project" => set_flag(&mut parsed.pin_project, &item)?,
+                "deref" => set_flag(&mut parsed.deref, &item)?,
+                "error" => set_flag(&mut parsed.error, &item)?,
+                "tag" => set_flag(&mut parsed.tag, &item)?,
+                "delegate" => add_delegates(&mut parsed.delegate, &item)?,
+                "delegate_fn" => parsed.delegate_fns.push(DelegateFn::parse(&item)?),
+                "dispatch" => parsed.dispatch.push((item.span(), item.expect_str()?.parse()?)),
+                "vis" => set_vis(&mut parsed.method_vis, &mut parsed.method_vis_span, &item)?,
+                "bound" => add_bounds(&mut parsed.bounds, &mut parsed.method_bounds, &item)?,
+                "rename_all" => set_case(&mut parsed.rename_all, &item)?,
+                "prefix" => set_prefixes(&mut parsed.method_prefixes, &mut parsed.method_prefixes_span, &item)?,
+                "into_mode" => set_into_mode(&mut parsed.into_mode, &item)?,
+                "compat" => set_compat(&mut compat, &item)?,
+                "no_panic" => set_flag(&mut parsed.no_panic, &item)?,
+                "static_panic" => set_flag(&mut parsed.static_panic, &item)?,
+                "only" => set_only(&mut parsed.only, &item)?,
+                "methods" => set_methods(&mut parsed.skip, &mut parsed.skip_span, &item)?,
+                "cfg" => set_cfg(&mut parsed.cfg, &mut parsed.method_cfg, &mut parsed.method_cfg_span, &item)?,
+                "attr" => add_attrs(&mut parsed.attrs, &mut parsed.method_attrs, &item)?,
+                "doc_hidden" => set_doc_hidden(
+                    &mut parsed.attrs,
+                    &mut parsed.method_attrs,
+                    &mut parsed.doc_hidden_span,
+                    &item,
+                )?,
+                "trait" => set_accessor_trait(&mut parsed.accessor_trait, &item)?,
+                "trait_vis" => set_trait_vis(&mut parsed.trait_vis, &item)?,
+                "trait_object_safe" => set_flag(&mut parsed.trait_object_safe, &item)?,
+                "remote" => set_path(&mut parsed.remote, &item)?,
+                "variants" => set_variants(&mut parsed.variants, &item)?,
+                "subset_of" => set_path(&mut parsed.subset_of, &item)?,
+                "subset" => parsed.subsets.push(Subset::parse(&item)?),
+                "crate" => set_path(&mut parsed.crate_path, &item)?,
+                _ => return Err(item.unknown()),
+            }
+        }
+
+        if let Some(span) = parsed.no_panic {
+            let conflict = match (parsed.into_mode, compat) {
+                (Some(IntoMode::Panic), _) => Some("into_mode = \"panic\""),
+                (_, Some((_, Compat::DeriveMore))) => Some("compat = \"derive_more\""),
+                _ => None,
+            };
+
+            if let Some(attribute) = conflict {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "`#[variation(no_panic)]` can't be combined with `#[variation({})]`, as it makes `into_*` \
+                         panic",
+                        attribute
+                    ),
+                ));
+            }
+        }
+
+        if let Some((span, compat)) = compat {
+            compat.apply(&mut parsed, span)?;
+        }
+
+        // The accessors of remote enums always go in a trait.
+        if parsed.accessor_trait.is_none() && parsed.remote.is_none() {
+            let trait_only = parsed
+                .trait_vis
+                .as_ref()
+                .map(|&(span, _)| (span, "trait_vis = \"...\""))
+                .or_else(|| parsed.trait_object_safe.map(|span| (span, "trait_object_safe")));
+
+            if let Some((span, attribute)) = trait_only {
+                return Err(Error::new(
+                    span,
+                    format!("`#[variation({})]` requires `#[variation(trait = \"...\")]`", attribute),
+                ));
+            }
+        } else if let Some(span) = parsed.method_vis_span {
+            return Err(Error::new(
+                span,
+                "`#[variation(vis(...))]` can't be combined with `#[variation(trait = \"...\")]`, as trait methods \
+                 have the visibility of the trait, set with `#[variation(trait_vis = \"...\")]`",
+            ));
+        } else if let Some(span) = parsed.const_fn {
+            return Err(Error::new(
+                span,
+                "`#[variation(const_fn)]` can't be combined with `#[variation(trait = \"...\")]`, as trait methods \
+                 can't be `const fn`",
+            ));
+        }
+
+        Ok(parsed)
+    }
+}
+
+fn set_only(only: &mut Option<(Span, Vec<Ident>)>, item: &Item) -> Result<()> {
+    let names = match item.value {
+        Value::List(ref tokens) => Punctuated::<Ident, Token![,]>::parse_terminated.parse2(tokens.clone())?,
+        _ => return Err(Error::new(item.span(), "expected `only(Variant, ...)`")),
+    };
+
+    if only.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *only = Some((item.span(), names.into_iter().collect()));
+    Ok(())
+}
+
+fn set_accessor_trait(accessor_trait: &mut Option<Ident>, item: &Item) -> Result<()> {
+    let value = item.expect_str()?;
+    let name = value
+        .parse()
+        .map_err(|_| Error::new(value.span(), format!("`{}` is not a valid trait name", value.value())))?;
+
+    if accessor_trait.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *accessor_trait = Some(name);
+    Ok(())
+}
+
+fn set_match_macro(match_macro: &mut Option<(Span, Option<Ident>)>, item: &Item) -> Result<()> {
+    let name = match item.value {
+        Value::Word => None,
+        Value::Str(ref value) => Some(
+            value
+                .parse()
+                .map_err(|_| Error::new(value.span(), format!("`{}` is not a valid macro name", value.value())))?,
+        ),
+        Value::List(_) => return Err(Error::new(item.span(), "expected `match_macro` or `match_macro = \"...\"`")),
+    };
+
+    if match_macro.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *match_macro = Some((item.span(), name));
+    Ok(())
+}
+
+/// Parses `#[variation(kind)]`, or `#[variation(kind(...))]` with the traits
+/// of other crates the enum and its kind enum implement as well.
+fn set_kind(parsed: &mut ContainerAttrs, item: &Item) -> Result<()> {
+    let options = match item.value {
+        Value::Word => Punctuated::new(),
+        Value::List(ref tokens) => Punctuated::<Item, Token![,]>::parse_terminated.parse2(tokens.clone())?,
+        Value::Str(_) => return Err(Error::new(item.span(), "expected `kind` or `kind(serde, variant_kind)`")),
+    };
+
+    if parsed.kind.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    parsed.kind = Some(item.span());
+
+    for option in &options {
+        match &*option.name.to_string() {
+            "serde" => set_flag(&mut parsed.kind_serde, option)?,
+            "variant_kind" => set_flag(&mut parsed.variant_kind, option)?,
+            _ => return Err(Error::new(option.span(), format!("unknown `kind` option `{}`", option.name))),
+        }
+    }
+
+    Ok(())
+}
+
+fn set_paths(target: &mut Option<(Span, Vec<Path>)>, item: &Item) -> Result<()> {
+    let paths = item.expect_paths()?;
+
+    if target.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *target = Some((item.span(), paths));
+    Ok(())
+}
+
+fn set_path(target: &mut Option<(Span, Path)>, item: &Item) -> Result<()> {
+    let path = item.expect_str()?.parse()?;
+
+    if target.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *target = Some((item.span(), path));
+    Ok(())
+}
+
+fn set_variants(variants: &mut Option<(Span, Vec<Variant>)>, item: &Item) -> Result<()> {
+    let parsed = match item.value {
+        Value::List(ref tokens) => Punctuated::<Variant, Token![,]>::parse_terminated.parse2(tokens.clone())?,
+        _ => return Err(Error::new(item.span(), format!("expected `{}(Variant(Type), ...)`", item.name))),
+    };
+
+    if variants.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *variants = Some((item.span(), parsed.into_iter().collect()));
+    Ok(())
+}
+
+/// The options of the enum `variation_remote!` takes, those of the accessor
+/// methods.
+const REMOTE_CONTAINER_ATTRS: &[&str] = &[
+    "attr", "boxed", "bound", "cfg", "compat", "doc_hidden", "inline", "into_mode", "methods", "must_use",
+    "no_panic", "only", "prefix", "remote", "rename_all", "static_panic", "trait", "trait_object_safe", "trait_vis",
+];
+
+/// The options of the variants `variation_remote!` takes.
+const REMOTE_VARIANT_ATTRS: &[&str] =
+    &["as", "as_mut", "attr", "doc", "inline", "into", "into_mode", "is", "rename", "skip"];
+
+/// Checks that the mirror definition of `variation_remote!` only sets options
+/// of the accessor methods, as nothing else of the enum is generated.
+pub(crate) fn check_remote(ast: &DeriveInput) -> Result<()> {
+    check_available(
+        &ast.attrs,
+        enum_variants(ast),
+        REMOTE_CONTAINER_ATTRS,
+        REMOTE_VARIANT_ATTRS,
+        "in `variation_remote!`, which only generates the accessor methods",
+    )
+}
+
+/// The options of the enum available along with a placeholder variant, those
+/// generating nothing that would have to handle it.
+const PLACEHOLDER_CONTAINER_ATTRS: &[&str] = &[
+    "attr", "boxed", "bound", "cfg", "companion_derive", "compat", "const_fn", "count_kinds", "crate", "doc_hidden",
+    "drain", "from_str", "group_by_kind", "inline", "into_mode", "iter", "kind", "methods", "must_use", "only",
+    "prefix", "rename_all", "trait", "trait_object_safe", "trait_vis", "vis",
+];
+
+/// The options of the variants available along with a placeholder variant.
+const PLACEHOLDER_VARIANT_ATTRS: &[&str] = &[
+    "alias", "as", "as_mut", "attr", "default", "doc", "flatten", "from", "inline", "into", "into_mode", "is",
+    "panic_msg", "partial_eq", "placeholder", "props", "rename", "skip", "str", "try_into",
+];
+
+/// Checks that an enum with a placeholder variant only sets options that
+/// can leave it out.
+pub(crate) fn check_placeholder(ast: &DeriveInput) -> Result<()> {
+    check_available(
+        &ast.attrs,
+        enum_variants(ast),
+        PLACEHOLDER_CONTAINER_ATTRS,
+        PLACEHOLDER_VARIANT_ATTRS,
+        "with a placeholder variant, which it would have to handle",
+    )
+}
+
+/// The options of a newtype struct wrapping an enum, those of the
+/// forwarding accessor methods.
+const NEWTYPE_CONTAINER_ATTRS: &[&str] = &[
+    "attr", "bound", "cfg", "compat", "doc_hidden", "inline", "into_mode", "methods", "must_use", "no_panic",
+    "only", "prefix", "rename_all", "variants", "vis",
+];
+
+/// The options of the variants of `#[variation(variants(...))]`.
+const NEWTYPE_VARIANT_ATTRS: &[&str] =
+    &["as", "as_mut", "attr", "doc", "inline", "into", "into_mode", "is", "rename", "skip"];
+
+/// Checks that a newtype struct and the variants it lists only set options of
+/// the forwarding methods.
+pub(crate) fn check_newtype(ast: &DeriveInput, variants: &[Variant]) -> Result<()> {
+    check_available(
+        &ast.attrs,
+        variants,
+        NEWTYPE_CONTAINER_ATTRS,
+        NEWTYPE_VARIANT_ATTRS,
+        "on a newtype struct, which only forwards the accessor methods",
+    )
+}
+
+/// The options of the variants of `#[variation(flatten(...))]`, those naming
+/// the forwarded methods.
+const FLATTEN_VARIANT_ATTRS: &[&str] = &["as", "as_mut", "is", "rename", "skip"];
+
+/// Checks that the variants listed in `#[variation(flatten(...))]` only set
+/// options naming the forwarded methods.
+pub(crate) fn check_flatten(variants: &[Variant]) -> Result<()> {
+    check_available(
+        &[],
+        variants,
+        &[],
+        FLATTEN_VARIANT_ATTRS,
+        "in `flatten(...)`, which only forwards the `is_*`, `as_*` and `as_*_mut` methods",
+    )
+}
+
+/// The variants of `ast`, if it's an enum.
+fn enum_variants(ast: &DeriveInput) -> Vec<&Variant> {
+    match ast.data {
+        Data::Enum(ref data) => data.variants.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Checks that `attrs` of the enum and its `variants` only set the options in
+/// `container` and `variant`, and that the fields set none, where `context`
+/// tells why in the error.
+fn check_available<'a>(
+    attrs: &[Attribute],
+    variants: impl IntoIterator<Item = &'a Variant>,
+    container: &[&str],
+    variant: &[&str],
+    context: &str,
+) -> Result<()> {
+    let mut checked = vec![(attrs, container)];
+
+    for each in variants {
+        checked.push((&each.attrs, variant));
+        checked.extend(each.fields.iter().map(|field| (&field.attrs[..], &[][..])));
+    }
+
+    for (attrs, available) in checked {
+        if let Some(item) = items(attrs)?.into_iter().find(|item| !available.contains(&&*item.name.to_string())) {
+            return Err(Error::new(
+                item.span(),
+                format!("`#[variation({})]` isn't available {}", item.name, context),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn set_trait_vis(trait_vis: &mut Option<(Span, Visibility)>, item: &Item) -> Result<()> {
+    let vis = item.expect_str()?.parse()?;
+
+    if trait_vis.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *trait_vis = Some((item.span(), vis));
+    Ok(())
+}
+
+fn set_debug(redacted: &mut Option<Span>, item: &Item) -> Result<()> {
+    let mode = item.expect_str()?;
+
+    if mode.value() != "redacted" {
+        return Err(Error::new(
+            mode.span(),
+            format!("unknown `debug` mode `{}`, expected `redacted`", mode.value()),
+        ));
+    }
+
+    if redacted.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *redacted = Some(item.span());
+    Ok(())
+}
+
+/// What `into_*` returns for the wrong variant, set with
+/// `#[variation(into_mode = "...")]` on the enum or a variant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum IntoMode {
+    /// `into_mode = "panic"`, panic, unless set otherwise.
+    Panic,
+    /// `into_mode = "option"`, return `None`.
+    Option,
+    /// `into_mode = "result"`, return the enum itself in `Err`.
+    Result,
+}
+
+impl IntoMode {
+    fn parse(mode: &str) -> Option<Self> {
+        match mode {
+            "panic" => Some(IntoMode::Panic),
+            "option" => Some(IntoMode::Option),
+            "result" => Some(IntoMode::Result),
+            _ => None,
+        }
+    }
+
+    /// The return type of `into_*` with the inner type `inner`, taking
+    /// `receiver` such as `Self` or `Box<Self>`.
+    pub(crate) fn output(self, inner: &TokenStream, receiver: TokenStream) -> TokenStream {
+        match self {
+            IntoMode::Panic => inner.clone(),
+            IntoMode::Option => quote::quote!(::core::option::Option<#inner>),
+            IntoMode::Result => quote::quote!(::core::result::Result<#inner, #receiver>),
+        }
+    }
+}
+
+fn set_into_mode(into_mode: &mut Option<IntoMode>, item: &Item) -> Result<()> {
+    let mode = item.expect_str()?;
+    let parsed = match IntoMode::parse(&mode.value()) {
+        Some(parsed) => parsed,
+        None => {
+            return Err(Error::new(
+                mode.span(),
+                format!("unknown `into_mode` `{}`, expected `panic`, `option` or `result`", mode.value()),
+            ))
+        }
+    };
+
+    if into_mode.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *into_mode = Some(parsed);
+    Ok(())
+}
+
+/// The crate whose method names and signatures are mirrored with
+/// `#[variation(compat = "...")]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Compat {
+    /// `compat = "enum_as_inner"`, `into_*` returns the enum in `Err`.
+    EnumAsInner,
+    /// `compat = "derive_more"`, `into_*` is called `unwrap_*` and panics.
+    DeriveMore,
+}
+
+impl Compat {
+    fn name(self) -> &'static str {
+        match self {
+            Compat::EnumAsInner => "enum_as_inner",
+            Compat::DeriveMore => "derive_more",
+        }
+    }
+
+    /// Sets the options the mode stands for, which can't be set along with
+    /// it.
+    fn apply(self, parsed: &mut ContainerAttrs, span: Span) -> Result<()> {
+        let conflict = match self {
+            Compat::EnumAsInner => parsed.into_mode.map(|_| "into_mode = \"...\""),
+            Compat::DeriveMore => parsed
+                .into_mode
+                .map(|_| "into_mode = \"...\"")
+                .or_else(|| parsed.method_prefixes.into.as_ref().map(|_| "prefix(into = \"...\")")),
+        };
+
+        if let Some(attribute) = conflict {
+            return Err(Error::new(
+                span,
+                format!(
+                    "`#[variation(compat = \"{}\")]` can't be combined with `#[variation({})]`, as it sets \
+                     the `into_*` methods itself",
+                    self.name(),
+                    attribute
+                ),
+            ));
+        }
+
+        match self {
+            Compat::EnumAsInner => parsed.into_mode = Some(IntoMode::Result),
+            Compat::DeriveMore => {
+                parsed.into_mode = Some(IntoMode::Panic);
+                parsed.method_prefixes.into = Some(LitStr::new("unwrap_", span));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn set_compat(compat: &mut Option<(Span, Compat)>, item: &Item) -> Result<()> {
+    let mode = item.expect_str()?;
+    let parsed = match &*mode.value() {
+        "enum_as_inner" => Compat::EnumAsInner,
+        "derive_more" => Compat::DeriveMore,
+        _ => {
+            return Err(Error::new(
+                mode.span(),
+                format!(
+                    "unknown `compat` mode `{}`, expected `enum_as_inner` or `derive_more`",
+                    mode.value()
+                ),
+            ))
+        }
+    };
+
+    if compat.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *compat = Some((item.span(), parsed));
+    Ok(())
+}
+
+/// The `#[inline]` hint of the accessor methods, set with
+/// `#[variation(inline)]` on the enum or a variant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Inline {
+    /// `inline`, `#[inline]`.
+    Hint,
+    /// `inline(always)`, `#[inline(always)]`.
+    Always,
+    /// `inline(never)`, `#[inline(never)]`.
+    Never,
+}
+
+impl Inline {
+    /// The `#[inline]` attribute of `inline`, nothing without one.
+    pub(crate) fn attr(inline: Option<Inline>) -> TokenStream {
+        match inline {
+            None => TokenStream::new(),
+            Some(Inline::Hint) => quote::quote!(#[inline]),
+            Some(Inline::Always) => quote::quote!(#[inline(always)]),
+            Some(Inline::Never) => quote::quote!(#[inline(never)]),
+        }
+    }
+
+    /// The attributes of a method with a plain `#[inline]` in front, unless
+    /// they have an `#[inline]` of their own. The accessors are small enough
+    /// to be worth inlining across crates, which takes the attribute without
+    /// LTO.
+    pub(crate) fn with_default(attrs: TokenStream) -> TokenStream {
+        let tokens = attrs.clone().into_iter().collect::<Vec<_>>();
+        let inline = tokens.windows(2).any(|pair| match (&pair[0], &pair[1]) {
+            (TokenTree::Punct(punct), TokenTree::Group(group)) if punct.as_char() == '#' => {
+                group.stream().into_iter().next().is_some_and(|name| name.to_string() == "inline")
+            }
+            _ => false,
+        });
+
+        if inline {
+            attrs
+        } else {
+            quote::quote!(#[inline] #attrs)
+        }
+    }
+}
+
+fn set_inline(inline: &mut Option<Inline>, item: &Item) -> Result<()> {
+    let parsed = match item.value {
+        Value::Word => Inline::Hint,
+        Value::List(ref tokens) => match parse2::<Ident>(tokens.clone()) {
+            Ok(ref hint) if hint == "always" => Inline::Always,
+            Ok(ref hint) if hint == "never" => Inline::Never,
+            _ => return Err(Error::new(item.span(), "expected `inline`, `inline(always)` or `inline(never)`")),
+        },
+        Value::Str(_) => return Err(Error::new(item.span(), "expected `inline`, `inline(always)` or `inline(never)`")),
+    };
+
+    if inline.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *inline = Some(parsed);
+    Ok(())
+}
+
+fn set_str(string: &mut Option<LitStr>, item: &Item) -> Result<()> {
+    let value = item.expect_str()?;
+
+    if string.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    *string = Some(value.clone());
+    Ok(())
+}
+
+fn set_error_msg(msg: &mut Option<LitStr>, item: &Item) -> Result<()> {
+    let options = match item.value {
+        Value::List(ref tokens) => Punctuated::<Item, Token![,]>::parse_terminated.parse2(tokens.clone())?,
+        _ => return Err(Error::new(item.span(), "expected `error(msg = \"...\")`")),
+    };
+
+    for option in &options {
+        match &*option.name.to_string() {
+            "msg" => set_str(msg, option)?,
+            _ => return Err(Error::new(option.span(), format!("unknown `error` option `{}`", option.name))),
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses `#[variation(props(key = "...", ...))]`, adding to the properties
+/// of earlier lists on the same variant.
+fn set_props(props: &mut Vec<(Ident, LitStr)>, item: &Item) -> Result<()> {
+    let options = match item.value {
+        Value::List(ref tokens) => Punctuated::<Item, Token![,]>::parse_terminated.parse2(tokens.clone())?,
+        _ => return Err(Error::new(item.span(), "expected `props(key = \"...\", ...)`")),
+    };
+
+    for option in &options {
+        let value = option.expect_str()?;
+
+        if props.iter().any(|(key, _)| key.unraw() == option.name.unraw()) {
+            return Err(Error::new(option.name.span(), format!("duplicate property `{}`", option.name)));
+        }
+
+        props.push((option.name.clone(), value.clone()));
+    }
+
+    Ok(())
+}
+
+/// The target of a reference conversion such as `as_ref`, which is either the
+/// field's own type or, when given as `as_ref(Type)`, a type the field
+/// converts to itself.
+pub(crate) struct Target {
+    pub span: Span,
+    pub ty: Option<Type>,
+}
+
+fn set_target(target: &mut Option<Target>, item: &Item) -> Result<()> {
+    if target.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    let ty = match item.value {
+        Value::Word => None,
+        Value::List(ref tokens) => Some(syn::parse2(tokens.clone())?),
+        Value::Str(_) => {
+            return Err(Error::new(
+                item.span(),
+                format!("expected `{0}` or `{0}(Type)`", item.name),
+            ))
+        }
+    };
+
+    *target = Some(Target { span: item.span(), ty });
+    Ok(())
+}
+
+/// What `#[variation(from(...))]` forwards to the `From` impl of the inner
+/// type.
+pub(crate) enum Forward {
+    /// `forward`, a blanket impl for every type converting into it.
+    Any(Span),
+    /// `forward(Type, ...)`, an impl for each of the types.
+    Types(Vec<Type>),
+}
+
+/// Parses `#[variation(from)]`, `from(forward)` or `from(forward(Type, ...))`.
+fn set_from(from: &mut Option<Span>, forward: &mut Option<Forward>, item: &Item) -> Result<()> {
+    if from.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    let options = match item.value {
+        Value::Word => Punctuated::new(),
+        Value::List(ref tokens) => Punctuated::<Item, Token![,]>::parse_terminated.parse2(tokens.clone())?,
+        Value::Str(_) => return Err(Error::new(item.span(), "expected `from` or `from(forward)`")),
+    };
+
+    for option in &options {
+        if option.name != "forward" {
+            return Err(Error::new(option.span(), format!("unknown `from` option `{}`", option.name)));
+        }
+
+        if forward.is_some() {
+            return Err(Error::new(option.span(), "duplicate `forward` attribute"));
+        }
+
+        *forward = Some(match option.value {
+            Value::Word => Forward::Any(option.span()),
+            Value::List(ref tokens) => {
+                let types = Punctuated::<Type, Token![,]>::parse_terminated.parse2(tokens.clone())?;
+
+                if types.is_empty() {
+                    return Err(Error::new(option.span(), "expected `forward(Type, ...)`"));
+                }
+
+                Forward::Types(types.into_iter().collect())
+            }
+            Value::Str(_) => return Err(Error::new(option.span(), "expected `forward` or `forward(Type, ...)`")),
+        });
+    }
+
+    *from = Some(item.span());
+    Ok(())
+}
+
+/// Options set on an individual variant.
+#[derive(Default)]
+pub(crate) struct VariantAttrs {
+    /// `#[variation(from)]`, generate `From<Inner> for Enum`.
+    pub from: Option<Span>,
+    /// `#[variation(from(forward))]` or `from(forward(Type, ...))`, the types
+    /// `From` also converts from through `Into<Inner>`.
+    pub forward: Option<Forward>,
+    /// `#[variation(try_into)]`, generate `TryFrom<Enum> for Inner`.
+    pub try_into: Option<Span>,
+    /// `#[variation(partial_eq)]`, generate `PartialEq<Inner> for Enum` and
+    /// the reflected impl.
+    pub partial_eq: Option<Span>,
+    /// `#[variation(as_ref)]`, generate `AsRef` and `AsMut` for the enum.
+    pub as_ref: Option<Target>,
+    /// `#[variation(borrow)]`, generate `Borrow` and `BorrowMut` for the enum.
+    pub borrow: Option<Target>,
+    /// `#[variation(ok)]`, the success variant of a `Result`-like enum.
+    pub ok: Option<Span>,
+    /// `#[variation(err)]`, the failure variant of a `Result`-like enum.
+    pub err: Option<Span>,
+    /// `#[variation(some)]`, the variant holding a value in an `Option`-like
+    /// enum.
+    pub some: Option<Span>,
+    /// `#[variation(none)]`, the unit variant of an `Option`-like enum.
+    pub none: Option<Span>,
+    /// `#[variation(default)]`, generate `Default` for the enum returning this
+    /// variant.
+    pub default: Option<Span>,
+    /// `#[variation(str = "...")]`, the string `as_str` and `FromStr` use for
+    /// this variant in place of its name.
+    pub str: Option<LitStr>,
+    /// `#[variation(error(msg = "..."))]`, the message the `Display` impl
+    /// generated by `#[variation(error)]` writes for this variant.
+    pub error_msg: Option<LitStr>,
+    /// `#[variation(tag = "...")]`, the string `tag` returns for this variant
+    /// in place of its snake case name.
+    pub tag: Option<LitStr>,
+    /// `#[variation(props(key = "...", ...))]`, the properties `properties`
+    /// and `get_property` return for this variant, in the order given.
+    pub props: Vec<(Ident, LitStr)>,
+    /// `#[variation(companion_derive(...))]`, the traits the marker type of
+    /// this variant derives in place of those set on the enum.
+    pub companion_derive: Option<(Span, Vec<Path>)>,
+    /// `#[variation(alias = "...")]`, the former names of the variant, which
+    /// get deprecated methods.
+    pub aliases: Vec<LitStr>,
+    /// `#[variation(skip)]` or `#[variation(skip(...))]`, the families the
+    /// variant gets no methods of.
+    pub skip: Skipped,
+    /// `#[variation(rename = "...")]`, the name generated names of the
+    /// variant are derived from in place of its own.
+    pub rename: Option<LitStr>,
+    /// `#[variation(is = "...", as = "...", as_mut = "...", into = "...")]`.
+    pub method_names: MethodNames,
+    /// `#[variation(into_mode = "panic")]`, `"option"` or `"result"`, what
+    /// `into_*` returns for the wrong variant in place of the enum's mode.
+    pub into_mode: Option<IntoMode>,
+    /// `#[variation(inline)]`, `inline(always)` or `inline(never)`, the
+    /// `#[inline]` hint of the variant's accessor methods in place of the
+    /// enum's.
+    pub inline: Option<Inline>,
+    /// `#[variation(panic_msg = "...")]`, the message `into_*` panics with
+    /// in place of the generic one.
+    pub panic_msg: Option<LitStr>,
+    /// `#[variation(doc = "...")]`, the documentation put before the generated
+    /// one on the variant's accessor methods.
+    pub doc: Option<LitStr>,
+    /// `#[variation(attr(...))]`, the attributes put on the variant's accessor
+    /// methods.
+    pub attrs: Vec<TokenStream>,
+    /// `#[variation(placeholder)]`, the variant is the state `take_*` leaves
+    /// behind, added by `#[variation::placeholder]`, and is left out of
+    /// everything generated.
+    pub placeholder: Option<Span>,
+    /// `#[variation(flatten(...))]`, the variants of the enum the variant
+    /// wraps, which get methods of the outer enum forwarding to it.
+    pub flatten: Option<(Span, Vec<Variant>)>,
+}
+
+impl VariantAttrs {
+    /// What `into_*` of the variant returns for other variants, the mode set
+    /// on it taking precedence over that of the enum.
+    pub(crate) fn mode_of_into(&self, container: &ContainerAttrs) -> IntoMode {
+        self.into_mode.or(container.into_mode).unwrap_or(IntoMode::Panic)
+    }
+
+    /// With `#[variation(no_panic)]` on the enum, rejects the options in
+    /// `attrs` asking for a panicking `into_*` and skips `into_*` where it
+    /// would panic.
+    pub(crate) fn forbid_panics(&mut self, attrs: &[Attribute], container: &ContainerAttrs) -> Result<()> {
+        let span = match container.no_panic {
+            Some(span) => span,
+            None => return Ok(()),
+        };
+
+        for item in items(attrs)? {
+            let panicking = match &*item.name.to_string() {
+                "panic_msg" => true,
+                "into_mode" => matches!(item.value, Value::Str(ref mode) if mode.value() == "panic"),
+                _ => false,
+            };
+
+            if panicking {
+                return Err(Error::new(
+                    item.span(),
+                    format!(
+                        "`#[variation({})]` asks for a panicking `into_*`, which `#[variation(no_panic)]` on the enum \
+                         rules out",
+                        if item.name == "into_mode" { "into_mode = \"panic\"" } else { "panic_msg = \"...\"" }
+                    ),
+                ));
+            }
+        }
+
+        if self.mode_of_into(container) == IntoMode::Panic {
+            self.skip.into = Some(span);
+        }
+
+        Ok(())
+    }
+
+    /// The `#[doc]` attributes putting the documentation of the variant before
+    /// that of its accessor methods, separated by an empty line.
+    pub(crate) fn doc(&self) -> TokenStream {
+        match self.doc {
+            Some(ref doc) => quote::quote!(#[doc = #doc] #[doc = ""]),
+            None => TokenStream::new(),
+        }
+    }
+
+    /// The attributes of `#[variation(attr(...))]` on the variant.
+    pub(crate) fn extra_attrs(&self) -> TokenStream {
+        let attrs = &self.attrs;
+        quote::quote!(#(#[#attrs])*)
+    }
+
+    /// The `#[inline]` attribute of the variant's accessor methods, the hint
+    /// set on it taking precedence over that of the enum.
+    pub(crate) fn inline(&self, container: &ContainerAttrs) -> TokenStream {
+        Inline::attr(self.inline.or(container.inline))
+    }
+
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = VariantAttrs::default();
+        let items = items(attrs)?;
+
+        for item in &items {
+            match &*item.name.to_string() {
+                "from" => set_from(&mut parsed.from, &mut parsed.forward, item)?,
+                "try_into" => set_flag(&mut parsed.try_into, item)?,
+                "partial_eq" => set_flag(&mut parsed.partial_eq, item)?,
+                "as_ref" => set_target(&mut parsed.as_ref, item)?,
+                "borrow" => set_target(&mut parsed.borrow, item)?,
+                "ok" => set_flag(&mut parsed.ok, item)?,
+                "err" => set_flag(&mut parsed.err, item)?,
+                "some" => set_flag(&mut parsed.some, item)?,
+                "none" => set_flag(&mut parsed.none, item)?,
+                "default" => set_flag(&mut parsed.default, item)?,
+                "str" => set_str(&mut parsed.str, item)?,
+                "error" => set_error_msg(&mut parsed.error_msg, item)?,
+                "tag" => set_str(&mut parsed.tag, item)?,
+                "props" => set_props(&mut parsed.props, item)?,
+                "companion_derive" => set_paths(&mut parsed.companion_derive, item)?,
+                "alias" => parsed.aliases.push(item.expect_str()?.clone()),
+                "skip" => set_skip(&mut parsed.skip, item)?,
+                "rename" => set_str(&mut parsed.rename, item)?,
+                "inline" => set_inline(&mut parsed.inline, item)?,
+                "panic_msg" => set_str(&mut parsed.panic_msg, item)?,
+                "doc" => set_str(&mut parsed.doc, item)?,
+                "attr" => parsed.attrs.push(list_tokens(item)?.clone()),
+                "placeholder" => set_flag(&mut parsed.placeholder, item)?,
+                "flatten" => set_variants(&mut parsed.flatten, item)?,
+                "into_mode" => set_into_mode(&mut parsed.into_mode, item)?,
+                name => match parsed.method_names.get_mut(name) {
+                    Some(method_name) => set_str(method_name, item)?,
+                    None => return Err(item.unknown()),
+                },
+            }
+        }
+
+        // Skipping only some families leaves methods the other attributes
+        // can apply to.
+        let alone = |item: &&Item| match &*item.name.to_string() {
+            "skip" => matches!(item.value, Value::Word),
+            "placeholder" => true,
+            _ => false,
+        };
+
+        if let Some(item) = items.iter().find(alone) {
+            if items.len() > 1 {
+                return Err(Error::new(
+                    item.span(),
+                    format!(
+                        "`#[variation({})]` can't be combined with other variation attributes on the same variant",
+                        item.name
+                    ),
+                ));
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Options set on an individual field.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    /// `#[variation(pin)]`, the field is structurally pinned by the
+    /// projection `#[variation(pin_project)]` generates.
+    pub pin: Option<Span>,
+    /// `#[variation(common)]` or `#[variation(common = "...")]`, the field is
+    /// shared by every variant and gets an accessor.
+    pub common: Option<Common>,
+    /// `#[variation(dyn_iter)]`, `children` yields the field, and only the
+    /// marked fields.
+    pub dyn_iter: Option<Span>,
+    /// `#[variation(no_dyn_iter)]`, `children` leaves out the field.
+    pub no_dyn_iter: Option<Span>,
+    /// `#[variation(delegate_fn)]`, the methods of
+    /// `#[variation(delegate_fn(...))]` are called on the field.
+    pub delegate_fn: Option<Span>,
+}
+
+/// A field marked with `#[variation(common)]`, where `name` is the name given
+/// as `common = "..."`.
+pub(crate) struct Common {
+    pub span: Span,
+    pub name: Option<LitStr>,
+}
+
+fn set_common(common: &mut Option<Common>, item: &Item) -> Result<()> {
+    if common.is_some() {
+        return Err(Error::new(item.span(), format!("duplicate `{}` attribute", item.name)));
+    }
+
+    let name = match item.value {
+        Value::Word => None,
+        Value::Str(ref name) => Some(name.clone()),
+        Value::List(_) => {
+            return Err(Error::new(
+                item.span(),
+                format!("expected `{0}` or `{0} = \"...\"`", item.name),
+            ))
+        }
+    };
+
+    *common = Some(Common { span: item.span(), name });
+    Ok(())
+}
+
+impl FieldAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = FieldAttrs::default();
+
+        for item in items(attrs)? {
+            match &*item.name.to_string() {
+                "pin" => set_flag(&mut parsed.pin, &item)?,
+                "common" => set_common(&mut parsed.common, &item)?,
+                "dyn_iter" => set_flag(&mut parsed.dyn_iter, &item)?,
+                "no_dyn_iter" => set_flag(&mut parsed.no_dyn_iter, &item)?,
+                "delegate_fn" => set_flag(&mut parsed.delegate_fn, &item)?,
+                _ => return Err(item.unknown()),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
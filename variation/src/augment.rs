@@ -0,0 +1,92 @@
+//! `augment!`, which generates the methods for an enum defined elsewhere in
+//! the crate from a restatement of its shape.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::*;
+
+use crate::attr::ContainerAttrs;
+
+/// The input of `augment!`, an enum definition named with the path of the
+/// existing enum.
+pub(crate) struct Augment {
+    /// The restated enum, named after the last segment of `path`.
+    pub ast: DeriveInput,
+    pub path: Path,
+}
+
+impl Parse for Augment {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let enum_token = input.parse()?;
+        let path = input.call(Path::parse_mod_style)?;
+        let mut generics: Generics = input.parse()?;
+        generics.where_clause = input.parse()?;
+        let content;
+        let brace_token = braced!(content in input);
+        let variants = content.parse_terminated(Variant::parse)?;
+        let ident = path.segments.last().unwrap().ident.clone();
+
+        Ok(Augment {
+            ast: DeriveInput {
+                attrs,
+                vis,
+                ident,
+                generics,
+                data: Data::Enum(DataEnum { enum_token, brace_token, variants }),
+            },
+            path,
+        })
+    }
+}
+
+impl Augment {
+    /// Whether the enum is named with more than its name, which keeps it from
+    /// being in scope where the generated code goes.
+    fn has_path(&self) -> bool {
+        self.path.leading_colon.is_some() || self.path.segments.len() > 1
+    }
+
+    /// Checks that no option generates items of its own for an enum named
+    /// with a path, as they'd be hidden along with the import of the enum.
+    pub(crate) fn check(&self) -> Result<()> {
+        if !self.has_path() {
+            return Ok(());
+        }
+
+        let container = ContainerAttrs::parse(&self.ast.attrs)?;
+
+        if let Some((span, attribute)) = container.own_items() {
+            return Err(Error::new(
+                span,
+                format!(
+                    "`#[variation({})]` generates items next to the enum, so `augment!` needs the enum named \
+                     without a path, such as `enum {}`, where it's in scope",
+                    attribute, self.ast.ident
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Puts `gen` where the enum is in scope by its name, in a block importing
+    /// it for enums named with a path.
+    pub(crate) fn scoped(&self, gen: TokenStream) -> TokenStream {
+        if !self.has_path() {
+            return gen;
+        }
+
+        let path = &self.path;
+
+        quote! {
+            const _: () = {
+                use #path;
+
+                #gen
+            };
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Accessors for fields marked with `#[variation(common)]`, which every
+//! variant has.
+
+use std::collections::BTreeMap;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::attr::FieldAttrs;
+use crate::{ident, reference_type};
+
+/// A marked field of one variant.
+struct Marked<'a> {
+    variant: &'a Variant,
+    field: &'a Field,
+    index: usize,
+    span: Span,
+}
+
+impl<'a> Marked<'a> {
+    /// The pattern binding the field as `common` with `binding`, such as
+    /// `ref`, ignoring the other fields.
+    fn pattern(&self, name: &Ident, binding: &TokenStream) -> TokenStream {
+        let variant_name = &self.variant.ident;
+        let skipped = (0..self.index).map(|_| quote!(_));
+
+        quote!(#name::#variant_name(#(#skipped,)* #binding common, ..))
+    }
+}
+
+/// Generates an accessor and its `_mut` counterpart for each name fields are
+/// marked with `#[variation(common = "...")]` under, which can be a keyword
+/// for a raw accessor such as `r#type`. Every variant has to mark a field of
+/// the same type under each name.
+pub(crate) fn methods(name: &Ident, variants: &[&Variant]) -> Result<TokenStream> {
+    let mut accessors: BTreeMap<String, Vec<Marked>> = BTreeMap::new();
+
+    for variant in variants {
+        let variant_name = &variant.ident;
+
+        for (index, field) in variant.fields.iter().enumerate() {
+            let common = match FieldAttrs::parse(&field.attrs)?.common {
+                Some(common) => common,
+                None => continue,
+            };
+
+            let accessor = match common.name {
+                Some(accessor) => {
+                    if !valid_name(&accessor.value()) {
+                        return Err(Error::new(
+                            accessor.span(),
+                            format!("`{}` is not a valid method name", accessor.value()),
+                        ));
+                    }
+
+                    accessor.value()
+                }
+                None => {
+                    return Err(Error::new(
+                        common.span,
+                        "`#[variation(common)]` requires a name, such as `#[variation(common = \"span\")]`",
+                    ))
+                }
+            };
+
+            let marked = accessors.entry(accessor.clone()).or_default();
+
+            if marked.iter().any(|other| other.variant.ident == *variant_name) {
+                return Err(Error::new(
+                    common.span,
+                    format!("`#[variation(common)]` `{}` is already set on `{}`", accessor, variant_name),
+                ));
+            }
+
+            marked.push(Marked { variant, field, index, span: common.span });
+        }
+    }
+
+    for accessor in accessors.keys() {
+        let accessor_mut = format!("{}_mut", accessor);
+
+        if let Some(other) = accessors.get(&accessor_mut) {
+            return Err(Error::new(
+                other[0].span,
+                format!(
+                    "conflicting `#[variation(common)]` `{}`, it's also the `_mut` accessor of `{}`",
+                    accessor_mut, accessor
+                ),
+            ));
+        }
+    }
+
+    let mut methods = TokenStream::new();
+
+    for (accessor, marked) in accessors {
+        let span = marked[0].span;
+        let ty = &marked[0].field.ty;
+        let ref_ty = reference_type(&quote!(&), ty);
+        let mut_ty = reference_type(&quote!(&mut), ty);
+        let missing = variants
+            .iter()
+            .filter(|variant| !marked.iter().any(|marked| marked.variant.ident == variant.ident))
+            .map(|variant| format!("`{}`", variant.ident))
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            return Err(Error::new(
+                span,
+                format!(
+                    "`#[variation(common)]` `{}` has to be set on every variant, it's missing on {}",
+                    accessor,
+                    missing.join(", ")
+                ),
+            ));
+        }
+
+        for other in &marked {
+            let other_ty = &other.field.ty;
+
+            if quote!(#other_ty).to_string() != quote!(#ty).to_string() {
+                return Err(Error::new(
+                    other.span,
+                    format!(
+                        "conflicting `#[variation(common)]` `{}`, `{}` is `{}` but `{}` is `{}`",
+                        accessor,
+                        marked[0].variant.ident,
+                        quote!(#ty),
+                        other.variant.ident,
+                        quote!(#other_ty)
+                    ),
+                ));
+            }
+        }
+
+        let accessor_mut = ident(&format!("{}_mut", accessor));
+        let accessor_doc = format!("Returns the `{}` every variant has.", accessor);
+        let accessor_mut_doc = format!("Returns a mutable reference to the `{}` every variant has.", accessor);
+        let accessor = ident(&accessor);
+        let mut arms = TokenStream::new();
+        let mut mut_arms = TokenStream::new();
+
+        for marked in &marked {
+            let pattern = marked.pattern(name, &quote!(ref));
+            let mut_pattern = marked.pattern(name, &quote!(ref mut));
+            arms.extend(quote!(#pattern => common,));
+            mut_arms.extend(quote!(#mut_pattern => common,));
+        }
+
+        methods.extend(quote! {
+            #[doc = #accessor_doc]
+            pub fn #accessor(&self) -> #ref_ty {
+                match *self {
+                    #arms
+                }
+            }
+
+            #[doc = #accessor_mut_doc]
+            pub fn #accessor_mut(&mut self) -> #mut_ty {
+                match *self {
+                    #mut_arms
+                }
+            }
+        });
+    }
+
+    Ok(methods)
+}
+
+/// Whether `name` can name an accessor, which keywords can as raw
+/// identifiers, except for the path keywords. Only names starting like an
+/// identifier are tried with `r#`, which would be lexed as a raw string
+/// otherwise.
+fn valid_name(name: &str) -> bool {
+    match name.chars().next() {
+        Some(first) if first == '_' || first.is_alphabetic() => {}
+        _ => return false,
+    }
+
+    !["_", "crate", "self", "Self", "super"].contains(&name)
+        && !name.starts_with("r#")
+        && (syn::parse_str::<Ident>(name).is_ok() || syn::parse_str::<Ident>(&format!("r#{}", name)).is_ok())
+}
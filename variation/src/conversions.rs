@@ -0,0 +1,564 @@
+//! Standard library conversion traits between an enum and its variants'
+//! inner types.
+
+use std::collections::HashMap;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
+use syn::*;
+
+use crate::attr::{Forward, Target, VariantAttrs};
+use crate::delegate::mentions_type_param;
+use crate::placeholder;
+use crate::{generate_ident_list_pattern, inner_ref_type, inner_type, inner_value, reference_type};
+
+/// Generates `impl From<Inner> for Enum` for every variant marked with
+/// `#[variation(from)]`, along with the impls `from(forward(...))` forwards
+/// through `Into<Inner>`. The blanket impl of `from(forward)` takes the place
+/// of the one for the inner type. `foreign_from` describes another `From` impl
+/// of the enum for a foreign type, such as `From<Result<..>>`, if there is one.
+pub(crate) fn impl_from(
+    ast: &DeriveInput,
+    variants: &[(&Variant, VariantAttrs)],
+    foreign_from: Option<&str>,
+) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let selected = unambiguous(variants, |attrs| attrs.from, "from")?;
+    let mut sources: HashMap<_, _> =
+        selected.iter().map(|(variant, inner)| (inner.to_string(), &variant.ident)).collect();
+    let mut impls = TokenStream::new();
+
+    for (variant, inner) in &selected {
+        let variant_name = &variant.ident;
+        let field_count = variant.fields.iter().count();
+        let fields = generate_ident_list_pattern(field_count, false, false);
+        let value = inner_value(field_count);
+        let attrs = &variants.iter().find(|(other, _)| other.ident == *variant_name).unwrap().1;
+        let forwarded = match attrs.forward {
+            Some(Forward::Any(span)) => {
+                check_blanket(ast, &selected, variant, inner, span, foreign_from)?;
+                let forward = Ident::new("__Forward", Span::call_site());
+                let mut generics = ast.generics.clone();
+                generics.params.insert(generics.lifetimes().count(), parse_quote!(#forward));
+                generics.make_where_clause().predicates.push(parse_quote!(#forward: ::std::convert::Into<#inner>));
+                let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+                impls.extend(quote! {
+                    impl #impl_generics ::std::convert::From<#forward> for #name #ty_generics #where_clause {
+                        fn from(value: #forward) -> Self {
+                            let #value: #inner = ::std::convert::Into::into(value);
+                            #name::#variant_name#fields
+                        }
+                    }
+                });
+                continue;
+            }
+            Some(Forward::Types(ref types)) => &types[..],
+            None => &[],
+        };
+
+        impls.extend(quote! {
+            impl #impl_generics ::std::convert::From<#inner> for #name #ty_generics #where_clause {
+                fn from(#value: #inner) -> Self {
+                    #name::#variant_name#fields
+                }
+            }
+        });
+
+        for ty in forwarded {
+            let source = ty.to_token_stream().to_string();
+
+            if let Some(other) = sources.insert(source.clone(), variant_name) {
+                let message = if other == variant_name {
+                    format!("`{}` already converts from `{}`", variant_name, source)
+                } else {
+                    format!(
+                        "conflicting `#[variation(from)]`, `{}` and `{}` both convert from `{}`",
+                        other, variant_name, source
+                    )
+                };
+
+                return Err(Error::new(ty.span(), message));
+            }
+
+            // Spanned at the type, where one not converting into the inner
+            // type is reported.
+            let into = quote_spanned!(ty.span()=> ::std::convert::Into::into(value));
+
+            impls.extend(quote! {
+                impl #impl_generics ::std::convert::From<#ty> for #name #ty_generics #where_clause {
+                    fn from(value: #ty) -> Self {
+                        let #value: #inner = #into;
+                        #name::#variant_name#fields
+                    }
+                }
+            });
+        }
+    }
+
+    Ok(impls)
+}
+
+/// Rejects the blanket impl of `from(forward)` where it would overlap with
+/// another `From` impl of the enum: those of the other variants, the one for
+/// `Result` or `Option` and, when the inner type names a type parameter, the
+/// standard library's `impl<T> From<T> for T`. A `From` impl for a local
+/// type, such as that of a subset enum, doesn't overlap.
+fn check_blanket(
+    ast: &DeriveInput,
+    selected: &[(&Variant, TokenStream)],
+    variant: &Variant,
+    inner: &TokenStream,
+    span: Span,
+    foreign_from: Option<&str>,
+) -> Result<()> {
+    let conflict = if let Some((other, _)) = selected.iter().find(|(other, _)| other.ident != variant.ident) {
+        format!("the `From` impl of `{}`", other.ident)
+    } else if let Some(foreign_from) = foreign_from {
+        String::from(foreign_from)
+    } else if mentions_type_param(&ast.generics, inner.clone()) {
+        String::from("`impl<T> From<T> for T`, as the inner type names a type parameter")
+    } else {
+        return Ok(());
+    };
+
+    Err(Error::new(
+        span,
+        format!(
+            "`#[variation(from(forward))]` on `{}` conflicts with {}, list the types to forward with \
+             `from(forward(Type, ...))` instead",
+            variant.ident, conflict
+        ),
+    ))
+}
+
+/// Generates `impl TryFrom<Enum> for Inner` for every variant marked with
+/// `#[variation(try_into)]`, handing back the enum itself as the error when
+/// it holds a different variant.
+pub(crate) fn impl_try_into(ast: &DeriveInput, variants: &[(&Variant, VariantAttrs)]) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let mut impls = TokenStream::new();
+
+    let source = type_name(quote!(#name #ty_generics));
+    check_covered(ast, variants, |ty| format!("TryFrom<{}> for {}", source, type_name(quote!(#ty))))?;
+
+    for (variant, inner) in unambiguous(variants, |attrs| attrs.try_into, "try_into")? {
+        let variant_name = &variant.ident;
+        let field_count = variant.fields.iter().count();
+        let fields = generate_ident_list_pattern(field_count, false, false);
+        let value = inner_value(field_count);
+
+        impls.extend(quote! {
+            impl #impl_generics ::std::convert::TryFrom<#name #ty_generics> for #inner #where_clause {
+                type Error = #name #ty_generics;
+
+                #[allow(unreachable_patterns)]
+                fn try_from(value: #name #ty_generics) -> ::std::result::Result<Self, Self::Error> {
+                    match value {
+                        #name::#variant_name#fields => ::std::result::Result::Ok(#value),
+                        value => ::std::result::Result::Err(value),
+                    }
+                }
+            }
+        });
+    }
+
+    Ok(impls)
+}
+
+/// Generates `impl TryFrom<&Enum> for &Inner` and its `&mut` counterpart for
+/// every variant marked with `#[variation(try_into)]`, handing back the
+/// reference to the enum as the error.
+pub(crate) fn impl_try_into_ref(ast: &DeriveInput, variants: &[(&Variant, VariantAttrs)]) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let lifetime = Lifetime::new("'variation", Span::call_site());
+    let mut generics = ast.generics.clone();
+    generics.params.insert(0, GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    let mut impls = TokenStream::new();
+
+    let source = type_name(quote!(#name #ty_generics));
+    check_covered(ast, variants, |ty| format!("TryFrom<&{}> for &{}", source, type_name(quote!(#ty))))?;
+
+    for (variant, _) in unambiguous(variants, |attrs| attrs.try_into, "try_into")? {
+        let variant_name = &variant.ident;
+        let field_count = variant.fields.iter().count();
+        let fields = generate_ident_list_pattern(field_count, false, false);
+        let value = inner_value(field_count);
+
+        for &mutable in &[false, true] {
+            let reference = if mutable { quote!(&#lifetime mut) } else { quote!(&#lifetime) };
+            let inner = inner_ref_type(variant, reference.clone());
+
+            impls.extend(quote! {
+                impl #impl_generics ::std::convert::TryFrom<#reference #name #ty_generics> for #inner #where_clause {
+                    type Error = #reference #name #ty_generics;
+
+                    #[allow(unreachable_patterns)]
+                    fn try_from(value: #reference #name #ty_generics) -> ::std::result::Result<Self, Self::Error> {
+                        match value {
+                            #name::#variant_name#fields => ::std::result::Result::Ok(#value),
+                            value => ::std::result::Result::Err(value),
+                        }
+                    }
+                }
+            });
+        }
+    }
+
+    Ok(impls)
+}
+
+/// Generates `impl From<Enum> for Superset` and `impl TryFrom<Superset> for
+/// Enum` for `#[variation(subset_of = "...")]`, matching the variants by
+/// name. The superset enum is constructed with the values and arity of the
+/// variants restated here, so a variant it lacks or whose fields differ is a
+/// compile error pointing at that variant or field.
+pub(crate) fn impl_subset_of(
+    ast: &DeriveInput,
+    variants: &[&Variant],
+    placeholder: Option<&Variant>,
+    superset: &Path,
+) -> TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    // The generic arguments of the superset are inferred in the patterns and
+    // constructors.
+    let mut enum_path = superset.clone();
+
+    for segment in &mut enum_path.segments {
+        segment.arguments = PathArguments::None;
+    }
+
+    let mut into_arms = TokenStream::new();
+    let mut from_arms = TokenStream::new();
+
+    for variant in variants {
+        let variant_name = &variant.ident;
+        let field_count = variant.fields.iter().count();
+        let fields = generate_ident_list_pattern(field_count, false, false);
+        // Spanned at the variant and its fields, so mismatches are reported
+        // there in both directions.
+        let superset_variant = quote_spanned!(variant_name.span()=> #enum_path::#variant_name);
+        let values = variant.fields.iter().enumerate().map(|(i, field)| {
+            let value = Ident::new(&format!("v{}", i), field.ty.span());
+            quote!(#value)
+        });
+        let values = if field_count == 0 { quote!() } else { quote!((#(#values),*)) };
+
+        into_arms.extend(quote!(#name::#variant_name#fields => #superset_variant#values,));
+        from_arms.extend(quote! {
+            #superset_variant#values => ::std::result::Result::Ok(#name::#variant_name#values),
+        });
+    }
+
+    let taken_arm = placeholder::taken_arm(name, placeholder, "from");
+
+    quote! {
+        impl #impl_generics ::std::convert::From<#name #ty_generics> for #superset #where_clause {
+            fn from(value: #name #ty_generics) -> Self {
+                match value {
+                    #into_arms
+                    #taken_arm
+                }
+            }
+        }
+
+        impl #impl_generics ::std::convert::TryFrom<#superset> for #name #ty_generics #where_clause {
+            type Error = #superset;
+
+            #[allow(unreachable_patterns)]
+            fn try_from(value: #superset) -> ::std::result::Result<Self, Self::Error> {
+                match value {
+                    #from_arms
+                    value => ::std::result::Result::Err(value),
+                }
+            }
+        }
+    }
+}
+
+/// Generates `impl PartialEq<Inner> for Enum` and `impl PartialEq<Enum> for
+/// Inner` for every single field variant marked with `#[variation(partial_eq)]`.
+pub(crate) fn impl_partial_eq(ast: &DeriveInput, variants: &[(&Variant, VariantAttrs)]) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let mut impls = TokenStream::new();
+
+    for (variant, inner) in unambiguous(variants, |attrs| attrs.partial_eq, "partial_eq")? {
+        let variant_name = &variant.ident;
+
+        if variant.fields.iter().count() != 1 {
+            return Err(Error::new_spanned(
+                variant,
+                "`#[variation(partial_eq)]` is only available for variants with a single field",
+            ));
+        }
+
+        let mut generics = ast.generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#inner: ::std::cmp::PartialEq));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        // The orphan rules forbid implementing a foreign trait on an
+        // uncovered type parameter, so neither `T == Enum<T>` nor
+        // `Box<T> == Enum<T>` can be provided.
+        let ty = &variant.fields.iter().next().unwrap().ty;
+        let reflected = if uncovered_param(&ast.generics, ty).is_some() {
+            quote!()
+        } else {
+            quote! {
+                impl #impl_generics ::std::cmp::PartialEq<#name #ty_generics> for #inner #where_clause {
+                    fn eq(&self, other: &#name #ty_generics) -> bool {
+                        other == self
+                    }
+                }
+            }
+        };
+
+        impls.extend(quote! {
+            impl #impl_generics ::std::cmp::PartialEq<#inner> for #name #ty_generics #where_clause {
+                #[allow(unreachable_patterns)]
+                fn eq(&self, other: &#inner) -> bool {
+                    match self {
+                        #name::#variant_name(ref v0) => v0 == other,
+                        _ => false,
+                    }
+                }
+            }
+
+            #reflected
+        });
+    }
+
+    Ok(impls)
+}
+
+/// Generates `AsRef` and `AsMut` for a single variant enum whose variant is
+/// marked with `#[variation(as_ref)]`.
+pub(crate) fn impl_as_ref(ast: &DeriveInput, variants: &[(&Variant, VariantAttrs)]) -> Result<TokenStream> {
+    impl_reference_traits(
+        ast,
+        variants,
+        |attrs| attrs.as_ref.as_ref(),
+        "as_ref",
+        (quote!(::std::convert::AsRef), quote!(as_ref)),
+        (quote!(::std::convert::AsMut), quote!(as_mut)),
+    )
+}
+
+/// Generates `Borrow` and `BorrowMut` for a single variant enum whose variant
+/// is marked with `#[variation(borrow)]`.
+pub(crate) fn impl_borrow(ast: &DeriveInput, variants: &[(&Variant, VariantAttrs)]) -> Result<TokenStream> {
+    impl_reference_traits(
+        ast,
+        variants,
+        |attrs| attrs.borrow.as_ref(),
+        "borrow",
+        (quote!(::std::borrow::Borrow), quote!(borrow)),
+        (quote!(::std::borrow::BorrowMut), quote!(borrow_mut)),
+    )
+}
+
+/// Generates a pair of traits borrowing the payload of a single variant,
+/// single field enum, such as `AsRef` and `AsMut`. As the traits can't fail
+/// they are only available when the enum can't hold anything else.
+fn impl_reference_traits<'a>(
+    ast: &DeriveInput,
+    variants: &'a [(&Variant, VariantAttrs)],
+    target: impl Fn(&'a VariantAttrs) -> Option<&'a Target>,
+    attribute: &str,
+    (shared_trait, shared_fn): (TokenStream, TokenStream),
+    (unique_trait, unique_fn): (TokenStream, TokenStream),
+) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let (variant, target) = match variants
+        .iter()
+        .find_map(|(variant, attrs)| target(attrs).map(|target| (variant, target)))
+    {
+        Some(found) => found,
+        None => return Ok(TokenStream::new()),
+    };
+
+    if variants.len() != 1 {
+        return Err(Error::new(
+            target.span,
+            format!("`#[variation({})]` is only available for enums with a single variant", attribute),
+        ));
+    }
+
+    if variant.fields.iter().count() != 1 {
+        return Err(Error::new(
+            target.span,
+            format!("`#[variation({})]` requires `{}` to have a single field", attribute, variant.ident),
+        ));
+    }
+
+    let variant_name = &variant.ident;
+    let target_ty = target.ty.as_ref().map(static_object);
+    let (ty, shared_value, unique_value) = match target_ty {
+        None => (&variant.fields.iter().next().unwrap().ty, quote!(v0), quote!(v0)),
+        Some(ref ty) => (
+            ty,
+            quote!(#shared_trait::<#ty>::#shared_fn(v0)),
+            quote!(#unique_trait::<#ty>::#unique_fn(v0)),
+        ),
+    };
+    let ref_ty = reference_type(&quote!(&), ty);
+    let mut_ty = reference_type(&quote!(&mut), ty);
+
+    Ok(quote! {
+        impl #impl_generics #shared_trait<#ty> for #name #ty_generics #where_clause {
+            fn #shared_fn(&self) -> #ref_ty {
+                match self {
+                    #name::#variant_name(ref v0) => #shared_value,
+                }
+            }
+        }
+
+        impl #impl_generics #unique_trait<#ty> for #name #ty_generics #where_clause {
+            fn #unique_fn(&mut self) -> #mut_ty {
+                match self {
+                    #name::#variant_name(ref mut v0) => #unique_value,
+                }
+            }
+        }
+    })
+}
+
+/// The target with the `'static` bound it has as the trait's parameter
+/// spelled out if it's a trait object, which it would take the lifetime of
+/// the reference for in the return type otherwise.
+fn static_object(ty: &Type) -> Type {
+    match *ty {
+        Type::TraitObject(ref object)
+            if !object.bounds.iter().any(|bound| matches!(bound, TypeParamBound::Lifetime(_))) =>
+        {
+            let mut object = object.clone();
+            object.bounds.push(parse_quote!('static));
+            Type::TraitObject(object)
+        }
+        _ => ty.clone(),
+    }
+}
+
+/// Rejects `#[variation(try_into)]` on variants whose single field leaves one
+/// of the enum's type parameters uncovered, as the orphan rules forbid
+/// implementing a foreign trait such as `TryFrom` for it. `impl_of` describes
+/// the impl for the field's type.
+fn check_covered(
+    ast: &DeriveInput,
+    variants: &[(&Variant, VariantAttrs)],
+    impl_of: impl Fn(&Type) -> String,
+) -> Result<()> {
+    for (variant, attrs) in variants {
+        let (span, ty) = match (attrs.try_into, variant.fields.iter().next()) {
+            (Some(span), Some(field)) if variant.fields.iter().count() == 1 => (span, &field.ty),
+            _ => continue,
+        };
+
+        if let Some(param) = uncovered_param(&ast.generics, ty) {
+            return Err(Error::new(
+                span,
+                format!(
+                    "`#[variation(try_into)]` on `{}` would implement `{}`, which the orphan rules forbid as it \
+                     leaves the type parameter `{}` uncovered",
+                    variant.ident,
+                    impl_of(ty),
+                    param
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds the type parameter that `ty` leaves uncovered for the orphan rules,
+/// either as the type itself or behind the fundamental `&`, `&mut`, `Box` and
+/// `Pin`, which don't cover their parameters.
+fn uncovered_param<'a>(generics: &'a Generics, ty: &Type) -> Option<&'a Ident> {
+    match ty {
+        Type::Reference(reference) => uncovered_param(generics, &reference.elem),
+        Type::Paren(paren) => uncovered_param(generics, &paren.elem),
+        Type::Group(group) => uncovered_param(generics, &group.elem),
+        Type::Path(TypePath { qself: None, path }) => {
+            if let Some(param) = generics.type_params().find(|param| path.is_ident(&param.ident)) {
+                return Some(&param.ident);
+            }
+
+            let last = path.segments.last()?;
+            let arguments = match &last.arguments {
+                PathArguments::AngleBracketed(arguments) if last.ident == "Box" || last.ident == "Pin" => arguments,
+                _ => return None,
+            };
+
+            arguments.args.iter().find_map(|argument| match argument {
+                GenericArgument::Type(ty) => uncovered_param(generics, ty),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Renders `tokens` the way the type would be written, for error messages.
+fn type_name(tokens: TokenStream) -> String {
+    tokens
+        .to_string()
+        .replace(" < ", "<")
+        .replace(" <", "<")
+        .replace(" >", ">")
+        .replace("& ", "&")
+        .replace(" :: ", "::")
+        .replace(" ,", ",")
+}
+
+/// Collects the variants that have the attribute selected by `flag` along
+/// with their inner type, rejecting unit variants and variants whose inner
+/// types are identical, as a trait impl keyed on that type would conflict.
+fn unambiguous<'a>(
+    variants: &[(&'a Variant, VariantAttrs)],
+    flag: impl Fn(&VariantAttrs) -> Option<Span>,
+    attribute: &str,
+) -> Result<Vec<(&'a Variant, TokenStream)>> {
+    let mut seen = HashMap::new();
+    let mut selected = Vec::new();
+
+    for (variant, attrs) in variants {
+        let span = match flag(attrs) {
+            Some(span) => span,
+            None => continue,
+        };
+
+        if variant.fields.iter().count() == 0 {
+            return Err(Error::new(
+                span,
+                format!("`#[variation({})]` requires `{}` to have fields", attribute, variant.ident),
+            ));
+        }
+
+        let inner = inner_type(variant);
+
+        if let Some(other) = seen.insert(inner.to_string(), &variant.ident) {
+            return Err(Error::new(
+                span,
+                format!(
+                    "conflicting `#[variation({})]`, `{}` and `{}` both wrap `{}`",
+                    attribute, other, variant.ident, inner
+                ),
+            ));
+        }
+
+        selected.push((*variant, inner));
+    }
+
+    Ok(selected)
+}
@@ -0,0 +1,68 @@
+//! The `Default` impl for enums with a variant marked `#[variation(default)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::*;
+
+use crate::attr::VariantAttrs;
+
+/// Generates `impl Default for Enum` returning the variant marked with
+/// `#[variation(default)]`, with its fields set to their own defaults. Like
+/// `#[derive(Default)]`, every type parameter is required to be `Default`.
+pub(crate) fn impl_default(ast: &DeriveInput, variants: &[(&Variant, VariantAttrs)]) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let mut found: Option<&Variant> = None;
+
+    for (variant, attrs) in variants {
+        let span = match attrs.default {
+            Some(span) => span,
+            None => continue,
+        };
+
+        if let Some(other) = found {
+            return Err(Error::new(
+                span,
+                format!("`#[variation(default)]` is already set on `{}`", other.ident),
+            ));
+        }
+
+        found = Some(*variant);
+    }
+
+    let variant = match found {
+        Some(variant) => variant,
+        None => return Ok(TokenStream::new()),
+    };
+
+    let variant_name = &variant.ident;
+    let default = quote!(::std::default::Default::default());
+    let value = match variant.fields {
+        Fields::Unit => quote!(#name::#variant_name),
+        Fields::Unnamed(ref fields) => {
+            let values = fields.unnamed.iter().map(|_| &default);
+            quote!(#name::#variant_name(#(#values),*))
+        }
+        Fields::Named(ref fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote!(#name::#variant_name { #(#names: #default),* })
+        }
+    };
+
+    let mut generics = ast.generics.clone();
+    let type_params = ast.generics.type_params().map(|param| &param.ident);
+    let where_clause = generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::std::default::Default));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::std::default::Default for #name #ty_generics #where_clause {
+            fn default() -> Self {
+                #value
+            }
+        }
+    })
+}
@@ -0,0 +1,567 @@
+//! Trait impls for `#[variation(delegate(...))]` and the methods of
+//! `#[variation(delegate_fn(...))]`, forwarding to whichever value the enum
+//! holds.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{ParseStream, Parser};
+use syn::*;
+
+use crate::attr::{FieldAttrs, Item, Value};
+
+/// A trait listed in `#[variation(delegate(...))]`.
+pub(crate) enum Delegate {
+    /// `Iterator<Item = Type>`.
+    Iterator(Type),
+    /// `Future<Output = Type>`.
+    Future(Type),
+    DoubleEndedIterator,
+    ExactSizeIterator,
+    Display,
+    Read,
+    Write,
+}
+
+impl Delegate {
+    /// Parses a trait path such as `Iterator<Item = u32>`.
+    pub(crate) fn parse(path: &Path) -> Result<(Span, Self)> {
+        let segment = path.segments.iter().last().unwrap();
+        let span = segment.ident.span();
+
+        let delegate = match &*segment.ident.to_string() {
+            "Iterator" => Delegate::Iterator(associated_type(segment, "Item").ok_or_else(|| {
+                Error::new(
+                    span,
+                    "`delegate(Iterator)` requires the item type, such as `delegate(Iterator<Item = u32>)`",
+                )
+            })?),
+            "DoubleEndedIterator" => Delegate::DoubleEndedIterator,
+            "ExactSizeIterator" => Delegate::ExactSizeIterator,
+            "Future" => Delegate::Future(associated_type(segment, "Output").ok_or_else(|| {
+                Error::new(
+                    span,
+                    "`delegate(Future)` requires the output type, such as `delegate(Future<Output = u32>)`",
+                )
+            })?),
+            "Display" => Delegate::Display,
+            "Read" => Delegate::Read,
+            "Write" => Delegate::Write,
+            other => {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "unknown delegated trait `{}`, expected `Iterator`, `DoubleEndedIterator`, \
+                         `ExactSizeIterator`, `Future`, `Display`, `Read` or `Write`",
+                        other
+                    ),
+                ))
+            }
+        };
+
+        if !segment.arguments.is_empty() && !delegate.has_associated_type() {
+            return Err(Error::new(span, format!("`{}` does not take arguments", segment.ident)));
+        }
+
+        Ok((span, delegate))
+    }
+
+    fn has_associated_type(&self) -> bool {
+        matches!(self, Delegate::Iterator(_) | Delegate::Future(_))
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Delegate::Iterator(_) => "Iterator",
+            Delegate::DoubleEndedIterator => "DoubleEndedIterator",
+            Delegate::ExactSizeIterator => "ExactSizeIterator",
+            Delegate::Future(_) => "Future",
+            Delegate::Display => "Display",
+            Delegate::Read => "Read",
+            Delegate::Write => "Write",
+        }
+    }
+
+    fn trait_path(&self) -> TokenStream {
+        match self {
+            Delegate::Iterator(_) => quote!(::std::iter::Iterator),
+            Delegate::DoubleEndedIterator => quote!(::std::iter::DoubleEndedIterator),
+            Delegate::ExactSizeIterator => quote!(::std::iter::ExactSizeIterator),
+            Delegate::Future(_) => quote!(::std::future::Future),
+            Delegate::Display => quote!(::std::fmt::Display),
+            Delegate::Read => quote!(::std::io::Read),
+            Delegate::Write => quote!(::std::io::Write),
+        }
+    }
+
+    /// The methods forwarded to the inner value, which are the required
+    /// methods and those with a more efficient implementation than their
+    /// default.
+    fn methods(&self) -> Vec<Forward> {
+        let path = self.trait_path();
+
+        match self {
+            Delegate::Iterator(_) => vec![
+                Forward::new(quote!(next(&mut self) -> Option<Self::Item>), quote!(#path::next), true),
+                Forward::new(quote!(size_hint(&self) -> (usize, Option<usize>)), quote!(#path::size_hint), false),
+            ],
+            Delegate::DoubleEndedIterator => vec![Forward::new(
+                quote!(next_back(&mut self) -> Option<Self::Item>),
+                quote!(#path::next_back),
+                true,
+            )],
+            Delegate::ExactSizeIterator => vec![Forward::new(quote!(len(&self) -> usize), quote!(#path::len), false)],
+            // `poll` takes the inner value pinned, see `future_items`.
+            Delegate::Future(_) => Vec::new(),
+            Delegate::Display => vec![Forward::new(
+                quote!(fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result),
+                quote!(#path::fmt),
+                false,
+            )
+            .args(quote!(f))],
+            Delegate::Read => vec![
+                Forward::new(quote!(read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize>), quote!(#path::read), true)
+                    .args(quote!(buf)),
+                Forward::new(
+                    quote!(read_vectored(&mut self, bufs: &mut [::std::io::IoSliceMut]) -> ::std::io::Result<usize>),
+                    quote!(#path::read_vectored),
+                    true,
+                )
+                .args(quote!(bufs)),
+            ],
+            Delegate::Write => vec![
+                Forward::new(quote!(write(&mut self, buf: &[u8]) -> ::std::io::Result<usize>), quote!(#path::write), true)
+                    .args(quote!(buf)),
+                Forward::new(
+                    quote!(write_vectored(&mut self, bufs: &[::std::io::IoSlice]) -> ::std::io::Result<usize>),
+                    quote!(#path::write_vectored),
+                    true,
+                )
+                .args(quote!(bufs)),
+                Forward::new(quote!(flush(&mut self) -> ::std::io::Result<()>), quote!(#path::flush), true),
+            ],
+        }
+    }
+}
+
+/// The `Type` of a trait path such as `Iterator<Item = Type>`, where `name`
+/// is `Item`.
+fn associated_type(segment: &PathSegment, name: &str) -> Option<Type> {
+    let arguments = match segment.arguments {
+        PathArguments::AngleBracketed(ref arguments) if arguments.args.len() == 1 => arguments,
+        _ => return None,
+    };
+
+    match arguments.args[0] {
+        GenericArgument::Binding(ref binding) if binding.ident == name => Some(binding.ty.clone()),
+        _ => None,
+    }
+}
+
+/// Whether `tokens` mention one of the enum's type parameters.
+pub(crate) fn mentions_type_param(generics: &Generics, tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ref ident) => generics.type_params().any(|param| param.ident == *ident),
+        TokenTree::Group(ref group) => mentions_type_param(generics, group.stream()),
+        _ => false,
+    })
+}
+
+/// Generates the impls of the delegated traits, requiring every variant to
+/// have a single field.
+pub(crate) fn impl_delegates(ast: &DeriveInput, variants: &[&Variant], delegates: &[(Span, Delegate)]) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let mut impls = TokenStream::new();
+    // The item type has to be known for the bounds of the iterator's
+    // subtraits as well, or their methods return the inner values' items.
+    let item = delegates.iter().find_map(|(_, delegate)| match delegate {
+        Delegate::Iterator(item) => Some(quote!(<Item = #item>)),
+        _ => None,
+    });
+
+    for (span, delegate) in delegates {
+        let mut types = Vec::new();
+
+        for variant in variants {
+            match variant.fields {
+                Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => types.push(&fields.unnamed[0].ty),
+                _ => {
+                    return Err(Error::new(
+                        *span,
+                        format!(
+                            "`delegate({})` requires every variant to have a single field, `{}` doesn't",
+                            delegate.name(),
+                            variant.ident
+                        ),
+                    ))
+                }
+            }
+        }
+
+        let trait_path = delegate.trait_path();
+        let bound = match delegate {
+            Delegate::Iterator(_) | Delegate::DoubleEndedIterator | Delegate::ExactSizeIterator => {
+                quote!(#trait_path #item)
+            }
+            Delegate::Future(output) => quote!(#trait_path<Output = #output>),
+            _ => trait_path.clone(),
+        };
+        let associated = match delegate {
+            Delegate::Iterator(item) => quote!(type Item = #item;),
+            Delegate::Future(output) => {
+                impls.extend(forbid_drop(ast));
+                impls.extend(conditional_unpin(ast, &types));
+                future_items(name, variants, output)
+            }
+            _ => TokenStream::new(),
+        };
+        let items = delegate_methods(name, variants, associated, &delegate.methods());
+
+        let mut generics = ast.generics.clone();
+        for ty in types {
+            if mentions_type_param(&ast.generics, quote!(#ty)) {
+                generics.make_where_clause().predicates.push(parse_quote!(#ty: #bound));
+            }
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        impls.extend(quote! {
+            impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+                #items
+            }
+        });
+    }
+
+    Ok(impls)
+}
+
+/// Generates the items of `impl Future for Enum`, which polls the inner
+/// future through a pinned reference to the enum. This requires the inner
+/// values to be structurally pinned, see `forbid_drop` and
+/// `conditional_unpin`.
+fn future_items(name: &Ident, variants: &[&Variant], output: &Type) -> TokenStream {
+    let mut arms = TokenStream::new();
+
+    for variant in variants {
+        let variant_name = &variant.ident;
+        arms.extend(quote! {
+            #name::#variant_name(ref mut v0) => ::std::future::Future::poll(::std::pin::Pin::new_unchecked(v0), cx),
+        });
+    }
+
+    quote! {
+        type Output = #output;
+
+        fn poll(
+            self: ::std::pin::Pin<&mut Self>,
+            cx: &mut ::std::task::Context,
+        ) -> ::std::task::Poll<Self::Output> {
+            // SAFETY: The inner value is never moved out of the enum while
+            // it's pinned, as the enum is only `Unpin`, and so only hands out
+            // a `&mut` from the `Pin`, if every inner value is, and it can't
+            // implement `Drop`. So the inner value stays pinned along with it.
+            unsafe {
+                match *::std::pin::Pin::get_unchecked_mut(self) {
+                    #arms
+                }
+            }
+        }
+    }
+}
+
+/// Prevents the enum from implementing `Drop` through a conflicting impl, as
+/// `drop` gets a `&mut` to the enum and could move a pinned inner value out.
+pub(crate) fn forbid_drop(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    quote! {
+        const _: () = {
+            trait MustNotImplDrop {}
+
+            #[allow(drop_bounds)]
+            impl<T: ::std::ops::Drop> MustNotImplDrop for T {}
+
+            impl #impl_generics MustNotImplDrop for #name #ty_generics #where_clause {}
+        };
+    }
+}
+
+/// Implements `Unpin` for the enum only if the `pinned` inner values are, in
+/// place of the auto impl, which would require the others to be but also let
+/// the enum implement it itself. The impl conflicts with one written for the
+/// enum, which would allow moving a pinned inner value out through
+/// `Pin::get_mut`. The types are the fields of a struct so that the bound
+/// isn't trivial, which would be an error for `!Unpin` types.
+pub(crate) fn conditional_unpin(ast: &DeriveInput, pinned: &[&Type]) -> TokenStream {
+    let name = &ast.ident;
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    let mut generics = ast.generics.clone();
+    generics.params.insert(0, parse_quote!('__variation_pin));
+    let (struct_generics, pinned_generics, struct_where_clause) = generics.split_for_impl();
+    let bound: WherePredicate = parse_quote!(__VariationPinned #pinned_generics: ::std::marker::Unpin);
+    let mut impl_generics = generics.clone();
+    impl_generics.make_where_clause().predicates.push(bound);
+    let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+
+    quote! {
+        const _: () = {
+            #[allow(dead_code)]
+            struct __VariationPinned #struct_generics (
+                ::std::marker::PhantomData<(&'__variation_pin (), fn() -> #name #ty_generics)>,
+                #(#pinned,)*
+            ) #struct_where_clause;
+
+            impl #impl_generics ::std::marker::Unpin for #name #ty_generics #where_clause {}
+        };
+    }
+}
+
+/// A method of a delegated trait.
+struct Forward {
+    signature: TokenStream,
+    /// The trait's method, called with the inner value followed by `args`.
+    function: TokenStream,
+    args: TokenStream,
+    /// Whether the inner value is borrowed as `&mut`.
+    mutable: bool,
+}
+
+impl Forward {
+    fn new(signature: TokenStream, function: TokenStream, mutable: bool) -> Self {
+        Forward {
+            signature,
+            function,
+            args: TokenStream::new(),
+            mutable,
+        }
+    }
+
+    fn args(self, args: TokenStream) -> Self {
+        Forward { args, ..self }
+    }
+}
+
+/// Generates the items of a delegated trait impl.
+fn delegate_methods(name: &Ident, variants: &[&Variant], associated: TokenStream, methods: &[Forward]) -> TokenStream {
+    let mut items = associated;
+
+    for Forward { signature, function, args, mutable } in methods {
+        let mut arms = TokenStream::new();
+
+        for variant in variants {
+            let variant_name = &variant.ident;
+            let binding = if *mutable { quote!(ref mut v0) } else { quote!(ref v0) };
+            arms.extend(quote!(#name::#variant_name(#binding) => #function(v0, #args),));
+        }
+
+        items.extend(quote! {
+            fn #signature {
+                match *self {
+                    #arms
+                }
+            }
+        });
+    }
+
+    items
+}
+
+const DELEGATE_FN_EXPECTED: &str = "expected `delegate_fn(fn name(&self, arg: Type, ...) -> Type)`";
+
+/// A method listed with `#[variation(delegate_fn(...))]`, written as its
+/// signature.
+pub(crate) struct DelegateFn {
+    pub attrs: Vec<Attribute>,
+    vis: Visibility,
+    pub name: Ident,
+    pub generics: Generics,
+    pub receiver: TokenStream,
+    /// Whether the method takes `self` by reference, and mutably.
+    pub reference: Option<bool>,
+    pub args: Vec<(Ident, Type)>,
+    pub output: ReturnType,
+}
+
+impl DelegateFn {
+    pub(crate) fn parse(item: &Item) -> Result<Self> {
+        match item.value {
+            Value::List(ref tokens) => {
+                let parser = |input: ParseStream| {
+                    let delegate_fn = DelegateFn::parse_signature(input, "`delegate_fn`")?;
+
+                    match input.is_empty() {
+                        true => Ok(delegate_fn),
+                        false => Err(input.error(DELEGATE_FN_EXPECTED)),
+                    }
+                };
+
+                parser.parse2(tokens.clone())
+            }
+            _ => Err(Error::new(item.span(), DELEGATE_FN_EXPECTED)),
+        }
+    }
+
+    /// Parses the signature of a method taking `self`, where `option` names
+    /// what requires one.
+    pub(crate) fn parse_signature(input: ParseStream, option: &str) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![fn]>()?;
+        let name: Ident = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+
+        let and_token: Option<Token![&]> = content.parse()?;
+        let (receiver, reference) = match and_token {
+            Some(and_token) => {
+                let lifetime: Option<Lifetime> = content.parse()?;
+                let mutability: Option<Token![mut]> = content.parse()?;
+                (quote!(#and_token #lifetime #mutability), Some(mutability.is_some()))
+            }
+            None => (TokenStream::new(), None),
+        };
+
+        if !content.peek(Token![self]) {
+            return Err(Error::new(
+                name.span(),
+                format!("{} requires a method taking `self`, `&self` or `&mut self`", option),
+            ));
+        }
+
+        let self_token: Token![self] = content.parse()?;
+        let mut args = Vec::new();
+
+        while !content.is_empty() {
+            content.parse::<Token![,]>()?;
+
+            if content.is_empty() {
+                break;
+            }
+
+            let arg = content.parse()?;
+            content.parse::<Token![:]>()?;
+            args.push((arg, content.parse()?));
+        }
+
+        let output = input.parse()?;
+        generics.where_clause = input.parse()?;
+
+        Ok(DelegateFn {
+            attrs,
+            vis,
+            name,
+            generics,
+            receiver: quote!(#receiver #self_token),
+            reference,
+            args,
+            output,
+        })
+    }
+}
+
+/// Generates the methods of `#[variation(delegate_fn(...))]`, calling the
+/// method of the same name on the single field of each variant, or the one
+/// marked with `#[variation(delegate_fn)]` in variants with several.
+pub(crate) fn delegate_fns(name: &Ident, variants: &[&Variant], delegate_fns: &[DelegateFn]) -> Result<TokenStream> {
+    let mut fields = Vec::new();
+
+    for variant in variants {
+        let mut marked = None;
+
+        for (i, field) in variant.fields.iter().enumerate() {
+            let span = match FieldAttrs::parse(&field.attrs)?.delegate_fn {
+                Some(span) => span,
+                None => continue,
+            };
+
+            if delegate_fns.is_empty() {
+                return Err(Error::new(
+                    span,
+                    "`#[variation(delegate_fn)]` on a field requires `#[variation(delegate_fn(...))]` on the enum",
+                ));
+            }
+
+            if marked.is_some() {
+                return Err(Error::new(
+                    span,
+                    format!("`#[variation(delegate_fn)]` is already set on another field of `{}`", variant.ident),
+                ));
+            }
+
+            marked = Some((i, field));
+        }
+
+        let mut all = variant.fields.iter().enumerate();
+
+        fields.push(match (marked, all.next(), all.next()) {
+            (Some(marked), _, _) => Some(marked),
+            (None, Some(field), None) => Some(field),
+            _ => None,
+        });
+    }
+
+    let mut methods = TokenStream::new();
+
+    for (i, delegate_fn) in delegate_fns.iter().enumerate() {
+        let DelegateFn { attrs, vis, name: method, generics, receiver, reference, args, output } = delegate_fn;
+
+        if delegate_fns[..i].iter().any(|other| other.name == *method) {
+            return Err(Error::new(method.span(), format!("duplicate `delegate_fn` `{}`", method)));
+        }
+
+        let binding = match reference {
+            Some(true) => quote!(ref mut),
+            Some(false) => quote!(ref),
+            None => TokenStream::new(),
+        };
+        let arg_names = args.iter().map(|(arg, _)| arg).collect::<Vec<_>>();
+        let arg_types = args.iter().map(|(_, ty)| ty);
+        let mut arms = TokenStream::new();
+
+        for (variant, field) in variants.iter().zip(&fields) {
+            let variant_name = &variant.ident;
+            let member = match field {
+                Some((_, Field { ident: Some(ref field_name), .. })) => Member::Named(field_name.clone()),
+                Some((index, _)) => Member::Unnamed(Index::from(*index)),
+                None => {
+                    return Err(Error::new(
+                        method.span(),
+                        format!(
+                            "`delegate_fn` requires every variant to have a single field or one marked \
+                             `#[variation(delegate_fn)]`, `{}` doesn't",
+                            variant_name
+                        ),
+                    ))
+                }
+            };
+
+            arms.extend(quote!(#name::#variant_name { #member: #binding v0, .. } => v0.#method(#(#arg_names),*),));
+        }
+
+        let doc = match attrs.iter().any(|attr| attr.path.is_ident("doc")) {
+            true => TokenStream::new(),
+            false => {
+                let doc = format!("Calls `{}` on the inner value of the variant.", method);
+                quote!(#[doc = #doc])
+            }
+        };
+        let scrutinee = match reference {
+            Some(_) => quote!(*self),
+            None => quote!(self),
+        };
+        let where_clause = &generics.where_clause;
+
+        methods.extend(quote! {
+            #doc
+            #(#attrs)*
+            #vis fn #method #generics(#receiver, #(#arg_names: #arg_types),*) #output #where_clause {
+                match #scrutinee {
+                    #arms
+                }
+            }
+        });
+    }
+
+    Ok(methods)
+}
@@ -0,0 +1,267 @@
+//! `#[variation::dispatchable]` on a trait and `#[variation(dispatch = "...")]`
+//! on an enum, implementing the trait for the enum by forwarding every method
+//! to the value the enum holds. The derive can't see the trait, so the
+//! attribute records its methods in a macro named like the trait, which the
+//! derive calls with the variants.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::spanned::Spanned;
+use syn::*;
+
+use crate::delegate::{mentions_type_param, DelegateFn};
+
+const DISPATCHABLE: &str = "`#[variation::dispatchable]`";
+
+/// The parts of a trait marked with `#[variation::dispatchable]` its impls
+/// are generated from.
+struct Trait {
+    unsafety: Option<Token![unsafe]>,
+    name: Ident,
+    methods: Vec<DelegateFn>,
+}
+
+impl Parse for Trait {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.call(Attribute::parse_outer)?;
+        input.parse::<Visibility>()?;
+        let unsafety = input.parse()?;
+
+        if !input.peek(Token![trait]) {
+            return Err(Error::new(Span::call_site(), format!("{} is only available for traits", DISPATCHABLE)));
+        }
+
+        input.parse::<Token![trait]>()?;
+        let name = input.parse()?;
+        let generics: Generics = input.parse()?;
+
+        if !generics.params.is_empty() {
+            return Err(Error::new(
+                generics.params[0].span(),
+                format!("{} doesn't support generic traits", DISPATCHABLE),
+            ));
+        }
+
+        // The supertraits and the where clause, which the impl doesn't
+        // restate.
+        while !input.peek(token::Brace) {
+            input.parse::<TokenTree>()?;
+        }
+
+        let content;
+        braced!(content in input);
+        let mut methods = Vec::new();
+
+        while !content.is_empty() {
+            methods.push(parse_method.parse2(item_signature(&content)?)?);
+        }
+
+        Ok(Trait { unsafety, name, methods })
+    }
+}
+
+/// The tokens of the next item of the trait up to its default body or the
+/// `;` ending it, which are left out.
+fn item_signature(input: ParseStream) -> Result<TokenStream> {
+    let mut signature = TokenStream::new();
+
+    loop {
+        match input.parse::<TokenTree>()? {
+            TokenTree::Punct(ref punct) if punct.as_char() == ';' => break,
+            TokenTree::Group(ref group) if group.delimiter() == proc_macro2::Delimiter::Brace => break,
+            token => signature.extend(Some(token)),
+        }
+    }
+
+    Ok(signature)
+}
+
+/// Parses the signature of a method the impl forwards, rejecting the items
+/// it can't.
+fn parse_method(input: ParseStream) -> Result<DelegateFn> {
+    let ahead = input.fork();
+    ahead.call(Attribute::parse_outer)?;
+
+    if ahead.peek(Token![type]) || ahead.peek(Token![const]) && !ahead.peek2(Token![fn]) {
+        return Err(ahead.error(format!("{} only forwards methods, not associated types or consts", DISPATCHABLE)));
+    }
+
+    let qualified = ahead.peek(Token![unsafe]) || ahead.peek(Token![async]) || ahead.peek(Token![const]);
+
+    if qualified || ahead.peek(Token![extern]) {
+        return Err(ahead.error(format!(
+            "{} can't forward `unsafe`, `async`, `const` or `extern` methods",
+            DISPATCHABLE
+        )));
+    }
+
+    let method = DelegateFn::parse_signature(input, DISPATCHABLE)?;
+
+    if !input.is_empty() {
+        return Err(input.error("expected the end of the method signature"));
+    }
+
+    let output = &method.output;
+    let types = method.args.iter().map(|(_, ty)| ty);
+
+    if names_self(quote!(#(#types)* #output)) {
+        return Err(Error::new(
+            method.name.span(),
+            format!(
+                "{} can't forward `{}`, which names `Self` outside of its receiver",
+                DISPATCHABLE, method.name
+            ),
+        ));
+    }
+
+    Ok(method)
+}
+
+fn names_self(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ref ident) => ident == "Self",
+        TokenTree::Group(ref group) => names_self(group.stream()),
+        _ => false,
+    })
+}
+
+/// Generates the trait unchanged, followed by the macro implementing it for
+/// an enum, which is hidden and imported under the name of the trait.
+pub(crate) fn dispatchable(args: TokenStream, input: TokenStream) -> Result<TokenStream> {
+    if let Some(token) = args.into_iter().next() {
+        return Err(Error::new(token.span(), format!("{} does not take arguments", DISPATCHABLE)));
+    }
+
+    let Trait { unsafety, name, methods } = parse2(input.clone())?;
+    let macro_name = format_ident!("__variation_dispatch_{}", name);
+    let methods = methods.iter().map(forward);
+
+    Ok(quote! {
+        #input
+
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {
+            (
+                $trait_path:path,
+                [$($impl_generics:tt)*],
+                [$($ty:tt)*],
+                [$($where_clause:tt)*],
+                $enum_name:ident { $($variant:ident { $member:tt },)* }
+            ) => {
+                #unsafety impl $($impl_generics)* $trait_path for $($ty)* $($where_clause)* {
+                    #(#methods)*
+                }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #macro_name as #name;
+    })
+}
+
+/// Generates a method of the impl, calling the trait's method on the inner
+/// value of each variant. The type of the inner value is left to inference,
+/// as the generics of the method could shadow the names of field types.
+/// Only the `cfg` attributes of the method are kept.
+fn forward(method: &DelegateFn) -> TokenStream {
+    let DelegateFn { attrs, name, generics, receiver, reference, args, output, .. } = method;
+    let cfgs = attrs.iter().filter(|attr| attr.path.is_ident("cfg"));
+    let binding = match reference {
+        Some(true) => quote!(ref mut),
+        Some(false) => quote!(ref),
+        None => TokenStream::new(),
+    };
+    let scrutinee = match reference {
+        Some(_) => quote!(*self),
+        None => quote!(self),
+    };
+    let arg_names = args.iter().map(|(arg, _)| arg).collect::<Vec<_>>();
+    let arg_types = args.iter().map(|(_, ty)| ty);
+    let where_clause = &generics.where_clause;
+
+    quote! {
+        #(#cfgs)*
+        fn #name #generics(#receiver, #(#arg_names: #arg_types),*) #output #where_clause {
+            match #scrutinee {
+                $(
+                    $enum_name::$variant { $member: #binding v0 } => {
+                        <_ as $trait_path>::#name(v0, #(#arg_names),*)
+                    }
+                )*
+            }
+        }
+    }
+}
+
+/// Generates the calls of the macros recording the traits of
+/// `#[variation(dispatch = "...")]`, requiring every variant to have a single
+/// field. Fields naming a type parameter are bound by the trait.
+pub(crate) fn impl_dispatch(
+    ast: &DeriveInput,
+    variants: &[&Variant],
+    dispatch: &[(Span, Path)],
+) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let span = match dispatch.first() {
+        Some(&(span, _)) => span,
+        None => return Ok(TokenStream::new()),
+    };
+    let mut fields = Vec::new();
+
+    for variant in variants {
+        let mut all = variant.fields.iter();
+
+        match (all.next(), all.next()) {
+            (Some(field), None) => fields.push((&variant.ident, field)),
+            _ => {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "`#[variation(dispatch = \"...\")]` requires every variant to have a single field, `{}` \
+                         doesn't",
+                        variant.ident
+                    ),
+                ))
+            }
+        }
+    }
+
+    let mut calls = TokenStream::new();
+
+    for (_, path) in dispatch {
+        let mut generics = ast.generics.clone();
+
+        for (_, field) in &fields {
+            let ty = &field.ty;
+
+            if mentions_type_param(&ast.generics, quote!(#ty)) {
+                generics.make_where_clause().predicates.push(parse_quote!(#ty: #path));
+            }
+        }
+
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let variants = fields.iter().map(|(variant_name, field)| {
+            let member = match field.ident {
+                Some(ref field_name) => quote!(#field_name),
+                None => quote!(0),
+            };
+
+            quote!(#variant_name { #member },)
+        });
+
+        calls.extend(quote! {
+            #path! {
+                #path,
+                [#impl_generics],
+                [#name #ty_generics],
+                [#where_clause],
+                #name { #(#variants)* }
+            }
+        });
+    }
+
+    Ok(calls)
+}
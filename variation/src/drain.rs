@@ -0,0 +1,55 @@
+//! Removing values of a variant from a `Vec` of the enum for
+//! `#[variation(drain)]`.
+
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::iterator::pluralize;
+use crate::kind::kind_ident;
+use crate::names::{method_stem, Case};
+use crate::{ident, Conversion};
+
+/// Generates `drain_*` for every variant with fields, and `retain_kind`.
+pub(crate) fn methods(name: &Ident, variants: &[&Variant], case: Case) -> TokenStream {
+    let kind = kind_ident(name);
+    let mut methods = TokenStream::new();
+
+    for variant in variants.iter().filter(|variant| variant.fields.iter().count() != 0) {
+        let drain_fn = ident(&format!("drain_{}", pluralize(&method_stem(variant, case))));
+        let Conversion { pattern, inner, value, .. } = Conversion::new(name, variant);
+        let doc = format!(
+            "Removes every `{}` from `values` and returns their inner values, keeping the \
+             order of both the removed and the remaining values.",
+            variant.ident
+        );
+
+        methods.extend(quote! {
+            #[doc = #doc]
+            #[allow(unreachable_patterns)]
+            pub fn #drain_fn(values: &mut Vec<Self>) -> Vec<#inner> {
+                let mut drained = Vec::new();
+                let mut remaining = Vec::with_capacity(values.len());
+
+                for value in values.drain(..) {
+                    match value {
+                        #pattern => drained.push(#value),
+                        value => remaining.push(value),
+                    }
+                }
+
+                *values = remaining;
+                drained
+            }
+        });
+    }
+
+    quote! {
+        #methods
+
+        /// Removes every value from `values` that isn't of the given kind.
+        pub fn retain_kind(values: &mut Vec<Self>, kind: #kind) {
+            values.retain(|value| value.kind() == kind);
+        }
+    }
+}
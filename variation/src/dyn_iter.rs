@@ -0,0 +1,113 @@
+//! The `children` method of `#[variation(dyn_iter = "...")]`, iterating over
+//! the inner values as trait objects.
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::*;
+
+use crate::attr::FieldAttrs;
+
+/// Generates `children`, yielding the fields of the variant as `&dyn Trait`.
+/// Every field is yielded, except for those marked with
+/// `#[variation(no_dyn_iter)]`, or only those marked with
+/// `#[variation(dyn_iter)]` if there are any. The fields are collected into
+/// an array as long as the most any variant yields, padded with `None`.
+pub(crate) fn methods(ast: &DeriveInput, variants: &[&Variant], dyn_iter: Option<&Path>) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let mut marked = Vec::new();
+    let mut opted_in = None;
+    let mut opted_out = None;
+
+    for variant in variants {
+        let mut fields = Vec::new();
+
+        for field in &variant.fields {
+            let attrs = FieldAttrs::parse(&field.attrs)?;
+            opted_in = opted_in.or(attrs.dyn_iter);
+            opted_out = opted_out.or(attrs.no_dyn_iter);
+            fields.push((field, attrs));
+        }
+
+        marked.push(fields);
+    }
+
+    let trait_path = match (dyn_iter, opted_in.or(opted_out)) {
+        (Some(trait_path), _) => trait_path,
+        (None, Some(span)) => {
+            let attribute = if opted_in.is_some() { "dyn_iter" } else { "no_dyn_iter" };
+
+            return Err(Error::new(
+                span,
+                format!(
+                    "`#[variation({})]` on a field requires `#[variation(dyn_iter = \"...\")]` on the enum",
+                    attribute
+                ),
+            ));
+        }
+        (None, None) => return Ok(TokenStream::new()),
+    };
+
+    if let (Some(_), Some(span)) = (opted_in, opted_out) {
+        return Err(Error::new(
+            span,
+            "`#[variation(no_dyn_iter)]` can't be combined with `#[variation(dyn_iter)]` on other fields, which \
+             only yields the marked ones",
+        ));
+    }
+
+    let mut arms = Vec::new();
+    let mut len = 0;
+
+    for (variant, fields) in variants.iter().zip(&marked) {
+        let variant_name = &variant.ident;
+        let mut bindings = TokenStream::new();
+        let mut values = Vec::new();
+
+        for (i, (field, attrs)) in fields.iter().enumerate() {
+            let yielded = match opted_in {
+                Some(_) => attrs.dyn_iter.is_some(),
+                None => attrs.no_dyn_iter.is_none(),
+            };
+
+            if !yielded {
+                continue;
+            }
+
+            let member = match field.ident {
+                Some(ref field_name) => Member::Named(field_name.clone()),
+                None => Member::Unnamed(Index::from(i)),
+            };
+            // Spanned at the field, where a type not implementing the trait
+            // is reported.
+            let span = field.ty.span();
+            let binding = Ident::new(&format!("v{}", i), span);
+
+            bindings.extend(quote!(#member: ref #binding,));
+            values.push(quote_spanned!(span=> ::core::option::Option::Some(#binding as &dyn #trait_path)));
+        }
+
+        len = len.max(values.len());
+        arms.push((quote!(#name::#variant_name { #bindings .. }), values));
+    }
+
+    let arms = arms.into_iter().map(|(pattern, mut values)| {
+        values.resize(len, quote!(::core::option::Option::None));
+        quote!(#pattern => [#(#values),*],)
+    });
+    let doc = format!(
+        "Returns an iterator over the inner values of the variant as `&dyn {}`.",
+        quote!(#trait_path).to_string().replace(' ', "")
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        pub fn children(&self) -> impl ::core::iter::Iterator<Item = &dyn #trait_path> + '_ {
+            let children: [::core::option::Option<&dyn #trait_path>; #len] = match *self {
+                #(#arms)*
+            };
+
+            ::core::iter::IntoIterator::into_iter(children).flatten()
+        }
+    })
+}
@@ -0,0 +1,64 @@
+//! Conversions between two variant enums and `either::Either`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::Conversion;
+
+/// Generates `into_either`, `from_either` and `as_either`.
+pub(crate) fn methods(name: &Ident, variants: &[&Variant], span: Span) -> Result<TokenStream> {
+    let (left, right) = match variants {
+        [left, right] => (Conversion::new(name, left), Conversion::new(name, right)),
+        _ => {
+            return Err(Error::new(
+                span,
+                "`#[variation(either)]` is only available for enums with two variants",
+            ))
+        }
+    };
+
+    let Conversion {
+        pattern: left_pattern,
+        ref_pattern: left_ref_pattern,
+        inner: left_inner,
+        inner_ref: left_inner_ref,
+        value: left_value,
+        ref_value: left_ref_value,
+    } = left;
+    let Conversion {
+        pattern: right_pattern,
+        ref_pattern: right_ref_pattern,
+        inner: right_inner,
+        inner_ref: right_inner_ref,
+        value: right_value,
+        ref_value: right_ref_value,
+    } = right;
+
+    Ok(quote! {
+        /// Converts the enum into an `Either`, with the first variant's inner
+        /// types on the left and the second's on the right.
+        pub fn into_either(self) -> ::either::Either<#left_inner, #right_inner> {
+            match self {
+                #left_pattern => ::either::Either::Left(#left_value),
+                #right_pattern => ::either::Either::Right(#right_value),
+            }
+        }
+
+        /// Converts an `Either` into the enum, the inverse of `into_either`.
+        pub fn from_either(either: ::either::Either<#left_inner, #right_inner>) -> Self {
+            match either {
+                ::either::Either::Left(#left_value) => #left_pattern,
+                ::either::Either::Right(#right_value) => #right_pattern,
+            }
+        }
+
+        /// Borrows the enum's inner types as an `Either`.
+        pub fn as_either(&self) -> ::either::Either<#left_inner_ref, #right_inner_ref> {
+            match self {
+                #left_ref_pattern => ::either::Either::Left(#left_ref_value),
+                #right_ref_pattern => ::either::Either::Right(#right_ref_value),
+            }
+        }
+    })
+}
@@ -0,0 +1,87 @@
+//! The `Display` and `Error` impls for error enums marked with
+//! `#[variation(error)]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::attr::VariantAttrs;
+use crate::delegate::mentions_type_param;
+
+/// Generates `impl Display for Enum` and `impl Error for Enum`. Variants wrap
+/// at most a single error, which `Display` writes after the variant's
+/// message, if any, and `source` returns. Unit variants need a message.
+pub(crate) fn impl_error(ast: &DeriveInput, variants: &[(&Variant, VariantAttrs)], span: Span) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let mut generics = ast.generics.clone();
+    let mut display_arms = TokenStream::new();
+    let mut source_arms = TokenStream::new();
+
+    for (variant, attrs) in variants {
+        let variant_name = &variant.ident;
+        let msg = attrs.error_msg.as_ref();
+
+        match variant.fields {
+            Fields::Unit => {
+                let msg = msg.ok_or_else(|| {
+                    Error::new(
+                        span,
+                        format!(
+                            "`#[variation(error)]` requires `{}` to have a message, such as \
+                             `#[variation(error(msg = \"...\"))]`",
+                            variant_name
+                        ),
+                    )
+                })?;
+
+                display_arms.extend(quote!(#name::#variant_name => f.write_str(#msg),));
+                source_arms.extend(quote!(#name::#variant_name => ::std::option::Option::None,));
+            }
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+
+                if mentions_type_param(&ast.generics, quote!(#ty)) {
+                    generics
+                        .make_where_clause()
+                        .predicates
+                        .push(parse_quote!(#ty: ::std::error::Error + 'static));
+                }
+
+                display_arms.extend(match msg {
+                    Some(msg) => quote!(#name::#variant_name(ref v0) => write!(f, "{}: {}", #msg, v0),),
+                    None => quote!(#name::#variant_name(ref v0) => ::std::fmt::Display::fmt(v0, f),),
+                });
+                source_arms.extend(quote!(#name::#variant_name(ref v0) => ::std::option::Option::Some(v0),));
+            }
+            _ => {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "`#[variation(error)]` requires every variant to have at most a single field, `{}` doesn't",
+                        variant_name
+                    ),
+                ))
+            }
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match *self {
+                    #display_arms
+                }
+            }
+        }
+
+        impl #impl_generics ::std::error::Error for #name #ty_generics #where_clause {
+            fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+                match *self {
+                    #source_arms
+                }
+            }
+        }
+    })
+}
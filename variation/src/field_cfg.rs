@@ -0,0 +1,100 @@
+//! `#[cfg]` on the fields of variants, which the enums of `augment!` and
+//! `variation_remote!` keep, unlike those of `#[derive(Variation)]` that the
+//! compiler configures before the derive sees them. How many fields such a
+//! variant has depends on the configuration, so everything is generated for
+//! each combination of the predicates, from the enum as it is in that
+//! combination and under a `#[cfg]` selecting it.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::*;
+
+/// The most distinct predicates the fields can be configured with, as each of
+/// them doubles the generated code.
+const MAX_PREDICATES: usize = 4;
+
+fn is_cfg(attr: &Attribute) -> bool {
+    attr.path.is_ident("cfg")
+}
+
+/// The distinct predicates of the `#[cfg]` attributes on the fields of the
+/// enum, in the order they're first used.
+pub(crate) fn predicates(ast: &DeriveInput) -> Result<Vec<TokenStream>> {
+    let data = match ast.data {
+        Data::Enum(ref data) => data,
+        _ => return Ok(Vec::new()),
+    };
+    let mut predicates = Vec::new();
+
+    for field in data.variants.iter().flat_map(|variant| &variant.fields) {
+        for attr in field.attrs.iter().filter(|attr| is_cfg(attr)) {
+            let predicate = attr.parse_args::<TokenStream>()?;
+
+            if position(&predicates, &predicate).is_some() {
+                continue;
+            }
+
+            if predicates.len() == MAX_PREDICATES {
+                return Err(Error::new_spanned(
+                    attr,
+                    format!("fields can only be configured with up to {} distinct `#[cfg]` predicates", MAX_PREDICATES),
+                ));
+            }
+
+            predicates.push(predicate);
+        }
+    }
+
+    Ok(predicates)
+}
+
+fn position(predicates: &[TokenStream], predicate: &TokenStream) -> Option<usize> {
+    let predicate = predicate.to_string();
+    predicates.iter().position(|other| other.to_string() == predicate)
+}
+
+/// The enum in the combination of `predicates` where those with their bit
+/// set in `enabled` hold, without the fields it leaves out and the `#[cfg]`
+/// of those it keeps, along with the `#[cfg]` selecting the combination.
+pub(crate) fn configure(
+    ast: &DeriveInput,
+    predicates: &[TokenStream],
+    enabled: usize,
+) -> Result<(DeriveInput, TokenStream)> {
+    let mut configured = ast.clone();
+
+    if let Data::Enum(ref mut data) = configured.data {
+        for variant in &mut data.variants {
+            let fields = match variant.fields {
+                Fields::Named(ref mut fields) => &mut fields.named,
+                Fields::Unnamed(ref mut fields) => &mut fields.unnamed,
+                Fields::Unit => continue,
+            };
+            let mut kept = Punctuated::new();
+
+            for mut field in fields.clone() {
+                let mut holds = true;
+
+                for attr in field.attrs.iter().filter(|attr| is_cfg(attr)) {
+                    let predicate = attr.parse_args::<TokenStream>()?;
+                    holds &= matches!(position(predicates, &predicate), Some(i) if enabled & 1 << i != 0);
+                }
+
+                if holds {
+                    field.attrs.retain(|attr| !is_cfg(attr));
+                    kept.push(field);
+                }
+            }
+
+            *fields = kept;
+        }
+    }
+
+    let terms = predicates.iter().enumerate().map(|(i, predicate)| match enabled & 1 << i {
+        0 => quote!(not(#predicate)),
+        _ => quote!(#predicate),
+    });
+
+    Ok((configured, quote!(#[cfg(all(#(#terms),*))])))
+}
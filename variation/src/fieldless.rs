@@ -0,0 +1,318 @@
+//! Methods and impls for fieldless enums, whose variants don't have inner
+//! values.
+
+use std::collections::HashMap;
+
+use heck::SnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::*;
+
+use crate::attr::VariantAttrs;
+use crate::names::variant_str;
+
+const INTEGERS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Rejects enums with a variant that has fields for the attribute at `span`.
+pub(crate) fn require_fieldless(variants: &[&Variant], span: Span, attribute: &str) -> Result<()> {
+    match variants.iter().find(|variant| variant.fields.iter().count() != 0) {
+        Some(variant) => Err(Error::new(
+            span,
+            format!(
+                "`#[variation({})]` is only available for fieldless enums, `{}` has fields",
+                attribute, variant.ident
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Generates `VARIANTS` and `iter`.
+pub(crate) fn iter_methods(name: &Ident, variants: &[&Variant]) -> TokenStream {
+    let count = variants.len();
+    let mut values = TokenStream::new();
+
+    for variant in variants {
+        let variant_name = &variant.ident;
+        values.extend(quote!(#name::#variant_name,));
+    }
+
+    quote! {
+        /// Every variant, in the order they are declared in.
+        pub const VARIANTS: [Self; #count] = [#values];
+
+        /// Returns an iterator over every variant, in the order they are
+        /// declared in.
+        pub fn iter() -> impl Iterator<Item = Self> {
+            ::std::iter::IntoIterator::into_iter(Self::VARIANTS)
+        }
+    }
+}
+
+/// Generates `next` and `prev`.
+pub(crate) fn cycle_methods(name: &Ident, variants: &[&Variant]) -> TokenStream {
+    let mut next_arms = TokenStream::new();
+    let mut prev_arms = TokenStream::new();
+
+    for (index, variant) in variants.iter().enumerate() {
+        let variant_name = &variant.ident;
+        let next = &variants[(index + 1) % variants.len()].ident;
+        let prev = &variants[(index + variants.len() - 1) % variants.len()].ident;
+        next_arms.extend(quote!(#name::#variant_name => #name::#next,));
+        prev_arms.extend(quote!(#name::#variant_name => #name::#prev,));
+    }
+
+    quote! {
+        /// Returns the variant declared after this one, wrapping around to the
+        /// first variant after the last.
+        pub fn next(self) -> Self {
+            match self {
+                #next_arms
+            }
+        }
+
+        /// Returns the variant declared before this one, wrapping around to
+        /// the last variant before the first.
+        pub fn prev(self) -> Self {
+            match self {
+                #prev_arms
+            }
+        }
+    }
+}
+
+/// The integer type of the enum's `#[repr(...)]` attribute.
+pub(crate) fn repr_type(ast: &DeriveInput, span: Span, attribute: &str) -> Result<Ident> {
+    for attr in ast.attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in list.nested {
+                if let NestedMeta::Meta(Meta::Path(ref path)) = nested {
+                    match path.get_ident() {
+                        Some(ty) if INTEGERS.contains(&&*ty.to_string()) => return Ok(ty.clone()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    Err(Error::new(
+        span,
+        format!(
+            "`#[variation({})]` requires an integer `#[repr(...)]` attribute, such as `#[repr(u8)]`",
+            attribute
+        ),
+    ))
+}
+
+/// Generates `from_repr` and `to_repr`. The discriminants are matched through
+/// constants, named apart from the variants so that a variant such as `None`
+/// can't shadow anything the arms use.
+pub(crate) fn repr_methods(name: &Ident, variants: &[&Variant], repr: &Ident) -> TokenStream {
+    let mut consts = TokenStream::new();
+    let mut arms = TokenStream::new();
+    let mut to_repr_arms = TokenStream::new();
+
+    for variant in variants {
+        let variant_name = &variant.ident;
+        let discriminant = format_ident!("__VARIATION_{}", variant_name);
+        consts.extend(quote!(const #discriminant: #repr = #name::#variant_name as #repr;));
+        arms.extend(quote!(#discriminant => ::core::option::Option::Some(#name::#variant_name),));
+        to_repr_arms.extend(quote!(#name::#variant_name => #name::#variant_name as #repr,));
+    }
+
+    quote! {
+        /// Returns the variant with the discriminant `value`, or `None` if
+        /// there is no such variant.
+        #[allow(non_upper_case_globals)]
+        pub fn from_repr(value: #repr) -> ::core::option::Option<Self> {
+            #consts
+
+            match value {
+                #arms
+                _ => ::core::option::Option::None,
+            }
+        }
+
+        /// Returns the discriminant of the variant.
+        pub fn to_repr(&self) -> #repr {
+            match self {
+                #to_repr_arms
+            }
+        }
+    }
+}
+
+/// Generates `impl TryFrom<Repr> for Enum` along with the `{Enum}ReprError`
+/// it returns for values that aren't a discriminant.
+pub(crate) fn impl_try_from_repr(ast: &DeriveInput, repr: &Ident) -> TokenStream {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let error = Ident::new(&format!("{}ReprError", name), Span::call_site());
+    let doc = format!("The error for a value that isn't the discriminant of a [`{}`].", name);
+    let message = format!("`{{}}` is not a discriminant of `{}`", name);
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        #vis struct #error(#repr);
+
+        impl #error {
+            /// The value that was rejected.
+            pub fn value(&self) -> #repr {
+                self.0
+            }
+        }
+
+        impl ::std::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(f, #message, self.0)
+            }
+        }
+
+        impl ::std::error::Error for #error {}
+
+        impl ::std::convert::TryFrom<#repr> for #name {
+            type Error = #error;
+
+            fn try_from(value: #repr) -> ::std::result::Result<Self, Self::Error> {
+                #name::from_repr(value).ok_or(#error(value))
+            }
+        }
+    }
+}
+
+/// The strings `FromStr` accepts for each variant, either the string given
+/// with `#[variation(str = "...")]` or its name and its snake case name. The
+/// first is the canonical spelling returned by `as_str`, and the only one
+/// checked for conflicts with `canonical_only`.
+fn spellings<'a>(
+    variants: &[(&'a Variant, VariantAttrs)],
+    span: Span,
+    attribute: &str,
+    canonical_only: bool,
+) -> Result<Vec<(&'a Variant, Vec<String>)>> {
+    let mut seen = HashMap::new();
+    let mut all = Vec::new();
+
+    for (variant, attrs) in variants {
+        let (spellings, span) = match attrs.str {
+            Some(ref string) => (vec![string.value()], string.span()),
+            None => {
+                let name = variant_str(variant);
+                let snake_case = name.to_snake_case();
+
+                if snake_case == name {
+                    (vec![name], span)
+                } else {
+                    (vec![name, snake_case], span)
+                }
+            }
+        };
+        let checked = if canonical_only { &spellings[..1] } else { &spellings[..] };
+
+        for spelling in checked {
+            if let Some(other) = seen.insert(spelling.clone(), &variant.ident) {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "conflicting `#[variation({})]`, `{}` and `{}` both use `{}`",
+                        attribute, other, variant.ident, spelling
+                    ),
+                ));
+            }
+        }
+
+        all.push((*variant, spellings));
+    }
+
+    Ok(all)
+}
+
+/// Generates `as_str`.
+pub(crate) fn as_str(name: &Ident, variants: &[(&Variant, VariantAttrs)], span: Span) -> Result<TokenStream> {
+    let mut arms = TokenStream::new();
+
+    for (variant, spellings) in spellings(variants, span, "as_str", true)? {
+        let variant_name = &variant.ident;
+        let canonical = &spellings[0];
+        arms.extend(quote!(#name::#variant_name => #canonical,));
+    }
+
+    Ok(quote! {
+        /// Returns the name of the variant, which `FromStr` parses back into
+        /// the variant if it is generated.
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                #arms
+            }
+        }
+    })
+}
+
+/// Generates `impl FromStr for Enum` along with the `{Enum}ParseError` it
+/// returns for strings that don't name a variant.
+pub(crate) fn impl_from_str(
+    ast: &DeriveInput,
+    variants: &[(&Variant, VariantAttrs)],
+    span: Span,
+) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let error = Ident::new(&format!("{}ParseError", name), Span::call_site());
+    let doc = format!("The error for a string that doesn't name a variant of [`{}`].", name);
+    let mut accepted = Vec::new();
+    let mut arms = TokenStream::new();
+
+    for (variant, spellings) in spellings(variants, span, "from_str", false)? {
+        let variant_name = &variant.ident;
+        let patterns = &spellings;
+        arms.extend(quote!(#(#patterns)|* => ::std::result::Result::Ok(#name::#variant_name),));
+        accepted.extend(spellings);
+    }
+
+    let message = format!(
+        "unknown `{}` `{{}}`, expected one of {}",
+        name,
+        accepted.iter().map(|spelling| format!("`{}`", spelling)).collect::<Vec<_>>().join(", ")
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        #vis struct #error(String);
+
+        impl #error {
+            /// The string that was rejected.
+            pub fn value(&self) -> &str {
+                &self.0
+            }
+
+            /// The strings that name a variant.
+            pub fn accepted(&self) -> &'static [&'static str] {
+                &[#(#accepted),*]
+            }
+        }
+
+        impl ::std::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(f, #message, self.0)
+            }
+        }
+
+        impl ::std::error::Error for #error {}
+
+        impl ::std::str::FromStr for #name {
+            type Err = #error;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                match s {
+                    #arms
+                    _ => ::std::result::Result::Err(#error(s.to_owned())),
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,148 @@
+//! Methods of the enum forwarding to the enum a variant marked with
+//! `#[variation(flatten(...))]` wraps.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::*;
+
+use crate::accessor::Accessor;
+use crate::attr::{self, ContainerAttrs, Families, VariantAttrs};
+use crate::inner_ref_type;
+use crate::names::{self, Case};
+
+/// A variant marked with `#[variation(flatten(...))]`, along with the
+/// variants of the wrapped enum it lists.
+pub(crate) struct Flattened<'a> {
+    pub outer: &'a Variant,
+    pub listed: Vec<(&'a Variant, VariantAttrs)>,
+}
+
+/// Finds the flattened variants, which have to wrap a single value. The
+/// listed variants only get `is_*`, `as_*` and `as_*_mut`, and skip those the
+/// enum leaves out with `#[variation(methods(...))]`.
+pub(crate) fn find<'a>(
+    variants: &'a [(&'a Variant, VariantAttrs)],
+    container: &ContainerAttrs,
+) -> Result<Vec<Flattened<'a>>> {
+    let mut flattened = Vec::new();
+
+    for (outer, attrs) in variants {
+        let (span, listed) = match attrs.flatten {
+            Some((span, ref listed)) => (span, listed),
+            None => continue,
+        };
+
+        if outer.fields.iter().count() != 1 {
+            return Err(Error::new(span, "`#[variation(flatten(...))]` requires a variant with a single field"));
+        }
+
+        attr::check_flatten(listed)?;
+
+        let listed = listed
+            .iter()
+            .map(|variant| {
+                let mut attrs = VariantAttrs::parse(&variant.attrs)?;
+                attrs.skip.extend(&container.skip);
+                attrs.skip.into = Some(span);
+                Ok((variant, attrs))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        flattened.push(Flattened { outer, listed });
+    }
+
+    Ok(flattened)
+}
+
+/// The accessors of the listed variants, calling those of the wrapped value
+/// for the flattened variant and returning `false` or `None` otherwise.
+pub(crate) fn accessors(
+    path: &TokenStream,
+    flattened: &[Flattened],
+    container: &ContainerAttrs,
+    case: Case,
+) -> Vec<Accessor> {
+    let attr::MethodVis { is: is_vis, as_ref: as_vis, as_mut: as_mut_vis, .. } = &container.method_vis;
+    let Families { is: is_must_use, as_ref: as_must_use, as_mut: as_mut_must_use, .. } = container.method_must_use();
+    let Families { is: is_cfg, as_ref: as_cfg, as_mut: as_mut_cfg, .. } = container.method_cfg_attrs();
+    let Families { is: is_attrs, as_ref: as_attrs, as_mut: as_mut_attrs, .. } = container.method_extra_attrs();
+    let bounds = &container.method_bounds;
+    let is_where = attr::method_where_clause(&bounds.is);
+    let as_where = attr::method_where_clause(&bounds.as_ref);
+    let as_mut_where = attr::method_where_clause(&bounds.as_mut);
+    let mut accessors = Vec::new();
+
+    for Flattened { outer, listed } in flattened {
+        let outer_name = &outer.ident;
+        let pattern = quote!(#path::#outer_name(inner));
+
+        for (variant, attrs) in listed {
+            let skip = &attrs.skip;
+            let inline = attrs.inline(container);
+            let doc = attrs.doc();
+            let Families { is: is_fn, as_ref: as_fn, as_mut: as_mut_fn, .. } =
+                names::method_names(variant, attrs, case, &container.method_prefixes);
+
+            if skip.is.is_none() {
+                accessors.push(Accessor {
+                    cfg: is_cfg.clone(),
+                    attrs: quote!(#doc #is_must_use),
+                    impl_attrs: quote!(#inline #is_attrs),
+                    vis: quote!(#is_vis),
+                    signature: quote!(fn #is_fn(&self) -> bool #is_where),
+                    body: quote! {
+                        match self {
+                            #pattern => inner.#is_fn(),
+                            _ => false,
+                        }
+                    },
+                    consuming: false,
+                });
+            }
+
+            if variant.fields.iter().count() == 0 {
+                continue;
+            }
+
+            if skip.as_ref.is_none() {
+                let return_by_ref = inner_ref_type(variant, quote!(&));
+
+                accessors.push(Accessor {
+                    cfg: as_cfg.clone(),
+                    attrs: quote!(#doc #as_must_use),
+                    impl_attrs: quote!(#inline #as_attrs),
+                    vis: quote!(#as_vis),
+                    signature: quote!(fn #as_fn(&self) -> Option<#return_by_ref> #as_where),
+                    body: quote! {
+                        match self {
+                            #pattern => inner.#as_fn(),
+                            _ => None,
+                        }
+                    },
+                    consuming: false,
+                });
+            }
+
+            if skip.as_mut.is_none() {
+                let return_by_ref_mut = inner_ref_type(variant, quote!(&mut));
+
+                accessors.push(Accessor {
+                    cfg: as_mut_cfg.clone(),
+                    attrs: quote!(#doc #as_mut_must_use),
+                    impl_attrs: quote!(#inline #as_mut_attrs),
+                    vis: quote!(#as_mut_vis),
+                    signature: quote!(fn #as_mut_fn(&mut self) -> Option<#return_by_ref_mut> #as_mut_where),
+                    body: quote! {
+                        match self {
+                            #pattern => inner.#as_mut_fn(),
+                            _ => None,
+                        }
+                    },
+                    consuming: false,
+                });
+            }
+        }
+    }
+
+    accessors
+}
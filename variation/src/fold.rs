@@ -0,0 +1,94 @@
+//! Methods handling every variant of the enum with its own closure.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::names::{method_stem, Case};
+use crate::{fresh_type_param, generate_ident_list_pattern, reference_type};
+
+/// Generates `either` and `either_ref` for enums marked with
+/// `#[variation(fold)]` with two variants that both hold data.
+pub(crate) fn either(ast: &DeriveInput, variants: &[&Variant]) -> TokenStream {
+    match variants {
+        [left, right] if left.fields.iter().count() > 0 && right.fields.iter().count() > 0 => {}
+        _ => return TokenStream::new(),
+    }
+
+    let closures = [Ident::new("f", Span::call_site()), Ident::new("g", Span::call_site())];
+    let either = fold_method(ast, variants, &quote!(either), &closures, false);
+    let either_ref = fold_method(ast, variants, &quote!(either_ref), &closures, true);
+
+    quote! {
+        /// Consumes the enum, calling `f` with the first variant's inner
+        /// values and `g` with the second's.
+        #either
+
+        /// Calls `f` with references to the first variant's inner values and
+        /// `g` with references to the second's.
+        #either_ref
+    }
+}
+
+/// Generates `fold` and `fold_ref`, taking a closure for every variant named
+/// after it, such as `on_integer` for `Integer`.
+pub(crate) fn fold(ast: &DeriveInput, variants: &[&Variant], case: Case) -> TokenStream {
+    let closures = variants
+        .iter()
+        .map(|variant| {
+            let snake_case = method_stem(variant, case);
+            Ident::new(&format!("on_{}", snake_case), Span::call_site())
+        })
+        .collect::<Vec<_>>();
+    let fold = fold_method(ast, variants, &quote!(fold), &closures, false);
+    let fold_ref = fold_method(ast, variants, &quote!(fold_ref), &closures, true);
+
+    quote! {
+        /// Consumes the enum, calling the closure for its variant with the
+        /// inner values.
+        #fold
+
+        /// Calls the closure for the enum's variant with references to the
+        /// inner values.
+        #fold_ref
+    }
+}
+
+/// A method taking one closure per variant, in declaration order, and
+/// calling the one matching the variant with its inner values.
+fn fold_method(
+    ast: &DeriveInput,
+    variants: &[&Variant],
+    method: &TokenStream,
+    closures: &[Ident],
+    by_ref: bool,
+) -> TokenStream {
+    let name = &ast.ident;
+    let output = fresh_type_param(&ast.generics, "R");
+    let (lifetime, receiver, reference) = if by_ref {
+        (quote!('variation,), quote!(&'variation self), quote!(&'variation))
+    } else {
+        (quote!(), quote!(self), quote!())
+    };
+    let mut params = TokenStream::new();
+    let mut arms = TokenStream::new();
+
+    for (variant, closure) in variants.iter().zip(closures) {
+        let variant_name = &variant.ident;
+        let field_count = variant.fields.iter().count();
+        let fields = generate_ident_list_pattern(field_count, false, false);
+        let types = variant.fields.iter().map(|field| reference_type(&reference, &field.ty));
+        let call = if field_count == 0 { quote!(#closure()) } else { quote!(#closure #fields) };
+
+        params.extend(quote!(#closure: impl FnOnce(#(#types,)*) -> #output,));
+        arms.extend(quote!(#name::#variant_name#fields => #call,));
+    }
+
+    quote! {
+        pub fn #method<#lifetime #output>(#receiver, #params) -> #output {
+            match self {
+                #arms
+            }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Methods for enums whose variants all wrap a single value of the same type.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::*;
+
+use crate::reference_type;
+
+/// The type every variant wraps, if the variants all have a single field of
+/// textually identical types.
+fn common_type<'a>(variants: &[&'a Variant]) -> Option<&'a Type> {
+    let mut types = variants.iter().map(|variant| match variant.fields {
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => Some(&fields.unnamed[0].ty),
+        _ => None,
+    });
+
+    let first = types.next()??;
+    let first_tokens = first.into_token_stream().to_string();
+
+    for ty in types {
+        if ty?.into_token_stream().to_string() != first_tokens {
+            return None;
+        }
+    }
+
+    Some(first)
+}
+
+/// The type every variant wraps, rejecting enums whose variants don't all
+/// wrap the same type for the attribute at `span`.
+pub(crate) fn require_common_type<'a>(variants: &[&'a Variant], span: Span, attribute: &str) -> Result<&'a Type> {
+    common_type(variants).ok_or_else(|| {
+        Error::new(
+            span,
+            format!(
+                "`#[variation({})]` requires every variant to wrap a single value of the same type",
+                attribute
+            ),
+        )
+    })
+}
+
+/// Generates `into_inner`, `as_inner`, `as_inner_mut`, `map_inner` and
+/// `map_inner_mut` for enums marked with `#[variation(inner)]`.
+pub(crate) fn inner_methods(name: &Ident, variants: &[&Variant], ty: &Type) -> TokenStream {
+    let mut arms = TokenStream::new();
+    let mut ref_arms = TokenStream::new();
+    let mut mut_arms = TokenStream::new();
+    let mut map_arms = TokenStream::new();
+    let ref_ty = reference_type(&quote!(&), ty);
+    let mut_ty = reference_type(&quote!(&mut), ty);
+
+    for variant in variants {
+        let variant_name = &variant.ident;
+        arms.extend(quote!(#name::#variant_name(v0) => v0,));
+        map_arms.extend(quote!(#name::#variant_name(v0) => #name::#variant_name(f(v0)),));
+        ref_arms.extend(quote!(#name::#variant_name(ref v0) => v0,));
+        mut_arms.extend(quote!(#name::#variant_name(ref mut v0) => v0,));
+    }
+
+    quote! {
+        /// Consumes the enum and returns the inner value, whichever variant it
+        /// is.
+        pub fn into_inner(self) -> #ty {
+            match self {
+                #arms
+            }
+        }
+
+        /// Returns a reference to the inner value, whichever variant it is.
+        pub fn as_inner(&self) -> #ref_ty {
+            match *self {
+                #ref_arms
+            }
+        }
+
+        /// Returns a mutable reference to the inner value, whichever variant
+        /// it is.
+        pub fn as_inner_mut(&mut self) -> #mut_ty {
+            match *self {
+                #mut_arms
+            }
+        }
+
+        /// Replaces the inner value with the result of calling `f` on it,
+        /// keeping the variant.
+        pub fn map_inner(self, f: impl FnOnce(#ty) -> #ty) -> Self {
+            match self {
+                #map_arms
+            }
+        }
+
+        /// Calls `f` with a mutable reference to the inner value, keeping the
+        /// variant.
+        pub fn map_inner_mut(&mut self, f: impl FnOnce(#mut_ty)) {
+            f(self.as_inner_mut())
+        }
+    }
+}
+
+/// Generates `Deref` and `DerefMut` to the inner value for enums marked with
+/// `#[variation(deref)]`.
+pub(crate) fn impl_deref(ast: &DeriveInput, variants: &[&Variant], ty: &Type) -> TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let mut ref_arms = TokenStream::new();
+    let mut mut_arms = TokenStream::new();
+
+    for variant in variants {
+        let variant_name = &variant.ident;
+        ref_arms.extend(quote!(#name::#variant_name(ref v0) => v0,));
+        mut_arms.extend(quote!(#name::#variant_name(ref mut v0) => v0,));
+    }
+
+    quote! {
+        impl #impl_generics ::std::ops::Deref for #name #ty_generics #where_clause {
+            type Target = #ty;
+
+            fn deref(&self) -> &Self::Target {
+                match *self {
+                    #ref_arms
+                }
+            }
+        }
+
+        impl #impl_generics ::std::ops::DerefMut for #name #ty_generics #where_clause {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                match *self {
+                    #mut_arms
+                }
+            }
+        }
+    }
+}
+
+/// Generates `into_ok_or_err` for enums marked with
+/// `#[variation(into_ok_or_err)]`, rejecting those without exactly two
+/// variants for the attribute at `span`.
+pub(crate) fn into_ok_or_err(name: &Ident, variants: &[&Variant], ty: &Type, span: Span) -> Result<TokenStream> {
+    if variants.len() != 2 {
+        return Err(Error::new(span, "`#[variation(into_ok_or_err)]` requires exactly two variants"));
+    }
+
+    let arms = variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        quote!(#name::#variant_name(v0) => v0,)
+    });
+
+    Ok(quote! {
+        /// Consumes the enum and returns the inner value, whichever of the two
+        /// variants it is.
+        pub fn into_ok_or_err(self) -> #ty {
+            match self {
+                #(#arms)*
+            }
+        }
+    })
+}
@@ -0,0 +1,126 @@
+//! Iterator adapters for `#[variation(iterator_ext)]`, filtering an iterator
+//! of the enum down to the inner values of one variant.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::names::{method_stem, Case};
+use crate::{ident, inner_ref_type, inner_type, stem_ident, Conversion};
+
+/// Generates the `{Enum}IteratorExt` trait, implemented for every iterator,
+/// with an adapter for each variant with inner values. Its methods are
+/// available on iterators of both the enum and references to it, through the
+/// `{Enum}IteratorItem` trait implemented for both.
+pub(crate) fn iterator_ext(ast: &DeriveInput, variants: &[&Variant], case: Case) -> TokenStream {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let mut ref_generics = ast.generics.clone();
+    ref_generics
+        .params
+        .insert(0, parse_quote!('variation));
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+    let item_trait = Ident::new(&format!("{}IteratorItem", name), Span::call_site());
+    let ext_trait = Ident::new(&format!("{}IteratorExt", name), Span::call_site());
+    let item_doc = format!(
+        "Items of an iterator that [`{}`] can filter, either [`{}`] itself or references to it.",
+        ext_trait, name
+    );
+    let ext_doc = format!("Filters iterators of [`{}`] down to the inner values of one variant.", name);
+
+    let mut item_methods = TokenStream::new();
+    let mut owned_items = TokenStream::new();
+    let mut ref_items = TokenStream::new();
+    let mut adapters = TokenStream::new();
+
+    for variant in variants.iter().filter(|variant| variant.fields.iter().count() > 0) {
+        let assoc = &variant.ident;
+        let filter = stem_ident(variant, case);
+        let adapter = ident(&pluralize(&method_stem(variant, case)));
+        let inner = inner_type(variant);
+        let inner_ref = inner_ref_type(variant, quote!(&'variation));
+        let Conversion { pattern, ref_pattern, value, .. } = Conversion::new(name, variant);
+        let adapter_doc = format!(
+            "Yields the inner values of the items holding the `{}` variant, skipping all others.",
+            variant.ident
+        );
+
+        item_methods.extend(quote! {
+            type #assoc;
+
+            fn #filter(self) -> Option<Self::#assoc>;
+        });
+
+        owned_items.extend(quote! {
+            type #assoc = #inner;
+
+            #[allow(unreachable_patterns)]
+            fn #filter(self) -> Option<#inner> {
+                match self {
+                    #pattern => Some(#value),
+                    _ => None,
+                }
+            }
+        });
+
+        ref_items.extend(quote! {
+            type #assoc = #inner_ref;
+
+            #[allow(unreachable_patterns)]
+            fn #filter(self) -> Option<#inner_ref> {
+                match self {
+                    #ref_pattern => Some(#value),
+                    _ => None,
+                }
+            }
+        });
+
+        adapters.extend(quote! {
+            #[doc = #adapter_doc]
+            fn #adapter(self) -> ::std::iter::FilterMap<Self, fn(Self::Item) -> Option<<Self::Item as #item_trait>::#assoc>>
+            where
+                Self::Item: #item_trait,
+            {
+                self.filter_map(<Self::Item as #item_trait>::#filter as fn(_) -> _)
+            }
+        });
+    }
+
+    quote! {
+        #[doc = #item_doc]
+        #vis trait #item_trait: Sized {
+            #item_methods
+        }
+
+        impl #impl_generics #item_trait for #name #ty_generics #where_clause {
+            #owned_items
+        }
+
+        impl #ref_impl_generics #item_trait for &'variation #name #ty_generics #where_clause {
+            #ref_items
+        }
+
+        #[doc = #ext_doc]
+        #vis trait #ext_trait: Iterator + Sized {
+            #adapters
+        }
+
+        impl<I: Iterator> #ext_trait for I {}
+    }
+}
+
+/// A best effort English plural of a snake case name, used to name the
+/// adapters after the variants they yield.
+pub(crate) fn pluralize(snake_case: &str) -> String {
+    let consonant_y = snake_case.ends_with('y')
+        && !snake_case[..snake_case.len() - 1].ends_with(|c| "aeiou".contains(c));
+
+    if consonant_y {
+        format!("{}ies", &snake_case[..snake_case.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh"].iter().any(|end| snake_case.ends_with(end)) {
+        format!("{}es", snake_case)
+    } else {
+        format!("{}s", snake_case)
+    }
+}
@@ -0,0 +1,295 @@
+//! The `{Enum}Kind` enum for `#[variation(kind)]`, a fieldless copy of the
+//! enum naming its variants.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::attr::{ContainerAttrs, VariantAttrs};
+use crate::names::{kind_variant, variant_str, Case};
+use crate::placeholder::taken_arm;
+use crate::{companion_derive, stem_ident};
+
+pub(crate) fn kind_ident(name: &Ident) -> Ident {
+    Ident::new(&format!("{}Kind", name), Span::call_site())
+}
+
+/// Generates the `{Enum}Kind` enum.
+pub(crate) fn kind_enum(ast: &DeriveInput, variants: &[&Variant]) -> TokenStream {
+    let vis = &ast.vis;
+    let kind = kind_ident(&ast.ident);
+    let doc = format!("The variants of [`{}`], without their inner values.", ast.ident);
+    let variant_names = variants.iter().map(|variant| kind_variant(variant));
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #vis enum #kind {
+            #(#variant_names,)*
+        }
+    }
+}
+
+/// Implements `variation_kind::VariantKind` with the `kind` and
+/// `variant_name` methods, for `#[variation(kind(variant_kind))]`.
+pub(crate) fn impl_variant_kind(ast: &DeriveInput, variants: &[&Variant], container: &ContainerAttrs) -> TokenStream {
+    let name = &ast.ident;
+    let kind = kind_ident(name);
+    let count = variants.len();
+    let krate = container.kind_crate();
+    let mut generics = ast.generics.clone();
+    generics.make_where_clause().predicates.extend(container.bounds.iter().cloned());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #krate::VariantKind for #name #ty_generics #where_clause {
+            type Kind = #kind;
+
+            const VARIANT_COUNT: usize = #count;
+
+            fn kind(&self) -> #kind {
+                #name::kind(self)
+            }
+
+            fn variant_name(&self) -> &'static str {
+                #name::variant_name(self)
+            }
+        }
+    }
+}
+
+/// Implements `Serialize` and `Deserialize` of `serde` for the `{Enum}Kind`
+/// enum, as the name of the variant or its `#[variation(str = "...")]`, for
+/// `#[variation(kind(serde))]`.
+pub(crate) fn impl_serde(name: &Ident, variants: &[(&Variant, VariantAttrs)]) -> TokenStream {
+    let kind = kind_ident(name);
+    let expecting = format!("a variant of `{}`", name);
+    let mut serialize_arms = TokenStream::new();
+    let mut deserialize_arms = TokenStream::new();
+    let mut strings = Vec::new();
+
+    for (variant, attrs) in variants {
+        let kind_variant = kind_variant(variant);
+        let string = match attrs.str {
+            Some(ref string) => string.value(),
+            None => variant_str(variant),
+        };
+
+        serialize_arms.extend(quote!(#kind::#kind_variant => #string,));
+        deserialize_arms.extend(quote!(#string => ::std::result::Result::Ok(#kind::#kind_variant),));
+        strings.push(string);
+    }
+
+    let serialize = quote! {
+        impl ::serde::Serialize for #kind {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(match self {
+                    #serialize_arms
+                })
+            }
+        }
+    };
+    let visitor = quote! {
+        struct Visitor;
+
+        impl<'de> ::serde::de::Visitor<'de> for Visitor {
+            type Value = #kind;
+
+            fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                formatter.write_str(#expecting)
+            }
+
+            fn visit_str<E: ::serde::de::Error>(self, value: &str) -> ::std::result::Result<#kind, E> {
+                match value {
+                    #deserialize_arms
+                    _ => ::std::result::Result::Err(E::unknown_variant(value, &[#(#strings),*])),
+                }
+            }
+        }
+    };
+
+    quote! {
+        #serialize
+
+        impl<'de> ::serde::Deserialize<'de> for #kind {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+                #visitor
+
+                deserializer.deserialize_str(Visitor)
+            }
+        }
+    }
+}
+
+/// Generates `kind`, and `group_by_kind` if `group_by_kind` is set.
+pub(crate) fn methods(
+    name: &Ident,
+    variants: &[&Variant],
+    placeholder: Option<&Variant>,
+    group_by_kind: bool,
+) -> TokenStream {
+    let kind = kind_ident(name);
+    let arms = variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        let kind_variant = kind_variant(variant);
+        quote!(#name::#variant_name { .. } => #kind::#kind_variant,)
+    });
+    let taken = taken_arm(name, placeholder, "kind");
+
+    let group_by_kind = if group_by_kind {
+        quote! {
+            /// Groups `values` by their kind, keeping the order they were in
+            /// within each group.
+            pub fn group_by_kind<I: IntoIterator<Item = Self>>(values: I) -> ::std::collections::HashMap<#kind, Vec<Self>> {
+                let mut groups = ::std::collections::HashMap::new();
+
+                for value in values {
+                    groups.entry(value.kind()).or_insert_with(Vec::new).push(value);
+                }
+
+                groups
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        /// Returns the kind of variant the enum holds.
+        pub fn kind(&self) -> #kind {
+            match self {
+                #(#arms)*
+                #taken
+            }
+        }
+
+        #group_by_kind
+    }
+}
+
+/// The methods of the `{Enum}KindCounts` struct other than the counts of the
+/// variants, which a variant's count can't be named.
+const COUNTS_METHODS: &[&str] = &["from_iter", "total"];
+
+/// Generates the `{Enum}KindCounts` struct, counting how many values of each
+/// variant a collection of the enum has, deriving the traits of
+/// `#[variation(companion_derive(...))]` along with its own.
+pub(crate) fn counts_struct(
+    ast: &DeriveInput,
+    variants: &[&Variant],
+    placeholder: Option<&Variant>,
+    case: Case,
+    derives: &[Path],
+) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let counts = Ident::new(&format!("{}KindCounts", name), Span::call_site());
+    let derive = companion_derive(&["Clone", "Copy", "Debug", "Default", "PartialEq", "Eq"], derives);
+    let doc = format!("The number of values of each variant in a collection of [`{}`].", name);
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+
+    let mut generics = ast.generics.clone();
+    generics.params.insert(0, parse_quote!('variation));
+    let lifetimes = ast.generics.lifetimes().map(|param| &param.lifetime);
+    let type_params = ast.generics.type_params().map(|param| &param.ident);
+    let where_clause = generics.make_where_clause();
+    for lifetime in lifetimes {
+        where_clause.predicates.push(parse_quote!(#lifetime: 'variation));
+    }
+    for param in type_params {
+        where_clause.predicates.push(parse_quote!(#param: 'variation));
+    }
+    let mut method_generics = generics.clone();
+    method_generics
+        .params
+        .push(parse_quote!(I: IntoIterator<Item = &'variation #name #ty_generics>));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (method_generics, _, method_where_clause) = method_generics.split_for_impl();
+
+    let fields = variants.iter().map(|variant| stem_ident(variant, case)).collect::<Vec<_>>();
+    let fields = &fields;
+
+    for (variant, field) in variants.iter().zip(fields) {
+        if COUNTS_METHODS.iter().any(|method| field == method) {
+            return Err(Error::new(
+                variant.ident.span(),
+                format!(
+                    "the count of `{}` would be called `{}` like the method of `{}`, rename the variant with \
+                     `#[variation(rename = \"...\")]`",
+                    variant.ident, field, counts
+                ),
+            ));
+        }
+    }
+
+    let accessor_docs = variants
+        .iter()
+        .map(|variant| format!("The number of `{}` values.", variant.ident));
+    let arms = variants.iter().zip(fields).map(|(variant, field)| {
+        let variant_name = &variant.ident;
+        quote!(#name::#variant_name { .. } => counts.#field += 1,)
+    });
+    // Taken values aren't of any variant.
+    let taken = placeholder.map(|placeholder| {
+        let placeholder = &placeholder.ident;
+        quote!(#name::#placeholder => {})
+    });
+
+    Ok(quote! {
+        #[doc = #doc]
+        #derive
+        #vis struct #counts {
+            #(#fields: usize,)*
+        }
+
+        impl #counts {
+            /// Counts the values of each variant in `values`.
+            #[allow(clippy::should_implement_trait)]
+            pub fn from_iter #method_generics(values: I) -> Self #method_where_clause {
+                let mut counts = Self::default();
+
+                for value in values {
+                    match value {
+                        #(#arms)*
+                        #taken
+                    }
+                }
+
+                counts
+            }
+
+            #(
+                #[doc = #accessor_docs]
+                pub fn #fields(&self) -> usize {
+                    self.#fields
+                }
+            )*
+
+            /// The number of values of all variants.
+            pub fn total(&self) -> usize {
+                0 #(+ self.#fields)*
+            }
+        }
+
+        impl #impl_generics ::std::iter::FromIterator<&'variation #name #ty_generics> for #counts #where_clause {
+            fn from_iter<I: IntoIterator<Item = &'variation #name #ty_generics>>(values: I) -> Self {
+                #counts::from_iter(values)
+            }
+        }
+
+        impl ::std::ops::Add for #counts {
+            type Output = Self;
+
+            fn add(mut self, other: Self) -> Self {
+                self += other;
+                self
+            }
+        }
+
+        impl ::std::ops::AddAssign for #counts {
+            fn add_assign(&mut self, other: Self) {
+                #(self.#fields += other.#fields;)*
+            }
+        }
+    })
+}
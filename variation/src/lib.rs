@@ -2,9 +2,23 @@
 //! A procedural macro to generate enum to variant conversion methods.
 //!
 //! ## Methods generated
+//! The variants of the enum can be unit or tuple variants. Variants with
+//! named fields are an error unless they're skipped with
+//! `#[variation(skip)]`, as the methods match on the fields by position.
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Shape {
+//!     Point,
+//!     Circle { radius: u32 },
+//! }
+//! ```
 //!
 //! #### `is_*` methods
-//! An `is_variant` method is generated for each for variant in an enum.
+//! An `is_variant` method is generated for each for variant in an enum. It
+//! matches only on the variant, without touching or bounding the fields.
 //!
 //! ```rust
 //! use variation::Variation;
@@ -49,10 +63,35 @@
 //! }
 //! ```
 //!
+//! The inner types can name the enum as `Self`, such as `Box<Self>` in a
+//! recursive enum. Where they're copied into items of their own, such as the
+//! `{Enum}Ref` enum, the visitor trait or the subsets, `Self` is replaced by
+//! the enum along with its generics.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, Variation)]
+//! #[variation(ref_enum)]
+//! enum Expr {
+//!     Number(i64),
+//!     Neg(Box<Self>),
+//! }
+//!
+//! fn main() {
+//!     let expr = Expr::Neg(Box::new(Expr::Number(1)));
+//!
+//!     assert!(matches!(expr.as_ref(), ExprRef::Neg(inner) if inner.is_number()));
+//! }
+//! ```
+//!
 //! #### `into_*` methods
 //! Variants that have one or more inner types have an `into` method, allowing you
 //! to attempt to convert a enum into its inner values. This method will panic when
-//! called on a variant that does not match the method.
+//! called on a variant that does not match the method, naming the variant it
+//! expected and the one it found. All of them panic through one `#[cold]`
+//! function generated for the enum, keeping the panics out of their hot
+//! paths.
 //!
 //! ```ignore
 //! use variation::Variation;
@@ -76,182 +115,4059 @@
 //!
 //! }
 //! ```
-
-extern crate proc_macro;
-
-use heck::SnakeCase;
-use proc_macro2::{Ident, Span, TokenStream};
-use quote::{quote, ToTokens};
-use syn::*;
-
-#[proc_macro_derive(Variation)]
-pub fn variation_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let ast = syn::parse(input).unwrap();
-
-    impl_variation(&ast)
-}
-
-fn impl_variation(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
-    let name = &ast.ident;
-    let mut implementation = TokenStream::new();
-
-    let data = match ast.data {
-        Data::Enum(ref s) => s,
-        _ => {
-            // name.span()
-            //     .unstable()
-            //     .error("`#[derive(Variation)]` is only available for structs")
-            //     .emit();
-
-            // return TokenStream::new()
-            panic!("`#[derive(Variation)]` is only available for enums")
-        }
-    };
-
-    for variant in &data.variants {
-        let variant_name = &variant.ident;
-        let snake_case = variant_name.to_string().to_snake_case();
-        let is_fn = Ident::new(&format!("is_{}", snake_case), Span::call_site());
-        let as_fn = Ident::new(&format!("as_{}", snake_case), Span::call_site());
-        let as_mut_fn = Ident::new(&format!("as_{}_mut", snake_case), Span::call_site());
-        let into_fn = Ident::new(&format!("into_{}", snake_case), Span::call_site());
-        let field_count = variant.fields.iter().count();
-        let ignoring_fields = if field_count > 0 {
-            let fields = vec![(); field_count].into_iter().fold(TokenStream::new(), |mut acc, _| {
-                acc.extend(quote!(_,));
-                acc
-            });
-
-            quote![(#fields)]
-        } else {
-            quote!()
-        };
-
-        let value_fields = generate_ident_list_pattern(field_count, false, false);
-        let ref_fields = generate_ident_list_pattern(field_count, true, false);
-        let ref_mut_fields = generate_ident_list_pattern(field_count, true, true);
-
-        let return_by_value = match field_count {
-            0 => quote!(),
-            1 => variant.fields.iter().next().unwrap().into_token_stream(),
-            _ => {
-                let type_list = variant.fields.iter().fold(TokenStream::new(), |mut acc, f| {
-                    let ty = &f.ty;
-                    acc.extend(quote!(#ty,));
-                    acc
-                });
-
-                quote![(#type_list)]
-            }
-        };
-
-        let return_by_ref = match field_count {
-            0 => quote!(),
-            1 => {
-                let ty = variant.fields.iter().next().unwrap();
-                quote!(&#ty)
-            },
-            _ => {
-                let type_list = variant.fields.iter().fold(TokenStream::new(), |mut acc, f| {
-                    let ty = &f.ty;
-                    acc.extend(quote!(&#ty,));
-                    acc
-                });
-
-                quote![(#type_list)]
-            }
-        };
-
-        let return_by_ref_mut = match field_count {
-            0 => quote!(),
-            1 => {
-                let ty = variant.fields.iter().next().unwrap();
-                quote!(&mut #ty)
-            },
-            _ => {
-                let type_list = variant.fields.iter().fold(TokenStream::new(), |mut acc, f| {
-                    let ty = &f.ty;
-                    acc.extend(quote!(&mut #ty,));
-                    acc
-                });
-
-                quote![(#type_list)]
-            }
-        };
-
-        let return_value = match field_count {
-            0 => quote!(),
-            1 => Ident::new("v0", Span::call_site()).into_token_stream(),
-            _ => value_fields.clone()
-        };
-
-        implementation.extend(quote! {
-            pub fn #is_fn(&self) -> bool {
-                match self {
-                    #name::#variant_name#ignoring_fields => true,
-                    _ => false,
-                }
-            }
-        });
-
-        if field_count > 0 {
-            implementation.extend(quote! {
-                pub fn #as_fn(&self) -> Option<#return_by_ref> {
-                    match self {
-                        #name::#variant_name#ref_fields => Some(#return_value),
-                        _ => None,
-                    }
-                }
-
-                pub fn #as_mut_fn(&mut self) -> Option<#return_by_ref_mut> {
-                    match self {
-                        #name::#variant_name#ref_mut_fields => Some(#return_value),
-                        _ => None,
-                    }
-                }
-
-                /// Consumes the enum and returns the inner type.
-                /// # Panics
-                /// When this method is called on the wrong enum variant.
-                pub fn #into_fn(self) -> #return_by_value {
-                    match self {
-                        #name::#variant_name#value_fields => #return_value,
-                        _ => panic!("`into_*` called on the wrong variant."),
-                    }
-                }
-            })
-        }
-    }
-
-    let gen = quote! {
-        impl #name {
-            #implementation
-        }
-    };
-
-    gen.into()
-}
-
-fn generate_ident_list_pattern(count: usize, refed: bool, mutable: bool) -> TokenStream {
-    if count > 0 {
-        let fields = (0..).take(count).fold(TokenStream::new(), |mut acc, i| {
-            let mut pattern = TokenStream::new();
-            let ident = Ident::new(&format!("v{}", i), Span::call_site());
-
-            if refed {
-                pattern.extend(quote!(ref));
-            }
-
-            if mutable {
-                pattern.extend(quote!(mut));
-            }
-
-            acc.extend(quote!(#pattern #ident,));
-            acc
-        });
-
-        quote![(#fields)]
-    } else {
-        quote!()
-    }
+//!
+//! `#[variation(panic_msg = "...")]` on a variant replaces the first part of
+//! the message its `into_*` methods panic with, which is followed by the name
+//! of the variant the enum holds, such as "expected an integer, found `Real`".
+//!
+//! ```should_panic
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Literal {
+//!     #[variation(panic_msg = "expected an integer")]
+//!     Integer(i64),
+//!     Real(f64),
+//! }
+//!
+//! fn main() {
+//!     // Panics with "expected an integer, found `Real`"
+//!     Literal::Real(1.5).into_integer();
+//! }
+//! ```
+//!
+//! Naming the variant the enum holds formats the message when `into_*`
+//! panics. `#[variation(static_panic)]` on the enum leaves it out, so the
+//! methods panic with a literal fixed when the enum is derived, such as
+//! "`into_integer` called on the wrong variant of `Literal`, expected
+//! `Integer`", and don't pull in any of the formatting of `core::fmt`. The
+//! messages of `panic_msg = "..."` are used as they are, and can't hold `{`
+//! or `}` with it.
+//!
+//! ```should_panic
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(static_panic)]
+//! enum Literal {
+//!     Integer(i64),
+//!     Real(f64),
+//! }
+//!
+//! fn main() {
+//!     // Panics with "`into_integer` called on the wrong variant of
+//!     // `Literal`, expected `Integer`"
+//!     Literal::Real(1.5).into_integer();
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(static_panic)]
+//! enum Literal {
+//!     #[variation(panic_msg = "expected {integer}")]
+//!     Integer(i64),
+//!     Real(f64),
+//! }
+//! #
+//! # fn main() {}
+//! ```
+//!
+//! Large enums that are kept in a `Box` can be marked with
+//! `#[variation(boxed)]` to get `into_*_boxed` methods as well, taking a
+//! `Box<Self>` and moving the inner values straight out of it, without first
+//! moving the whole enum onto the stack.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(boxed)]
+//! enum Node {
+//!     Leaf(i32),
+//!     Branch([u8; 512], Vec<Node>),
+//! }
+//!
+//! fn main() {
+//!     let node = Box::new(Node::Leaf(5));
+//!
+//!     assert_eq!(5, node.into_leaf_boxed());
+//! }
+//! ```
+//!
+//! `#[variation(into_mode = "option")]` on the enum makes every `into_*`
+//! method return an `Option` of the inner values instead of panicking, which
+//! is `None` for the other variants. With `#[variation(into_mode = "result")]`
+//! they return a `Result` instead, handing the enum itself back in `Err`, so
+//! it can still be used. `into_*_boxed` and the aliases of `into_*` change
+//! the same way, with `into_*_boxed` handing back the box.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(into_mode = "result")]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Ok(5), Type::Integer(5).into_integer());
+//!     assert_eq!(Err(Type::Unit), Type::Unit.into_integer());
+//! }
+//! ```
+//!
+//! A variant can set its own mode with `#[variation(into_mode = "panic")]`,
+//! `"option"` or `"result"`, which takes precedence over the mode of the enum
+//! for its `into_*` methods only. The other variants keep the mode of the
+//! enum, or panic when it doesn't set one.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(into_mode = "option")]
+//! enum Type {
+//!     Integer(i32),
+//!     #[variation(into_mode = "panic")]
+//!     Checked(u32),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Some(5), Type::Integer(5).into_integer());
+//!     assert_eq!(3, Type::Checked(3).into_checked());
+//! }
+//! ```
+//!
+//! A mode other than `"panic"`, `"option"` or `"result"` is an error.
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Type {
+//!     #[variation(into_mode = "optoin")]
+//!     Integer(i32),
+//! }
+//! ```
+//!
+//! Crates that rule out panicking code can mark the enum with
+//! `#[variation(no_panic)]`. Variants whose `into_*` would panic don't get
+//! one, as if `into` was left out of `#[variation(methods(...))]`, while those
+//! with `into_mode = "option"` or `"result"` from the enum or the variant keep
+//! theirs. Asking for a panicking `into_*` with `into_mode = "panic"` or
+//! `panic_msg = "..."` is an error.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(no_panic)]
+//! enum Type {
+//!     Integer(i32),
+//!     #[variation(into_mode = "option")]
+//!     Checked(u32),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Some(&5), Type::Integer(5).as_integer());
+//!     assert_eq!(Some(3), Type::Checked(3).into_checked());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(no_panic)]
+//! enum Type {
+//!     #[variation(panic_msg = "expected an integer")]
+//!     Integer(i32),
+//! }
+//! #
+//! # fn main() {}
+//! ```
+//!
+//! #### `take_*` methods
+//! Moving the inner values out of a borrowed enum needs something to leave
+//! in their place. `#[variation::placeholder]`, put before
+//! `#[derive(Variation)]`, adds a hidden `__Taken` variant to the enum for
+//! that, and gets every variant with fields a `take_*` method, which moves
+//! the inner values out and leaves a taken value behind, or returns `None`
+//! for other variants. Changing the enum this way is why it's an attribute of
+//! its own.
+//!
+//! A taken value isn't any variant to the other methods: `is_*` returns
+//! `false`, `as_*` returns `None`, and it's left out of `{Enum}Kind`,
+//! `{Enum}KindCounts` and `VARIANTS`. Only `variant_name` and `kind` panic
+//! for it, as they have nothing to return. The options that would have to
+//! handle taken values otherwise, such as `#[variation(fold)]`, aren't
+//! available along with the placeholder.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[variation::placeholder]
+//! #[derive(Variation)]
+//! enum Slot {
+//!     Empty,
+//!     Filled(String),
+//! }
+//!
+//! fn main() {
+//!     let mut slot = Slot::Filled("a".to_owned());
+//!
+//!     assert_eq!(Some("a".to_owned()), slot.take_filled());
+//!     assert_eq!(None, slot.take_filled());
+//!     assert!(!slot.is_empty() && !slot.is_filled());
+//! }
+//! ```
+//!
+//! #### Visibility
+//! The `is_*`, `as_*` and `into_*` methods are public unless their families
+//! are given a different visibility with `#[variation(vis(...))]`, such as
+//! `#[variation(vis(as_mut = "pub(crate)", into = "pub(crate)"))]` to keep
+//! changing and taking apart the enum to the crate. The families are `is`,
+//! `as`, `as_mut` and `into`, which includes `into_*_boxed`.
+//!
+//! ```compile_fail
+//! mod shapes {
+//!     use variation::Variation;
+//!
+//!     #[derive(Variation)]
+//!     #[variation(vis(into = "pub(self)"))]
+//!     pub enum Shape {
+//!         Circle(u32),
+//!     }
+//! }
+//!
+//! fn main() {
+//!     shapes::Shape::Circle(1).into_circle();
+//! }
+//! ```
+//!
+//! #### Extra bounds
+//! `#[variation(bound = "T: Clone + Send")]` adds where predicates the enum
+//! itself doesn't have to the impl of the generated methods, and
+//! `#[variation(bound(into = "T: Send"))]` to only the methods of one family,
+//! with the same families as `vis`.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(bound(into = "T: Send"))]
+//! enum Job<T> {
+//!     Queued(T),
+//! }
+//!
+//! fn main() {
+//!     assert!(Job::Queued(std::rc::Rc::new(1)).is_queued());
+//!     assert_eq!(1, Job::Queued(1).into_queued());
+//! }
+//! ```
+//!
+//! #### Accessor traits
+//! `#[variation(trait = "...")]` generates the `is_*`, `as_*`, `as_*_mut`,
+//! `into_*` and `into_*_boxed` methods in a trait of that name implemented
+//! for the enum, in place of the inherent impl, to write code that is generic
+//! over several enums. The trait has the generics of the enum and its
+//! visibility, unless set with `#[variation(trait_vis = "...")]`. The aliases
+//! of the methods go in the trait with them, while the other methods stay in
+//! the inherent impl.
+//!
+//! The consuming methods keep the trait from being used as a trait object,
+//! so `#[variation(trait_object_safe)]` leaves them out of it. Since trait
+//! methods have the visibility of the trait and can't be `const fn`, the
+//! trait can't be combined with `#[variation(vis(...))]` or
+//! `#[variation(const_fn)]`.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(trait = "ValueExt", trait_object_safe)]
+//! enum Value {
+//!     Integer(i64),
+//!     Null,
+//! }
+//!
+//! fn main() {
+//!     let value: &dyn ValueExt = &Value::Integer(1);
+//!
+//!     assert_eq!(Some(&1), value.as_integer());
+//!     assert!(!value.is_null());
+//! }
+//! ```
+//!
+//! #### Remote enums
+//! Enums of other crates can't be marked with `#[derive(Variation)]`, so
+//! `variation_remote!` takes a mirror definition of one instead, with the
+//! path of the actual enum in `#[variation(remote = "...")]`. It generates
+//! the accessor methods in a `{Enum}VariationExt` trait implemented for that
+//! enum, or in the trait named with `#[variation(trait = "...")]`. The mirror
+//! definition has to list the variants with the types of the actual enum,
+//! differences show up as type errors in the generated methods.
+//!
+//! Only the options of the accessor methods can be set, as nothing else is
+//! generated. `#[variation(const_fn)]` and `#[variation(vis(...))]` aren't
+//! available in a trait, and `#[variation(panic_msg = "...")]` needs the
+//! `variant_name` method of the enum.
+//!
+//! ```rust
+//! use std::net::{Ipv4Addr, Ipv6Addr};
+//!
+//! use variation::variation_remote;
+//!
+//! variation_remote! {
+//!     #[variation(remote = "std::net::IpAddr")]
+//!     enum IpAddr {
+//!         V4(Ipv4Addr),
+//!         V6(Ipv6Addr),
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let localhost = std::net::IpAddr::V4(Ipv4Addr::LOCALHOST);
+//!
+//!     assert!(localhost.is_v4());
+//!     assert_eq!(Some(&Ipv4Addr::LOCALHOST), localhost.as_v4());
+//! }
+//! ```
+//!
+//! #### Augmenting enums
+//! Enums of the crate that can't be marked with `#[derive(Variation)]`, such
+//! as those defined by other macros, can be restated in `augment!` instead,
+//! named with their path. It generates everything the derive would for the
+//! existing enum, without defining it again.
+//!
+//! Enums named with a path only get methods and impls, as the code is
+//! generated in a block importing the enum, so the options generating items
+//! next to it, such as `#[variation(kind)]` or `#[variation(trait = "...")]`,
+//! need the enum named where it's in scope.
+//!
+//! ```rust
+//! use variation::augment;
+//!
+//! macro_rules! value_enum {
+//!     ($name:ident) => {
+//!         pub enum $name {
+//!             Integer(i64),
+//!             Null,
+//!         }
+//!     };
+//! }
+//!
+//! mod values {
+//!     value_enum!(Value);
+//! }
+//!
+//! augment! {
+//!     enum values::Value {
+//!         Integer(i64),
+//!         Null,
+//!     }
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Some(&1), values::Value::Integer(1).as_integer());
+//!     assert!(values::Value::Null.is_null());
+//! }
+//! ```
+//!
+//! Fields restated in `augment!` or `variation_remote!` can keep their
+//! `#[cfg]` attributes, which the compiler doesn't apply before these macros
+//! like it does for the derive. Everything is then generated once for each
+//! combination of the predicates, up to four distinct ones, under a
+//! `#[cfg]` selecting that combination, so the methods match the fields of
+//! the configuration they're compiled in.
+//!
+//! #### Newtype structs
+//! A tuple struct wrapping an enum can derive `Variation` too, to get the
+//! `is_*`, `as_*`, `as_*_mut` and `into_*` methods of the enum forwarding to
+//! the wrapped value. As the derive can't see the enum, its variants are
+//! listed with `#[variation(variants(...))]`, along with options naming them
+//! like those set on the enum, and the families, names and visibilities
+//! follow the same rules. With `into_mode = "result"`, the wrapper is handed
+//! back for the wrong variant.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Body {
+//!     Text(String),
+//!     Empty,
+//! }
+//!
+//! #[derive(Variation)]
+//! #[variation(variants(Text(String), Empty))]
+//! struct Response(Body);
+//!
+//! fn main() {
+//!     let response = Response(Body::Text("ok".to_owned()));
+//!
+//!     assert!(!response.is_empty());
+//!     assert_eq!(Some(&"ok".to_owned()), response.as_text());
+//! }
+//! ```
+//!
+//! #### Flattened variants
+//! A variant wrapping another enum can forward the `is_*`, `as_*` and
+//! `as_*_mut` methods of that enum with `#[variation(flatten(...))]`, which
+//! lists the variants of the wrapped enum like `variants(...)` does for
+//! newtype structs. The forwarded methods are named with the options of the
+//! outer enum, return `false` or `None` for its other variants, and names
+//! clashing with its own methods are an error. `into_*` isn't forwarded, as
+//! it can't hand back the outer enum for the wrong inner variant.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Literal {
+//!     Text(String),
+//!     Number(i64),
+//! }
+//!
+//! #[derive(Variation)]
+//! enum Expr {
+//!     Name(String),
+//!     #[variation(flatten(Text(String), Number(i64)))]
+//!     Literal(Literal),
+//! }
+//!
+//! fn main() {
+//!     let expr = Expr::Literal(Literal::Number(1));
+//!
+//!     assert!(expr.is_literal());
+//!     assert!(expr.is_number());
+//!     assert_eq!(Some(&1), expr.as_number());
+//!     assert_eq!(None, Expr::Name("x".to_owned()).as_text());
+//! }
+//! ```
+//!
+//! #### `const fn` methods
+//! `#[variation(const_fn)]` makes the `is_*` and `as_*` methods, their
+//! aliases and `variant_name` `const fn`s, so they can be used in constants
+//! and static assertions. `as_*_mut` and `into_*` stay regular methods, as
+//! taking `&mut self` in a `const fn` needs a newer compiler and `into_*`
+//! drops the rest of the enum, which constant evaluation can't do for every
+//! type.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(const_fn)]
+//! enum Mode {
+//!     Fast(u8),
+//!     Slow,
+//! }
+//!
+//! const DEFAULT: Mode = Mode::Fast(2);
+//! const IS_FAST: bool = DEFAULT.is_fast();
+//!
+//! fn main() {
+//!     assert!(IS_FAST);
+//! }
+//! ```
+//!
+//! #### `#[must_use]` methods
+//! `#[variation(must_use)]` marks the `is_*`, `as_*`, `as_*_mut`, `into_*`
+//! and `into_*_boxed` methods, their aliases and `variant_name` with
+//! `#[must_use]`, so calling one without using its result is warned about.
+//!
+//! ```compile_fail
+//! #![deny(unused_must_use)]
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(must_use)]
+//! enum Type {
+//!     Integer(i32),
+//!     Unit,
+//! }
+//!
+//! fn main() {
+//!     Type::Integer(1).as_integer();
+//! }
+//! ```
+//!
+//! #### Inlining
+//! `#[variation(inline)]`, `#[variation(inline(always))]` or
+//! `#[variation(inline(never))]` on the enum gives the `is_*`, `as_*`,
+//! `as_*_mut`, `into_*` and `into_*_boxed` methods and their aliases the
+//! matching `#[inline]` attribute. The same attribute on a variant sets the
+//! hint of its own methods, taking precedence over that of the enum. Without
+//! either, the methods get a plain `#[inline]`, so they can be inlined into
+//! other crates without LTO, unless `#[variation(attr(...))]` gives them an
+//! `#[inline]` attribute of their own.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(inline)]
+//! enum Opcode {
+//!     Push(u8),
+//!     #[variation(inline(never))]
+//!     Debug(String),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Some(&1), Opcode::Push(1).as_push());
+//!     assert!(Opcode::Debug(String::new()).is_debug());
+//! }
+//! ```
+//!
+//! #### Conditional methods
+//! `#[variation(cfg(...))]` on the enum puts `#[cfg(...)]` with the given
+//! predicate on everything that is generated, such as
+//! `#[variation(cfg(feature = "introspection"))]` to only generate the
+//! methods with that feature of the crate. Any predicate `#[cfg]` accepts can
+//! be used.
+//!
+//! The predicates of single families are given as strings with
+//! `#[variation(cfg(is = "...", as = "...", as_mut = "...", into = "..."))]`,
+//! which applies to the methods of the family, their aliases and
+//! `into_*_boxed` for `into`.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(cfg(as_mut = "feature = \"editing\""))]
+//! enum Value {
+//!     Integer(i64),
+//!     Null,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Some(&1), Value::Integer(1).as_integer());
+//! }
+//! ```
+//!
+//! #### Extra attributes
+//! `#[variation(attr(...))]` copies an attribute as written onto generated
+//! code, for attributes this crate has no option of its own for. On the enum,
+//! such as `#[variation(attr(allow(clippy::missing_const_for_fn)))]`, it goes
+//! on every generated item, and with strings like
+//! `#[variation(attr(as_mut = "cfg_attr(docsrs, doc(cfg(feature = \"x\")))"))]`
+//! on the methods of the family and their aliases. On a variant it goes on
+//! the methods of the variant and their aliases. It can be given several
+//! times, and the methods of a trait from `#[variation(trait = "...")]` get
+//! them on their implementation.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(attr(allow(clippy::all)))]
+//! enum Value {
+//!     #[variation(attr(allow(unused)))]
+//!     Integer(i64),
+//!     Null,
+//! }
+//!
+//! fn main() {
+//!     assert!(Value::Null.is_null());
+//! }
+//! ```
+//!
+//! `#[variation(doc_hidden)]` on the enum is a shorthand for
+//! `#[variation(attr(doc(hidden)))]`, keeping everything generated out of the
+//! documentation while it can still be used, and
+//! `#[variation(doc_hidden(is, as))]` does the same for the methods of the
+//! listed families. Together with `#[variation(vis(...))]` this keeps helpers
+//! that are only meant to be used internally out of the documentation of a
+//! public type.
+//!
+//! #### Prefixes
+//! `#[variation(prefix(as = "get_", as_mut = "get_mut_", into = "take_"))]`
+//! replaces the prefixes of the method names, with the same families as
+//! `vis`, to follow the naming of a code base. A prefix given for `as_mut`
+//! replaces the `_mut` suffix as well. Prefixes can be empty, naming the
+//! methods of a family after the variants alone, as long as no two methods
+//! end up with the same name.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(prefix(as = "get_", as_mut = "get_mut_", into = "take_"))]
+//! enum Slot {
+//!     Filled(String),
+//!     Empty,
+//! }
+//!
+//! fn main() {
+//!     let mut slot = Slot::Filled("a".to_owned());
+//!
+//!     slot.get_mut_filled().unwrap().push('b');
+//!     assert_eq!(Some(&"ab".to_owned()), slot.get_filled());
+//!     assert_eq!("ab", slot.take_filled());
+//! }
+//! ```
+//!
+//! #### Renamed variants
+//! A variant can be given its former names with `#[variation(alias = "...")]`,
+//! as often as needed, to keep the `is_*`, `as_*` and `into_*` methods named
+//! after them around while code using them is updated. They forward to the
+//! methods named after the variant and are deprecated.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Shape {
+//!     #[variation(alias = "square")]
+//!     Rectangle(u32, u32),
+//! }
+//!
+//! #[allow(deprecated)]
+//! fn main() {
+//!     assert_eq!(Some((&2, &3)), Shape::Rectangle(2, 3).as_square());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! #![deny(deprecated)]
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Shape {
+//!     #[variation(alias = "square")]
+//!     Rectangle(u32, u32),
+//! }
+//!
+//! fn main() {
+//!     Shape::Rectangle(2, 3).is_square();
+//! }
+//! ```
+//!
+//! An alias has to be a valid name for each of the methods, so an empty
+//! alias is an error.
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Shape {
+//!     #[variation(alias = "")]
+//!     Rectangle(u32, u32),
+//! }
+//! ```
+//!
+//! #### Skipping variants
+//! A variant marked with `#[variation(skip)]` gets no methods of its own: no
+//! `is_*`, `as_*` or `into_*` methods, no `drain_*` method and no adapters or
+//! accessors from `#[variation(iterator_ext)]` and `#[variation(option_ext)]`.
+//! It can't have any other variation attributes.
+//!
+//! `#[variation(skip(...))]` only leaves out the listed families, `is`, `as`,
+//! `as_mut` and `into`, along with their aliases, such as for a variant whose
+//! inner value must not be moved out of the enum. The `drain_*` method and
+//! the `into_*` accessor of `#[variation(option_ext)]` follow `into`, the
+//! other accessors follow their family, and the iterator adapters are left
+//! out with either `as` or `into`.
+//!
+//! Everything describing the enum as a whole still covers it, since leaving
+//! it out would make those incomplete. It has a variant in the kind enum and
+//! the projection enums, is counted by `VARIANTS` and `kind_counts`, gets a
+//! method in the visitor trait and an argument to `fold`, and is handled by
+//! `variant_name`, the delegated impls and the other trait impls.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Token {
+//!     Word(String),
+//!     #[variation(skip)]
+//!     Whitespace(usize),
+//! }
+//!
+//! fn main() {
+//!     assert!(Token::Word("hi".to_owned()).is_word());
+//!     assert_eq!("Whitespace", Token::Whitespace(2).variant_name());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Token {
+//!     Word(String),
+//!     #[variation(skip)]
+//!     Whitespace(usize),
+//! }
+//!
+//! fn main() {
+//!     Token::Whitespace(2).is_whitespace();
+//! }
+//! ```
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Resource {
+//!     #[variation(skip(as_mut, into))]
+//!     Handle(u32),
+//!     Closed,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Some(&3), Resource::Handle(3).as_handle());
+//! }
+//! ```
+//!
+//! `#[variation(methods(...))]` on the enum lists the only families generated
+//! for any variant, such as `methods(is, as)` for an enum that shouldn't be
+//! taken apart. The families left out are skipped on every variant, so a
+//! variant gets the families that are both listed on the enum and not
+//! skipped on the variant itself.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(methods(is, as))]
+//! enum Shape {
+//!     Circle(f64),
+//!     #[variation(skip(as))]
+//!     Square(f64),
+//! }
+//!
+//! fn main() {
+//!     assert!(Shape::Square(2.0).is_square());
+//!     assert_eq!(Some(&1.0), Shape::Circle(1.0).as_circle());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(methods(is, as))]
+//! enum Shape {
+//!     Circle(f64),
+//!     Square(f64),
+//! }
+//!
+//! fn main() {
+//!     Shape::Circle(1.0).into_circle();
+//! }
+//! ```
+//!
+//! `#[variation(only(...))]` on the enum lists the only variants that get
+//! methods of their own, as if all others were marked `#[variation(skip)]`.
+//! A listed variant still leaves out the families it skips itself, and every
+//! name in the list has to be a variant of the enum.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(only(Integer))]
+//! enum Value {
+//!     Integer(i64),
+//!     Real(f64),
+//! }
+//!
+//! fn main() {
+//!     assert!(Value::Integer(1).is_integer());
+//! }
+//! ```
+//!
+//! #### Custom names
+//! `#[variation(rename = "...")]` gives a variant the name its generated names
+//! are derived from, in place of its own. The methods and `tag` use its snake
+//! case form, the kind enum its camel case form, and `variant_name`, `as_str`
+//! and `FromStr` the name as given, unless `#[variation(tag = "...")]` or
+//! `#[variation(str = "...")]` set them. Two variants ending up with the same
+//! names are rejected.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(kind)]
+//! enum Status {
+//!     #[variation(rename = "tls_up")]
+//!     HTTPSConnectionEstablished(u16),
+//!     Down,
+//! }
+//!
+//! fn main() {
+//!     let status = Status::HTTPSConnectionEstablished(443);
+//!
+//!     assert!(status.is_tls_up());
+//!     assert_eq!(StatusKind::TlsUp, status.kind());
+//!     assert_eq!("tls_up", status.variant_name());
+//!     assert_eq!(443, status.into_tls_up());
+//! }
+//! ```
+//!
+//! A single method can be given another name with `#[variation(is = "...")]`,
+//! `#[variation(as = "...")]`, `#[variation(as_mut = "...")]` or
+//! `#[variation(into = "...")]`, while the others keep theirs. `into_*_boxed`
+//! is named after `into_*`. The names can't clash with any other method of
+//! the variants or of the whole enum. `into = "..."` only ever names the
+//! method, even `into = "option"`, the mode being set with `into_mode`.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Message {
+//!     #[variation(is = "has_payload", into = "take_payload")]
+//!     Payload(Vec<u8>),
+//!     Ping,
+//! }
+//!
+//! fn main() {
+//!     let message = Message::Payload(vec![1, 2]);
+//!
+//!     assert!(message.has_payload());
+//!     assert_eq!(Some(&vec![1, 2]), message.as_payload());
+//!     assert_eq!(vec![1, 2], message.take_payload());
+//! }
+//! ```
+//!
+//! How the names of the variants become the stems of method names and their
+//! tags is set for the whole enum with `#[variation(rename_all = "...")]`, one
+//! of `"snake_case"`, the default, `"lowercase"`, which turns `IPv4` into
+//! `ipv4` where snake case would make it `i_pv4`, and `"verbatim"`, which
+//! keeps the name as it is, for variants already named in snake case.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(rename_all = "lowercase")]
+//! enum Address {
+//!     IPv4([u8; 4]),
+//!     IPv6([u16; 8]),
+//! }
+//!
+//! fn main() {
+//!     assert!(Address::IPv4([127, 0, 0, 1]).is_ipv4());
+//!     assert_eq!(None, Address::IPv4([127, 0, 0, 1]).as_ipv6());
+//! }
+//! ```
+//!
+//! #### Compatibility modes
+//! `#[variation(compat = "...")]` makes the methods match those of another
+//! crate, so switching to this one doesn't change the call sites:
+//!
+//! - `"enum_as_inner"` mirrors `enum-as-inner`, with `is_*`, `as_*` and
+//!   `as_*_mut` as they are by default, and `into_*` returning the enum in
+//!   `Err` for the wrong variant, as with `into_mode = "result"`.
+//! - `"derive_more"` mirrors the `IsVariant` and `Unwrap` derives of
+//!   `derive_more`, with `is_*` as it is and `into_*` named `unwrap_*` and
+//!   panicking for the wrong variant, as with `prefix(into = "unwrap_")`.
+//!   `as_*` and `as_*_mut` are generated too, and unit variants get no
+//!   `unwrap_*`.
+//!
+//! The names follow the other options such as `rename`, but the mode can't
+//! be combined with the options for the whole enum it sets itself.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(compat = "enum_as_inner")]
+//! enum Value {
+//!     Integer(i64),
+//!     Null,
+//! }
+//!
+//! #[derive(Variation)]
+//! #[variation(compat = "derive_more")]
+//! enum Shape {
+//!     Circle(f64),
+//!     Point,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Ok(1), Value::Integer(1).into_integer());
+//!     assert_eq!(Err(Value::Null), Value::Null.into_integer());
+//!     assert_eq!(2.0, Shape::Circle(2.0).unwrap_circle());
+//!     assert!(Shape::Point.is_point());
+//! }
+//! ```
+//!
+//! #### Documentation
+//! `#[variation(doc = "...")]` on a variant puts its own documentation before
+//! the generated one on the `is_*`, `as_*`, `as_*_mut`, `into_*` and
+//! `into_*_boxed` methods of the variant and their aliases. The string is
+//! used as written, so it can span several lines and use markdown.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Literal {
+//!     #[variation(doc = "Returns the parsed integer literal, if any.")]
+//!     Integer(i64),
+//!     Real(f64),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Some(&1), Literal::Integer(1).as_integer());
+//! }
+//! ```
+//!
+//! #### `variant_name` method
+//! A `variant_name` method returns the name of the variant the enum holds.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!("Unit", Type::Unit.variant_name());
+//!     assert_eq!("Integer", Type::Integer(5).variant_name());
+//! }
+//! ```
+//!
+//! #### `tag` method
+//! Enums marked with `#[variation(tag)]` get a `tag` method returning a
+//! string for the variant meant for external systems, such as a metrics or
+//! log label. It's the name of the variant cased like the method stems, in
+//! snake case unless `#[variation(rename_all = "...")]` says otherwise, and
+//! the variant can be given any other with `#[variation(tag = "...")]`.
+//! Variants can share a tag.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(tag)]
+//! enum Event {
+//!     #[variation(tag = "http.request")]
+//!     Request(String),
+//!     #[variation(tag = "http.request")]
+//!     Retry(String),
+//!     ConnectionClosed,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!("http.request", Event::Retry(String::from("/")).tag());
+//!     assert_eq!("connection_closed", Event::ConnectionClosed.tag());
+//! }
+//! ```
+//!
+//! #### `properties` and `get_property` methods
+//! Variants can be given static properties with
+//! `#[variation(props(key = "...", ...))]`, and the enum then gets a
+//! `properties` method returning those of the variant it holds as pairs of keys
+//! and values, and a `get_property` method returning the value of one key.
+//! Variants without properties return none.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Shape {
+//!     #[variation(props(color = "red", weight = "3"))]
+//!     Circle(f64),
+//!     #[variation(props(color = "blue"))]
+//!     Square(f64),
+//!     Point,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Some("red"), Shape::Circle(1.0).get_property("color"));
+//!     assert_eq!(None, Shape::Square(2.0).get_property("weight"));
+//!     assert_eq!(&[("color", "red"), ("weight", "3")], Shape::Circle(1.0).properties());
+//!     assert!(Shape::Point.properties().is_empty());
+//! }
+//! ```
+//!
+//! A variant can set a key only once.
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Shape {
+//!     #[variation(props(color = "red", color = "blue"))]
+//!     Circle(f64),
+//!     Point,
+//! }
+//! ```
+//!
+//! #### `kind` method
+//! Enums marked with `#[variation(kind)]` get a `{Enum}Kind` enum with the same
+//! variants without any inner types, and a `kind` method returning which of
+//! them the enum holds. The kind enum implements `Clone`, `Copy`, `Debug`,
+//! `PartialEq`, `Eq`, `Hash`, `PartialOrd` and `Ord`, and has the same
+//! visibility as the enum.
+//!
+//! `#[variation(group_by_kind)]` additionally generates a `group_by_kind`
+//! function collecting values of the enum into a `HashMap` from their kind to
+//! the values of that kind, in the order they were in.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(group_by_kind)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     let groups = Type::group_by_kind(vec![Type::Integer(1), Type::Unit, Type::Integer(2)]);
+//!
+//!     assert_eq!(TypeKind::Real, Type::Real(3, 14).kind());
+//!     assert_eq!(vec![Type::Integer(1), Type::Integer(2)], groups[&TypeKind::Integer]);
+//!     assert_eq!(None, groups.get(&TypeKind::Real));
+//! }
+//! ```
+//!
+//! The kind enum also comes with `try_as_*` and `try_as_*_mut` for each
+//! `as_*` and `as_*_mut` method, named after them, which return the inner
+//! type like `Result<&Inner, {Enum}Kind>` with the kind of the variant the
+//! enum holds in `Err`, for reporting what it was instead of just that it
+//! wasn't the one asked for.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, Variation)]
+//! #[variation(kind)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//!     Real(i32, u32),
+//! }
+//!
+//! fn integer(value: &Type) -> Result<i32, String> {
+//!     match value.try_as_integer() {
+//!         Ok(n) => Ok(*n),
+//!         Err(kind) => Err(format!("expected integer, found {:?}", kind)),
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut value = Type::Real(3, 14);
+//!
+//!     assert_eq!(Ok(2), integer(&Type::Integer(2)));
+//!     assert_eq!(Err("expected integer, found Real".to_owned()), integer(&value));
+//!
+//!     *value.try_as_real_mut().unwrap().0 = 2;
+//!     assert_eq!(Err(TypeKind::Real), value.try_as_integer_mut().map(|n| *n));
+//! }
+//! ```
+//!
+//! #### `drain_*` & `retain_kind` methods
+//! Enums marked with `#[variation(drain)]` get a `drain_*` function for every
+//! variant with fields, named after the plural of the variant, which removes
+//! the values of that variant from a `Vec` and returns their inner values.
+//! Both the removed and the remaining values stay in the order they were in.
+//! A `retain_kind` function removes every value not of the given kind, so
+//! `drain` also generates the `kind` method and `{Enum}Kind` enum.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(drain)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     let mut values = vec![Type::Integer(1), Type::Unit, Type::Real(3, 14), Type::Integer(2)];
+//!
+//!     assert_eq!(vec![1, 2], Type::drain_integers(&mut values));
+//!     assert_eq!(2, values.len());
+//!
+//!     Type::retain_kind(&mut values, TypeKind::Real);
+//!     assert_eq!(vec![(3, 14)], Type::drain_reals(&mut values));
+//! }
+//! ```
+//!
+//! #### Counting variants
+//! Enums marked with `#[variation(count_kinds)]` get a `{Enum}KindCounts`
+//! struct counting the values of each variant in a collection, created with
+//! `from_iter` or by collecting an iterator of references to the enum. It has
+//! an accessor for each variant named after it, a `total` method, and can be
+//! added together to merge counts. It implements `Clone`, `Copy`, `Debug`,
+//! `Default`, `PartialEq` and `Eq`, and has the same visibility as the enum.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(count_kinds)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     let values = vec![Type::Integer(1), Type::Unit, Type::Integer(2)];
+//!     let counts = TypeKindCounts::from_iter(&values);
+//!
+//!     assert_eq!(2, counts.integer());
+//!     assert_eq!(0, counts.real());
+//!     assert_eq!(3, counts.total());
+//! }
+//! ```
+//!
+//! A variant whose accessor would be named `total` or `from_iter` has to be
+//! renamed with `#[variation(rename = "...")]`.
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(count_kinds)]
+//! enum Line {
+//!     Subtotal(u32),
+//!     Total(u32),
+//! }
+//! ```
+//!
+//! #### `either` & `either_ref` methods
+//! Enums marked with `#[variation(fold)]` that have exactly two variants,
+//! both with inner types, get an `either` method along with `fold`, taking a
+//! closure for each variant and calling the one matching the enum with the
+//! variant's inner values, and its borrowing counterpart `either_ref`. Enums where one
+//! of the two variants is a unit variant don't get these methods, as there
+//! would be nothing to pass to its closure.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(fold)]
+//! enum Value {
+//!     Integer(i32),
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     let values = vec![Value::Integer(5), Value::Real(3, 14)];
+//!     let strings: Vec<String> = values
+//!         .iter()
+//!         .map(|value| value.either_ref(|i| i.to_string(), |i, f| format!("{}.{}", i, f)))
+//!         .collect();
+//!
+//!     assert_eq!(vec!["5", "3.14"], strings);
+//!     assert_eq!(5.0, Value::Integer(5).either(f64::from, |i, _| f64::from(i)));
+//! }
+//! ```
+//!
+//! #### `fold` & `fold_ref` methods
+//! Enums marked with `#[variation(fold)]` get a `fold` method taking a closure
+//! for each variant in declaration order, named `on_` followed by the
+//! variant's name, and calling the one for the enum's variant with its inner
+//! values. `fold_ref` does the same with references to the inner values.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(fold)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     let describe = |value: &Type| {
+//!         value.fold_ref(
+//!             || String::from("unit"),
+//!             |i| i.to_string(),
+//!             |i, f| format!("{}.{}", i, f),
+//!         )
+//!     };
+//!
+//!     assert_eq!("unit", describe(&Type::Unit));
+//!     assert_eq!("3.14", describe(&Type::Real(3, 14)));
+//!     assert_eq!(5, Type::Integer(5).fold(|| 0, |i| i, |i, _| i));
+//! }
+//! ```
+//!
+//! #### Visitors
+//! Enums marked with `#[variation(visitor)]` get a `{Enum}Visitor` trait, with
+//! an `Output` type and a `visit_` method for each variant taking references
+//! to the variant's inner values, and an `accept` method calling the visitor's
+//! method for the enum's variant. `{Enum}OwnedVisitor` and `accept_owned` do the
+//! same with the inner values themselves. The traits have the same visibility
+//! as the enum.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(visitor)]
+//! enum Expr {
+//!     Number(i64),
+//!     Add(Box<Expr>, Box<Expr>),
+//! }
+//!
+//! struct Evaluate;
+//!
+//! impl ExprVisitor for Evaluate {
+//!     type Output = i64;
+//!
+//!     fn visit_number(&mut self, v0: &i64) -> i64 {
+//!         *v0
+//!     }
+//!
+//!     fn visit_add(&mut self, v0: &Box<Expr>, v1: &Box<Expr>) -> i64 {
+//!         v0.accept(self) + v1.accept(self)
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let expr = Expr::Add(Box::new(Expr::Number(1)), Box::new(Expr::Number(2)));
+//!
+//!     assert_eq!(3, expr.accept(&mut Evaluate));
+//! }
+//! ```
+//!
+//! #### Iterator adapters
+//! Enums marked with `#[variation(iterator_ext)]` get an `{Enum}IteratorExt`
+//! trait, implemented for every iterator of the enum or of references to it.
+//! It has an adapter for each variant with inner types, named after the
+//! variant in plural, yielding the inner values of the items holding that
+//! variant, by value or by reference respectively. The `{Enum}IteratorItem`
+//! helper trait it's based on and the extension trait itself have the same
+//! visibility as the enum.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(iterator_ext)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     let values = vec![Type::Integer(1), Type::Unit, Type::Real(3, 14), Type::Integer(2)];
+//!
+//!     assert_eq!(vec![(&3, &14)], values.iter().reals().collect::<Vec<_>>());
+//!     assert_eq!(vec![1, 2], values.into_iter().integers().collect::<Vec<_>>());
+//! }
+//! ```
+//!
+//! #### Accessors for optional values
+//! Enums marked with `#[variation(option_ext)]` get an `{Enum}OptionExt`
+//! trait, implemented for `Option<Enum>` and `Option<&Enum>`, with the
+//! `is_*`, `as_*` and `into_*` methods of the enum treating `None` like a
+//! different variant. `into_*` on an `Option<&Enum>` returns references to the
+//! inner values. The trait has the same visibility as the enum.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(option_ext)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//! }
+//!
+//! fn main() {
+//!     let values = vec![Type::Integer(1), Type::Unit];
+//!
+//!     assert_eq!(Some(&1), values.first().into_integer());
+//!     assert!(values.get(1).is_unit());
+//!     assert!(!values.get(2).is_unit());
+//!     assert_eq!(Some(1), Some(Type::Integer(1)).into_integer());
+//! }
+//! ```
+//!
+//! #### `partition_variants` method
+//! Enums marked with `#[variation(partition)]` get a `partition_variants`
+//! function splitting a collection of the enum by variant into a generated
+//! `{Enum}Partition` struct. The struct has a field for each variant, named
+//! after it, holding a `Vec` of the inner values of that variant, or the
+//! number of values for unit variants. It implements `Debug` and `Default`,
+//! and has the same visibility as the enum.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, Variation)]
+//! #[variation(partition)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     let values = vec![Type::Integer(1), Type::Unit, Type::Real(3, 14), Type::Integer(2)];
+//!     let partition = Type::partition_variants(values);
+//!
+//!     assert_eq!(1, partition.unit);
+//!     assert_eq!(vec![1, 2], partition.integer);
+//!     assert_eq!(vec![(3, 14)], partition.real);
+//! }
+//! ```
+//!
+//! #### Variant markers
+//! `#[variation(markers)]` generates a `{enum}_markers` module next to the
+//! enum, with a marker type named after each variant with a single field and
+//! a `Variant<Enum>` trait they implement, whose `Output` is the type of the
+//! field. The enum gets `get::<V>()`, `get_mut::<V>()` and
+//! `into_variant::<V>()`, taking a marker to pick the variant, so generic code
+//! can be written over the variants.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(markers)]
+//! enum Type {
+//!     Integer(i64),
+//!     Text(String),
+//! }
+//!
+//! fn collect<V: type_markers::Variant<Type>>(values: Vec<Type>) -> Vec<V::Output> {
+//!     values.into_iter().filter_map(|value| value.into_variant::<V>().ok()).collect()
+//! }
+//!
+//! fn main() {
+//!     let values = vec![Type::Integer(1), Type::Text("a".to_owned()), Type::Integer(2)];
+//!
+//!     assert_eq!(Some(&1), values[0].get::<type_markers::Integer>());
+//!     assert_eq!(None, values[1].get::<type_markers::Integer>());
+//!     assert_eq!(vec![1, 2], collect::<type_markers::Integer>(values));
+//! }
+//! ```
+//!
+//! #### Derives of generated structs
+//! `#[variation(companion_derive(...))]` adds the traits it lists to the
+//! `#[derive(...)]` of the structs the enum gets next to it, the
+//! `{Enum}Partition` of `partition`, the `{Enum}KindCounts` of `count_kinds`,
+//! the marker types of `markers` and the projection enums of `ref_enum`,
+//! `mut_enum` and `pin_project`. The paths are copied as they're
+//! written, so derives of other crates work too, while traits a struct
+//! derives already are left out. Setting it on a variant with a single field
+//! replaces the list for that variant's marker type.
+//!
+//! ```rust
+//! use std::collections::HashSet;
+//!
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(partition, markers, companion_derive(Clone, PartialEq, Debug))]
+//! enum Type {
+//!     Integer(i64),
+//!     #[variation(companion_derive(PartialEq, Eq, Hash))]
+//!     Text(String),
+//! }
+//!
+//! fn main() {
+//!     let partition = Type::partition_variants(vec![Type::Integer(1), Type::Text("a".to_owned())]);
+//!
+//!     assert_eq!(partition.clone(), partition);
+//!     assert!(Some(type_markers::Text).into_iter().collect::<HashSet<_>>().contains(&type_markers::Text));
+//! }
+//! ```
+//!
+//! #### Match macro
+//! `#[variation(match_macro)]` generates a `match_{enum}!` macro next to the
+//! enum, or one with the name given by `#[variation(match_macro = "...")]`.
+//! It takes a value and an arm for each variant, named like the methods of
+//! the variant and listed in the order the variants are declared, and
+//! expands to a match on the value. Fields are matched by position, named
+//! ones included, so adding, removing or reordering variants or fields breaks
+//! every call of the macro instead of falling through to a wildcard arm.
+//!
+//! The macro is imported with `pub(crate)` next to the enum, so other modules
+//! of the crate can use it by path. The match names the enum as it's
+//! written, which has to be in scope where the macro is called.
+//!
+//! ```rust
+//! mod types {
+//!     use variation::Variation;
+//!
+//!     #[derive(Variation)]
+//!     #[variation(match_macro)]
+//!     pub enum Type {
+//!         Unit,
+//!         Integer(i64),
+//!         Real(i64, u64),
+//!     }
+//! }
+//!
+//! use types::{match_type, Type};
+//!
+//! fn describe(value: &Type) -> String {
+//!     match_type!(value, {
+//!         unit => "unit".to_owned(),
+//!         integer(n) => n.to_string(),
+//!         real(a, b) => format!("{}.{}", a, b),
+//!     })
+//! }
+//!
+//! fn main() {
+//!     assert_eq!("3.14", describe(&Type::Real(3, 14)));
+//! }
+//! ```
+//!
+//! Leaving out a variant is an error:
+//!
+//! ```rust,compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(match_macro)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i64),
+//! }
+//!
+//! fn main() {
+//!     let n = match_type!(Type::Integer(1), { integer(n) => n });
+//! }
+//! ```
+//!
+//! #### `into_ok_or_err` method
+//! Enums marked with `#[variation(into_ok_or_err)]` whose two variants both
+//! wrap a single value of the same type get an `into_ok_or_err` method
+//! returning that value, whichever variant it is. The types are compared as
+//! written, so `String` and `std::string::String` aren't considered the same.
+//! Enums with another number of variants or with different types are an
+//! error.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(into_ok_or_err)]
+//! enum Side {
+//!     Left(String),
+//!     Right(String),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!("left", Side::Left(String::from("left")).into_ok_or_err());
+//!     assert_eq!("right", Side::Right(String::from("right")).into_ok_or_err());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(into_ok_or_err)]
+//! enum Side {
+//!     Left(String),
+//!     Right(String),
+//!     Center(String),
+//! }
+//! ```
+//!
+//! #### Common fields
+//! Fields that every variant has, such as the source location of a syntax
+//! tree node, can be marked with `#[variation(common = "name")]` in each
+//! variant to get a `name` method returning a reference to the field,
+//! whichever variant the enum holds, and a `name_mut` method returning a
+//! mutable reference. The fields can be in different positions, but have to
+//! be of the same type, and every variant has to mark one. The name can be a
+//! keyword, such as `common = "type"`, which gives a raw `r#type` method and a
+//! `type_mut` method.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Span(usize, usize);
+//!
+//! #[derive(Variation)]
+//! enum Expr {
+//!     Literal(#[variation(common = "span")] Span, i64),
+//!     Add(Box<Expr>, #[variation(common = "span")] Span, Box<Expr>),
+//! }
+//!
+//! fn main() {
+//!     let mut literal = Expr::Literal(Span(0, 1), 1);
+//!
+//!     literal.span_mut().1 = 2;
+//!
+//!     assert_eq!(&Span(0, 2), literal.span());
+//! }
+//! ```
+//!
+//! #### `children` method
+//! Enums marked with `#[variation(dyn_iter = "Trait")]` get a `children`
+//! method returning an iterator over the inner values of the variant as
+//! `&dyn Trait`, such as the child nodes of a syntax tree. Every field is
+//! yielded unless it's marked with `#[variation(no_dyn_iter)]`. When some
+//! fields are marked with `#[variation(dyn_iter)]` instead, only those are
+//! yielded. A yielded field whose type doesn't implement the trait is an
+//! error at the field. The iterator goes over an array as long as the most
+//! fields any variant yields, so it doesn't allocate.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! trait Node {
+//!     fn size(&self) -> usize;
+//! }
+//!
+//! impl Node for Box<Expr> {
+//!     fn size(&self) -> usize {
+//!         1 + self.children().map(Node::size).sum::<usize>()
+//!     }
+//! }
+//!
+//! #[derive(Variation)]
+//! #[variation(dyn_iter = "Node")]
+//! enum Expr {
+//!     Literal(#[variation(no_dyn_iter)] i64),
+//!     Add(Box<Expr>, Box<Expr>),
+//! }
+//!
+//! fn main() {
+//!     let literal = || Box::new(Expr::Literal(1));
+//!     let sum = Expr::Add(literal(), Box::new(Expr::Add(literal(), literal())));
+//!
+//!     assert_eq!(0, Expr::Literal(1).children().count());
+//!     assert_eq!(4, sum.children().map(Node::size).sum::<usize>());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Expr {
+//!     Add(#[variation(dyn_iter)] Box<Expr>, Box<Expr>),
+//! }
+//! #
+//! # fn main() {}
+//! ```
+//!
+//! #### `into_inner`, `as_inner` & `as_inner_mut` methods
+//! Enums whose variants all wrap a single value of the same type can be marked
+//! with `#[variation(inner)]` to get an `into_inner` method returning that
+//! value, whichever variant it is, and `as_inner` and `as_inner_mut` methods
+//! returning a reference to it. `map_inner` and `map_inner_mut` change the
+//! inner value with a closure while keeping the variant. The types are
+//! compared as written, like for `into_ok_or_err`.
+//!
+//! ```rust
+//! use std::path::PathBuf;
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(inner)]
+//! enum Source {
+//!     File(PathBuf),
+//!     Override(PathBuf),
+//!     Default(PathBuf),
+//! }
+//!
+//! fn main() {
+//!     let mut source = Source::Override(PathBuf::from("config.toml"));
+//!
+//!     source.as_inner_mut().set_extension("json");
+//!
+//!     assert_eq!("config.json", source.as_inner().to_str().unwrap());
+//!
+//!     let source = source.map_inner(|path| PathBuf::from("/etc").join(path));
+//!
+//!     assert!(source.is_override());
+//!     assert_eq!(PathBuf::from("/etc/config.json"), source.into_inner());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use std::path::PathBuf;
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(inner)]
+//! enum Source {
+//!     File(PathBuf),
+//!     Default(String),
+//! }
+//! ```
+//!
+//! #### `as_ref` & `as_mut` methods
+//! Enums marked with `#[variation(ref_enum)]` get an `{Enum}Ref` enum with
+//! the same variants, holding shared references to the inner values, and an
+//! `as_ref` method converting a reference to the enum into it. Matching on
+//! it gives access to the inner values of every variant without writing the
+//! borrowed enum by hand. It implements `Clone` and `Copy`, and has the same
+//! visibility as the enum. `#[variation(mut_enum)]` likewise generates an
+//! `{Enum}Mut` enum with mutable references and an `as_mut` method. Other
+//! traits such as `Debug` are derived with
+//! `#[variation(companion_derive(...))]`, as the fields may not implement
+//! them. Neither can be combined with `#[variation(as_ref)]`, which
+//! implements `AsRef` and `AsMut` instead.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(ref_enum)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//!     Real(i32, u32),
+//! }
+//!
+//! fn describe(value: &Type) -> String {
+//!     match value.as_ref() {
+//!         TypeRef::Unit => String::from("unit"),
+//!         TypeRef::Integer(i) => format!("integer {}", i),
+//!         TypeRef::Real(i, f) => format!("real {}.{}", i, f),
+//!     }
+//! }
+//!
+//! fn main() {
+//!     assert_eq!("real 3.14", describe(&Type::Real(3, 14)));
+//! }
+//! ```
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(mut_enum)]
+//! enum Type {
+//!     Integer(i32),
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     let mut value = Type::Real(3, 14);
+//!
+//!     match value.as_mut() {
+//!         TypeMut::Integer(i) => *i = 0,
+//!         TypeMut::Real(i, f) => *f = *i as u32,
+//!     }
+//!
+//!     assert_eq!(Some((&3, &3)), value.as_real());
+//! }
+//! ```
+//!
+//! #### `project` method
+//! Enums marked with `#[variation(pin_project)]` get an `{Enum}Proj` enum
+//! and a `project` method converting a pinned mutable reference to the enum
+//! into it, like `pin-project` does for structs. Fields marked with
+//! `#[variation(pin)]` are projected to pinned references, such as for
+//! polling a future the enum holds, and the others to plain mutable
+//! references. The enum must not implement `Drop`, which could move the
+//! pinned fields, and it's `Unpin` only if the pinned fields are.
+//!
+//! ```rust
+//! use std::future::{self, Future};
+//! use std::pin::Pin;
+//! use std::task::{Context, Poll};
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(pin_project)]
+//! enum State<F> {
+//!     Running(#[variation(pin)] F, u32),
+//!     Done,
+//! }
+//!
+//! impl<F: Future> State<F> {
+//!     fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<F::Output> {
+//!         let output = match self.as_mut().project() {
+//!             StateProj::Running(future, polls) => {
+//!                 *polls += 1;
+//!                 future.poll(cx)
+//!             }
+//!             StateProj::Done => panic!("polled after completion"),
+//!         };
+//!
+//!         if output.is_ready() {
+//!             self.set(State::Done);
+//!         }
+//!
+//!         output
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let state = Box::pin(State::Running(future::ready(1), 0));
+//!
+//!     assert!(state.is_running());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(pin_project)]
+//! enum State<F> {
+//!     Running(#[variation(pin)] F, u32),
+//!     Done,
+//! }
+//!
+//! // `Pin::get_mut` could move a pinned `F` out of the enum.
+//! impl<F> Unpin for State<F> {}
+//! ```
+//!
+//! ## Fieldless enums
+//!
+//! #### `VARIANTS` & `iter`
+//! Fieldless enums marked with `#[variation(iter)]` get a `VARIANTS` constant
+//! holding every variant, and an `iter` function returning an iterator over
+//! them. Both list the variants in the order they are declared in.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(iter)]
+//! enum Theme {
+//!     Light,
+//!     Dark,
+//!     System,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(3, Theme::VARIANTS.len());
+//!     assert_eq!(vec![Theme::Light, Theme::Dark, Theme::System], Theme::iter().collect::<Vec<_>>());
+//! }
+//! ```
+//!
+//! #### `next` & `prev` methods
+//! Fieldless enums marked with `#[variation(cycle)]` get `next` and `prev`
+//! methods stepping through the variants, wrapping around at either end. The
+//! steps follow the order the variants are declared in, so reordering the
+//! variants changes them.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(cycle)]
+//! enum Theme {
+//!     Light,
+//!     Dark,
+//!     System,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Theme::Dark, Theme::Light.next());
+//!     assert_eq!(Theme::Light, Theme::System.next());
+//!     assert_eq!(Theme::System, Theme::Light.prev());
+//! }
+//! ```
+//!
+//! #### `FromStr` impl
+//! Fieldless enums marked with `#[variation(from_str)]` implement `FromStr`,
+//! parsing either the name of a variant or its snake case form. Strings that
+//! don't name a variant are rejected with an `{Enum}ParseError`, which
+//! implements `Error`, holds on to the string and lists the accepted names.
+//! Variants whose spellings overlap are an error.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(from_str)]
+//! enum Mode {
+//!     Fast,
+//!     LowPower,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Ok(Mode::LowPower), "LowPower".parse());
+//!     assert_eq!(Ok(Mode::LowPower), "low_power".parse());
+//!     assert_eq!(
+//!         "unknown `Mode` `slow`, expected one of `Fast`, `fast`, `LowPower`, `low_power`",
+//!         "slow".parse::<Mode>().unwrap_err().to_string()
+//!     );
+//! }
+//! ```
+//!
+//! #### `as_str` method
+//! Fieldless enums marked with `#[variation(as_str)]` get a `const` `as_str`
+//! method returning the name of the variant, which is the canonical spelling
+//! accepted by the `FromStr` impl.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(as_str, from_str)]
+//! enum Mode {
+//!     Fast,
+//!     LowPower,
+//! }
+//!
+//! const LOW_POWER: &str = Mode::LowPower.as_str();
+//!
+//! fn main() {
+//!     assert_eq!("LowPower", LOW_POWER);
+//!     assert_eq!(Ok(Mode::Fast), Mode::Fast.as_str().parse());
+//! }
+//! ```
+//!
+//! A variant marked with `#[variation(str = "...")]` uses the given string in
+//! place of its name for both `as_str` and `FromStr`, which then doesn't
+//! accept the name or its snake case form. Two variants using the same string
+//! are an error.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(as_str, from_str)]
+//! enum Theme {
+//!     #[variation(str = "light-mode")]
+//!     Light,
+//!     Dark,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!("light-mode", Theme::Light.as_str());
+//!     assert_eq!(Ok(Theme::Light), "light-mode".parse());
+//!     assert!("Light".parse::<Theme>().is_err());
+//!     assert_eq!(Ok(Theme::Dark), "dark".parse());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(as_str)]
+//! enum Theme {
+//!     #[variation(str = "dark")]
+//!     Light,
+//!     Dark,
+//!     #[variation(str = "dark")]
+//!     System,
+//! }
+//! ```
+//!
+//! #### `from_repr` & `to_repr` methods
+//! Fieldless enums with an integer `#[repr(...)]` attribute marked with
+//! `#[variation(repr)]` get a `from_repr` function returning the variant with
+//! the given discriminant, and a `to_repr` method returning the discriminant
+//! of a variant, both using the integer type of the `#[repr]`.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[repr(u8)]
+//! #[variation(repr)]
+//! enum Opcode {
+//!     Nop,
+//!     Add = 4,
+//!     Sub,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Some(Opcode::Nop), Opcode::from_repr(0));
+//!     assert_eq!(Some(Opcode::Sub), Opcode::from_repr(5));
+//!     assert_eq!(None, Opcode::from_repr(1));
+//!     assert_eq!(4, Opcode::Add.to_repr());
+//! }
+//! ```
+//!
+//! `#[variation(try_from_repr)]` additionally implements `TryFrom` for the
+//! integer type, also generating `from_repr` and `to_repr`. Values that aren't
+//! a discriminant are rejected with an `{Enum}ReprError`, which implements
+//! `Error` and holds on to the value.
+//!
+//! ```rust
+//! use std::convert::TryFrom;
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[repr(u8)]
+//! #[variation(try_from_repr)]
+//! enum Opcode {
+//!     Nop,
+//!     Add = 4,
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Ok(Opcode::Add), Opcode::try_from(4));
+//!     assert_eq!(1, Opcode::try_from(1).unwrap_err().value());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[repr(u8)]
+//! #[variation(repr)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//! }
+//! ```
+//!
+//! ## Trait implementations
+//!
+//! #### `From` impls
+//! Variants marked with `#[variation(from)]` get a `From` impl for their inner
+//! types, allowing you to construct the enum with `.into()`. Variants that have
+//! more than one inner type convert from a tuple of those types.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! enum Type {
+//!     Unit,
+//!     #[variation(from)]
+//!     Integer(i32),
+//!     #[variation(from)]
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     let integer: Type = 5.into();
+//!     let real: Type = (3, 14).into();
+//!
+//!     assert_eq!(Type::Integer(5), integer);
+//!     assert_eq!(Type::Real(3, 14), real);
+//! }
+//! ```
+//!
+//! Marking two variants that wrap the same types is an error, as it would be
+//! ambiguous which variant to convert into.
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Type {
+//!     #[variation(from)]
+//!     Integer(i32),
+//!     #[variation(from)]
+//!     Count(i32),
+//! }
+//! ```
+//!
+//! With `#[variation(from(forward))]`, the impl converts from any type that
+//! converts into the inner types, such as `&str` for a variant wrapping a
+//! `String`. That blanket impl overlaps with any other `From` impl for a
+//! foreign type, so it's an error next to another variant marked with
+//! `#[variation(from)]`, `ok` and `err` variants or an inner type naming a type
+//! parameter. `#[variation(from(forward(Type, ...)))]` forwards from the listed
+//! types only, next to the impl for the inner types.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! enum Label {
+//!     #[variation(from(forward))]
+//!     Text(String),
+//!     Empty,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! enum Value {
+//!     #[variation(from(forward(&str, char)))]
+//!     Text(String),
+//!     #[variation(from)]
+//!     Flag(bool),
+//! }
+//!
+//! fn main() {
+//!     let label: Label = "name".into();
+//!     let value: Value = 'x'.into();
+//!
+//!     assert_eq!(Label::Text(String::from("name")), label);
+//!     assert_eq!(Value::Text(String::from("x")), value);
+//!     assert_eq!(Value::Flag(true), true.into());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Value {
+//!     #[variation(from(forward))]
+//!     Text(String),
+//!     #[variation(from)]
+//!     Flag(bool),
+//! }
+//! ```
+//!
+//! #### `TryFrom` impls
+//! Variants marked with `#[variation(try_into)]` get a `TryFrom<Enum>` impl on
+//! their inner types, the counterpart of the `From` impls. Converting a value
+//! that holds a different variant returns the value back as the error. As with
+//! `From`, two variants wrapping the same types can't both be marked.
+//!
+//! ```rust
+//! use std::convert::TryFrom;
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! enum Type {
+//!     Unit,
+//!     #[variation(try_into)]
+//!     Integer(i32),
+//!     #[variation(try_into)]
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Ok(5), i32::try_from(Type::Integer(5)));
+//!     assert_eq!(Ok((3, 14)), <(i32, u32)>::try_from(Type::Real(3, 14)));
+//!     assert_eq!(Err(Type::Unit), i32::try_from(Type::Unit));
+//! }
+//! ```
+//!
+//! A variant whose only inner type is one of the enum's type parameters can't
+//! be marked, as the orphan rules forbid implementing `TryFrom` for a bare type
+//! parameter. The same goes for a type parameter behind `&`, `&mut`, `Box` or
+//! `Pin`, which don't cover it. The error points at the attribute of that
+//! variant.
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Wrapper<T> {
+//!     #[variation(try_into)]
+//!     Value(T),
+//!     Empty,
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Wrapper<T> {
+//!     #[variation(try_into)]
+//!     Value(Box<T>),
+//!     Empty,
+//! }
+//! ```
+//!
+//! The same variants also get `TryFrom<&Enum>` and `TryFrom<&mut Enum>` impls
+//! on references to their inner types, allowing you to borrow the inner values
+//! in generic code. Variants with more than one inner type convert into a tuple
+//! of references. The error is the reference to the enum that was passed in.
+//! The orphan rules forbid these for an uncovered type parameter too, so the
+//! same variants are rejected.
+//!
+//! ```rust
+//! use std::convert::TryFrom;
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! enum Type {
+//!     Unit,
+//!     #[variation(try_into)]
+//!     Integer(i32),
+//!     #[variation(try_into)]
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     let mut integer = Type::Integer(5);
+//!
+//!     *<&mut i32>::try_from(&mut integer).unwrap() += 1;
+//!
+//!     assert_eq!(Ok(&6), <&i32>::try_from(&integer));
+//!     assert_eq!(Ok((&3, &14)), <(&i32, &u32)>::try_from(&Type::Real(3, 14)));
+//!     assert_eq!(Err(&Type::Unit), <&i32>::try_from(&Type::Unit));
+//! }
+//! ```
+//!
+//! An enum whose variants are all variants of a bigger enum, with the same
+//! names and inner types, can convert to and from it with
+//! `#[variation(subset_of = "...")]`, naming the bigger enum the way a type
+//! is named there. The bigger enum gets a `From` impl for the smaller one and
+//! the smaller one a `TryFrom` impl for the bigger one, returning the
+//! original value as the error for the variants it lacks. A variant the
+//! bigger enum doesn't have, or whose inner types differ, fails to compile
+//! with the error pointing at that variant.
+//!
+//! ```rust
+//! use std::convert::TryFrom;
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum Token {
+//!     Number(i64),
+//!     Word(String),
+//!     Comment(String),
+//! }
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(subset_of = "Token")]
+//! enum Value {
+//!     Number(i64),
+//!     Word(String),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Token::Number(1), Token::from(Value::Number(1)));
+//!     assert_eq!(Ok(Value::Number(1)), Value::try_from(Token::Number(1)));
+//!     assert_eq!(Err(Token::Comment("x".to_owned())), Value::try_from(Token::Comment("x".to_owned())));
+//! }
+//! ```
+//!
+//! The other way around, `#[variation(subset(name = "...", variants(...)))]`
+//! generates an enum with copies of the listed variants, along with the same
+//! `From` and `TryFrom` impls, keeping it in step with the enum. It takes the
+//! visibility of the enum unless given with `vis = "..."`, derives the traits
+//! listed with `derive(...)`, and `error = "..."` sets the error of `TryFrom`,
+//! which is built with its `From` impl for the enum. The option can be given
+//! more than once for several subsets.
+//!
+//! ```rust
+//! use std::convert::TryFrom;
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(subset(name = "Scalar", variants(Integer, Bool), derive(Debug, PartialEq)))]
+//! enum Type {
+//!     Integer(i64),
+//!     Bool(bool),
+//!     Text(String),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Type::Integer(1), Type::from(Scalar::Integer(1)));
+//!     assert_eq!(Ok(Scalar::Bool(true)), Scalar::try_from(Type::Bool(true)));
+//!     assert!(Scalar::try_from(Type::Text("a".to_owned())).is_err());
+//! }
+//! ```
+//!
+//! #### `PartialEq` impls
+//! Variants with a single inner type marked with `#[variation(partial_eq)]` get
+//! `PartialEq` impls in both directions between the enum and the inner type,
+//! which are equal only when the enum holds that variant with an equal value.
+//! Two variants wrapping the same type can't both be marked. The impl on the
+//! inner type is omitted when it is one of the enum's type parameters, or one
+//! behind `&`, `&mut`, `Box` or `Pin`, as the orphan rules don't allow it.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, Variation)]
+//! enum Type {
+//!     Unit,
+//!     #[variation(partial_eq)]
+//!     Integer(i32),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Type::Integer(5), 5);
+//!     assert_eq!(5, Type::Integer(5));
+//!     assert_ne!(Type::Integer(4), 5);
+//!     assert_ne!(Type::Unit, 5);
+//! }
+//! ```
+//!
+//! #### `AsRef` & `AsMut` impls
+//! Enums with a single variant holding a single inner type can mark it with
+//! `#[variation(as_ref)]` to get `AsRef` and `AsMut` impls for the inner type.
+//! Writing `#[variation(as_ref(Target))]` instead implements them for `Target`
+//! by forwarding to the inner type's own `AsRef<Target>` and `AsMut<Target>`
+//! impls. As these traits can't fail, marking a variant of an enum with more
+//! than one variant is an error.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Name {
+//!     #[variation(as_ref(str))]
+//!     Owned(String),
+//! }
+//!
+//! fn main() {
+//!     let mut name = Name::Owned(String::from("variation"));
+//!
+//!     name.as_mut().make_ascii_uppercase();
+//!
+//!     assert_eq!("VARIATION", AsRef::<str>::as_ref(&name));
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Type {
+//!     Unit,
+//!     #[variation(as_ref)]
+//!     Integer(i32),
+//! }
+//! ```
+//!
+//! #### `Borrow` & `BorrowMut` impls
+//! `#[variation(borrow)]` and `#[variation(borrow(Target))]` work the same as
+//! `as_ref`, implementing `Borrow` and `BorrowMut` instead. `Borrow` requires
+//! that `Hash`, `Eq` and `Ord` behave identically on the enum and the borrowed
+//! type, so these have to be implemented by hand by forwarding to the inner
+//! value, rather than derived.
+//!
+//! ```rust
+//! use std::borrow::Borrow;
+//! use std::collections::HashSet;
+//! use std::hash::{Hash, Hasher};
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Key {
+//!     #[variation(borrow(str))]
+//!     Name(String),
+//! }
+//!
+//! impl PartialEq for Key {
+//!     fn eq(&self, other: &Key) -> bool {
+//!         Borrow::<str>::borrow(self) == Borrow::<str>::borrow(other)
+//!     }
+//! }
+//!
+//! impl Eq for Key {}
+//!
+//! impl Hash for Key {
+//!     fn hash<H: Hasher>(&self, state: &mut H) {
+//!         Borrow::<str>::borrow(self).hash(state)
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut keys = HashSet::new();
+//!     keys.insert(Key::Name(String::from("variation")));
+//!
+//!     assert!(keys.contains("variation"));
+//! }
+//! ```
+//!
+//! #### `Default` impl
+//! Marking a variant with `#[variation(default)]` implements `Default` for the
+//! enum, returning that variant. Variants with fields are allowed, each field
+//! is set to its own default value. As with `#[derive(Default)]`, the impl
+//! requires every type parameter of the enum to implement `Default`. Only one
+//! variant can be marked.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! enum Type {
+//!     Unit,
+//!     #[variation(default)]
+//!     Integer(i32),
+//!     Real(i32, u32),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(Type::Integer(0), Type::default());
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Type {
+//!     #[variation(default)]
+//!     Unit,
+//!     #[variation(default)]
+//!     Integer(i32),
+//! }
+//! ```
+//!
+//! #### `Display` impl
+//! Enums marked with `#[variation(display)]` implement `Display` by writing
+//! the name of the variant, the same as `variant_name`, leaving out the inner
+//! values. To write the inner value instead, see `delegate(Display)` below.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(display)]
+//! enum Type {
+//!     Unit,
+//!     Integer(i32),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!("Integer", Type::Integer(5).to_string());
+//! }
+//! ```
+//!
+//! #### Redacted `Debug` impl
+//! Enums whose inner values shouldn't end up in logs, such as passwords or
+//! tokens, can be marked with `#[variation(debug = "redacted")]` to implement
+//! `Debug` by writing only the name of the variant, with `(..)` in place of
+//! the inner values. The inner types don't need to implement `Debug`.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! struct Secret(String);
+//!
+//! #[derive(Variation)]
+//! #[variation(debug = "redacted")]
+//! enum Credentials {
+//!     Anonymous,
+//!     Token(Secret),
+//! }
+//!
+//! fn main() {
+//!     let token = Credentials::Token(Secret(String::from("hunter2")));
+//!
+//!     assert_eq!("Credentials::Token(..)", format!("{:?}", token));
+//!     assert_eq!("Credentials::Anonymous", format!("{:?}", Credentials::Anonymous));
+//! }
+//! ```
+//!
+//! #### `Error` impl
+//! Error enums whose variants each wrap an underlying error can be marked
+//! with `#[variation(error)]` to implement `Display` by writing the wrapped
+//! error, and `Error` with its `source` returning it. A variant marked with
+//! `#[variation(error(msg = "..."))]` writes the message before the wrapped
+//! error instead, and unit variants write only their message, so they need
+//! one. The enum still has to implement `Debug`.
+//!
+//! ```rust
+//! use std::error::Error;
+//! use std::io;
+//! use std::num::ParseIntError;
+//! use variation::Variation;
+//!
+//! #[derive(Debug, Variation)]
+//! #[variation(error)]
+//! enum ConfigError {
+//!     Io(io::Error),
+//!     #[variation(error(msg = "invalid port"))]
+//!     Port(ParseIntError),
+//!     #[variation(error(msg = "missing host"))]
+//!     MissingHost,
+//! }
+//!
+//! fn main() {
+//!     let port = ConfigError::Port("http".parse::<u16>().unwrap_err());
+//!
+//!     assert_eq!("invalid port: invalid digit found in string", port.to_string());
+//!     assert!(port.source().is_some());
+//!     assert_eq!("missing host", ConfigError::MissingHost.to_string());
+//! }
+//! ```
+//!
+//! #### `Deref` & `DerefMut` impls
+//! Enums whose variants all wrap a single value of the same type can be marked
+//! with `#[variation(deref)]` to implement `Deref` and `DerefMut` to that
+//! value, whichever variant it is, so its methods can be called on the enum
+//! directly.
+//!
+//! ```rust
+//! use std::path::{Path, PathBuf};
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(deref)]
+//! enum Source {
+//!     File(PathBuf),
+//!     Default(PathBuf),
+//! }
+//!
+//! fn main() {
+//!     let mut source = Source::File(PathBuf::from("config.toml"));
+//!
+//!     source.set_extension("json");
+//!
+//!     assert_eq!(Some("json".as_ref()), source.extension());
+//!     assert!(Path::new("config.json") == &*source);
+//! }
+//! ```
+//!
+//! #### Delegated impls
+//! Enums whose variants each wrap a single value can implement traits by
+//! forwarding to whichever value they hold, by listing the traits in
+//! `#[variation(delegate(...))]`. `Iterator` needs its item type written out,
+//! as in `delegate(Iterator<Item = u32>)`, and `DoubleEndedIterator` and
+//! `ExactSizeIterator` can be delegated along with it. `std::io`'s `Read` and
+//! `Write` can be delegated as well, such as for an enum holding either a
+//! file or a network stream. `delegate(Display)` writes the inner value, such
+//! as for an identifier that can be one of several types.
+//!
+//! `delegate(Future<Output = T>)` polls whichever future the enum holds,
+//! which is useful for returning one of several futures without boxing. The
+//! inner futures are pinned along with the enum, so it must not implement
+//! `Drop`, and it's `Unpin` only if all of them are.
+//!
+//! ```rust
+//! use std::iter::{self, Once};
+//! use std::ops::Range;
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(delegate(Iterator<Item = u32>, DoubleEndedIterator))]
+//! enum Numbers {
+//!     Single(Once<u32>),
+//!     Range(Range<u32>),
+//! }
+//!
+//! fn numbers(single: bool) -> Numbers {
+//!     if single {
+//!         Numbers::Single(iter::once(1))
+//!     } else {
+//!         Numbers::Range(1..4)
+//!     }
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(vec![1], numbers(true).collect::<Vec<_>>());
+//!     assert_eq!(vec![3, 2, 1], numbers(false).rev().collect::<Vec<_>>());
+//! }
+//! ```
+//!
+//! ```rust
+//! use std::io::{self, Cursor, Read, Write};
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(delegate(Write))]
+//! enum Output {
+//!     Buffer(Cursor<Vec<u8>>),
+//!     Sink(io::Sink),
+//! }
+//!
+//! fn main() -> io::Result<()> {
+//!     let mut output = Output::Buffer(Cursor::new(Vec::new()));
+//!
+//!     write!(output, "{}", 5)?;
+//!
+//!     assert_eq!(Some(&b"5"[..]), output.as_buffer().map(|cursor| &cursor.get_ref()[..]));
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use std::future::Ready;
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(delegate(Future<Output = u32>))]
+//! enum Task {
+//!     Ready(Ready<u32>),
+//! }
+//!
+//! // `drop` could move the pinned future out of the enum.
+//! impl Drop for Task {
+//!     fn drop(&mut self) {}
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use std::future::Ready;
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(delegate(Future<Output = u32>))]
+//! enum Task {
+//!     Ready(Ready<u32>),
+//! }
+//!
+//! // `Pin::get_mut` could move the pinned future out of the enum.
+//! impl Unpin for Task {}
+//! ```
+//!
+//! Methods can be forwarded the same way without a trait, by writing their
+//! signatures in `#[variation(delegate_fn(...))]`, one in each. The generated
+//! method calls the method of the same name on the inner value, so it can be
+//! an inherent method of each type. It can take `self`, `&self` or
+//! `&mut self` along with other arguments. Variants with several fields mark
+//! the one to call it on with `#[variation(delegate_fn)]`.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! # #[derive(Clone, Copy, Debug, PartialEq)]
+//! # struct Span(usize, usize);
+//! struct Ident {
+//!     span: Span,
+//!     name: String,
+//! }
+//!
+//! impl Ident {
+//!     fn span(&self) -> Span {
+//!         self.span
+//!     }
+//!
+//!     fn rename(&mut self, name: &str) {
+//!         self.name = name.to_owned();
+//!     }
+//! }
+//!
+//! # struct Literal(Span);
+//! #
+//! # impl Literal {
+//! #     fn span(&self) -> Span {
+//! #         self.0
+//! #     }
+//! #
+//! #     fn rename(&mut self, _: &str) {}
+//! # }
+//! #[derive(Variation)]
+//! #[variation(delegate_fn(pub fn span(&self) -> Span))]
+//! #[variation(delegate_fn(pub fn rename(&mut self, name: &str)))]
+//! enum Expr {
+//!     Ident(Ident),
+//!     Literal(#[variation(delegate_fn)] Literal, i64),
+//! }
+//!
+//! fn main() {
+//!     let mut expr = Expr::Ident(Ident { span: Span(0, 1), name: "a".to_owned() });
+//!
+//!     expr.rename("b");
+//!
+//!     assert_eq!(Span(0, 1), expr.span());
+//!     assert_eq!("b", expr.as_ident().unwrap().name);
+//!     assert_eq!(Span(2, 3), Expr::Literal(Literal(Span(2, 3)), 1).span());
+//! }
+//! ```
+//!
+//! A trait of the crate can be implemented the same way, by marking it with
+//! `#[variation::dispatchable]` and the enum with
+//! `#[variation(dispatch = "...")]` naming it. A derive can't see the trait,
+//! so the attribute records its methods in a hidden macro named like the
+//! trait, which the enum's impl is generated with. It has to be in scope
+//! where the enum is, as it is along with the trait when the trait is
+//! imported, and it's only available within the crate. Every method is
+//! forwarded, including those with a default, and they can take `self`,
+//! `&self` or `&mut self`. Traits with generics, associated types or consts,
+//! or methods naming `Self` elsewhere than in their receiver are an error, and
+//! every variant needs a single field.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[variation::dispatchable]
+//! trait Shape {
+//!     fn area(&self) -> f64;
+//!     fn scale(&mut self, by: f64);
+//!     fn into_name(self) -> String;
+//! }
+//!
+//! struct Circle(f64);
+//! struct Square(f64);
+//!
+//! impl Shape for Circle {
+//!     fn area(&self) -> f64 {
+//!         3.0 * self.0 * self.0
+//!     }
+//!
+//!     fn scale(&mut self, by: f64) {
+//!         self.0 *= by;
+//!     }
+//!
+//!     fn into_name(self) -> String {
+//!         String::from("circle")
+//!     }
+//! }
+//! #
+//! # impl Shape for Square {
+//! #     fn area(&self) -> f64 {
+//! #         self.0 * self.0
+//! #     }
+//! #
+//! #     fn scale(&mut self, by: f64) {
+//! #         self.0 *= by;
+//! #     }
+//! #
+//! #     fn into_name(self) -> String {
+//! #         String::from("square")
+//! #     }
+//! # }
+//!
+//! #[derive(Variation)]
+//! #[variation(dispatch = "Shape")]
+//! enum AnyShape {
+//!     Circle(Circle),
+//!     Square(Square),
+//! }
+//!
+//! fn main() {
+//!     let mut shape = AnyShape::Square(Square(2.0));
+//!
+//!     shape.scale(1.5);
+//!
+//!     assert_eq!(9.0, shape.area());
+//!     assert_eq!("square", shape.into_name());
+//!     assert_eq!(3.0, AnyShape::Circle(Circle(1.0)).area());
+//! }
+//! ```
+//!
+//! ## `either` interop
+//! With the `either` feature of this crate enabled, enums with two variants
+//! marked with `#[variation(either)]` get `into_either`, `from_either` and
+//! `as_either` methods converting between the enum and an `either::Either`
+//! of the variants' inner types, with the first variant on the left. Unit
+//! variants convert into `()`. The generated code refers to `::either`, so the
+//! crate deriving the methods needs to depend on `either` itself.
+//!
+//! ```ignore
+//! use either::Either;
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! #[variation(either)]
+//! enum Value {
+//!     Integer(i32),
+//!     Text(String),
+//! }
+//!
+//! fn main() {
+//!     let text = Value::Text(String::from("text"));
+//!
+//!     assert_eq!(Either::Right(&String::from("text")), text.as_either());
+//!     assert_eq!(Either::Left(5), Value::Integer(5).into_either());
+//!     assert_eq!(Value::Integer(5), Value::from_either(Either::Left(5)));
+//! }
+//! ```
+//!
+//! ## `VariantKind` trait
+//! Enums marked with `#[variation(kind(variant_kind))]` also implement the
+//! `VariantKind` trait of the `variation-kind` crate, with the kind enum as
+//! its `Kind`, the `kind` and `variant_name` methods, and the number of
+//! variants as `VARIANT_COUNT`, so code can be generic over such enums. The
+//! generated code refers to `::variation_kind`, so the crate deriving the
+//! methods needs to depend on `variation-kind` itself.
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use variation::Variation;
+//! use variation_kind::VariantKind;
+//!
+//! fn histogram<E: VariantKind>(values: &[E]) -> HashMap<E::Kind, usize> {
+//!     let mut counts = HashMap::with_capacity(E::VARIANT_COUNT);
+//!
+//!     for value in values {
+//!         *counts.entry(value.kind()).or_insert(0) += 1;
+//!     }
+//!
+//!     counts
+//! }
+//!
+//! #[derive(Variation)]
+//! #[variation(kind(variant_kind))]
+//! enum Event {
+//!     Click(u32),
+//!     Key(char),
+//! }
+//!
+//! fn main() {
+//!     let counts = histogram(&[Event::Click(1), Event::Key('a'), Event::Click(2)]);
+//!
+//!     assert_eq!(2, counts[&EventKind::Click]);
+//!     assert_eq!(2, Event::VARIANT_COUNT);
+//! }
+//! ```
+//!
+//! Crates depending on `variation-kind` under another name, or reaching it
+//! through a crate re-exporting it, give the path to use in its place with
+//! `#[variation(crate = "...")]`, such as `crate = "my_facade::kind"`.
+//!
+//! ## `serde` support
+//! The kind enum of `#[variation(kind(serde))]` implements `Serialize` and
+//! `Deserialize` of `serde` as a string, the name of the variant or the one
+//! given with `#[variation(str = "...")]`, which is also allowed on the
+//! variants of such enums. This adds no dependencies to this crate, but the
+//! generated code refers to `::serde`, so the crate deriving the methods
+//! needs to depend on `serde` itself.
+//!
+//! ```ignore
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! #[variation(kind(serde))]
+//! enum Event {
+//!     Click(u32),
+//!     #[variation(str = "key_press")]
+//!     Key(char),
+//! }
+//!
+//! fn main() {
+//!     assert_eq!("\"key_press\"", serde_json::to_string(&EventKind::Key).unwrap());
+//!     assert_eq!(EventKind::Click, serde_json::from_str("\"Click\"").unwrap());
+//! }
+//! ```
+//!
+//! ## `Result`-like enums
+//! Enums that are a `Result` with additional variants can mark their success
+//! variant with `#[variation(ok)]` and their failure variant with
+//! `#[variation(err)]`. This generates `ok` and `err` methods converting the
+//! enum into an `Option` of the variant's inner types, `as_ok` and `as_err`
+//! borrowing them, and `map_ok` and `map_err` transforming them in place, which
+//! leave every other variant untouched. Both markers have to be present, and
+//! each of them can only be used once.
+//!
+//! The enum also implements `From<Result<Ok, Err>>`, and when the marked
+//! variants are its only ones, it gets `into_result` and `as_result` methods
+//! converting it into the equivalent `Result` of its inner types.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! enum Outcome {
+//!     #[variation(ok)]
+//!     Success(u32),
+//!     #[variation(err)]
+//!     Failure(String),
+//!     Pending,
+//! }
+//!
+//! fn main() {
+//!     let success = Outcome::Success(2).map_ok(|value| value * 2);
+//!     let failure = Outcome::Failure(String::from("timeout"));
+//!
+//!     assert_eq!(Some(&4), success.as_ok());
+//!     assert_eq!(None, success.as_err());
+//!     assert_eq!(Some(String::from("timeout")), failure.err());
+//!     assert_eq!(None, Outcome::Pending.ok());
+//!     assert_eq!(Outcome::Success(1), Outcome::from(Ok(1)));
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Outcome {
+//!     #[variation(ok)]
+//!     Success(u32),
+//!     Failure(String),
+//! }
+//! ```
+//!
+//! ## `Option`-like enums
+//! Enums that are an `Option` with a reason, or with additional variants, can
+//! mark the variant holding a value with `#[variation(some)]` and a unit
+//! variant with `#[variation(none)]`. This generates `into_option` and
+//! `as_option` methods converting the enum into an `Option` of the inner types
+//! of the `some` variant, and `unwrap_or` returning them or a default. Every
+//! variant other than `some` counts as `None`. The enum also implements
+//! `From<Option<Some>>`, converting `None` into the `none` variant. Both
+//! markers have to be present, and each of them can only be used once.
+//!
+//! ```rust
+//! use variation::Variation;
+//!
+//! #[derive(Debug, PartialEq, Variation)]
+//! enum Cache {
+//!     #[variation(some)]
+//!     Hit(String),
+//!     #[variation(none)]
+//!     Miss,
+//!     Expired,
+//! }
+//!
+//! fn main() {
+//!     let hit = Cache::from(Some(String::from("value")));
+//!
+//!     assert_eq!(Some(&String::from("value")), hit.as_option());
+//!     assert_eq!(Some(String::from("value")), hit.into_option());
+//!     assert_eq!(Cache::Miss, Cache::from(None));
+//!     assert_eq!("default", Cache::Expired.unwrap_or(String::from("default")));
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use variation::Variation;
+//!
+//! #[derive(Variation)]
+//! enum Cache {
+//!     #[variation(some)]
+//!     Hit(String),
+//!     #[variation(none)]
+//!     Miss(u32),
+//! }
+//! ```
+
+extern crate proc_macro;
+
+mod accessor;
+mod alias;
+mod attr;
+mod augment;
+mod common;
+mod conversions;
+mod default;
+mod delegate;
+mod dispatch;
+mod drain;
+mod dyn_iter;
+mod either;
+mod error;
+mod field_cfg;
+mod fieldless;
+mod flatten;
+mod fold;
+mod homogeneous;
+mod iterator;
+mod kind;
+mod markers;
+mod match_macro;
+mod names;
+mod newtype;
+mod option;
+mod optional;
+mod panic;
+mod partition;
+mod placeholder;
+mod projection;
+mod props;
+mod result;
+mod self_ty;
+mod subset;
+mod visitor;
+
+use std::iter;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::*;
+
+use crate::accessor::Accessor;
+use crate::attr::{ContainerAttrs, FieldAttrs, IntoMode, VariantAttrs};
+
+#[proc_macro_derive(Variation, attributes(variation))]
+pub fn variation_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    impl_variation(&ast, Generator::Derive)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Generates the accessor methods for an enum of another crate in an
+/// extension trait, from a mirror definition of the enum marked with
+/// `#[variation(remote = "...")]`.
+#[proc_macro]
+pub fn variation_remote(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = match syn::parse::<DeriveInput>(input) {
+        Ok(ast) => ast,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    attr::check_remote(&ast)
+        .and_then(|()| impl_variation(&ast, Generator::Remote))
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Generates the methods and impls of `#[derive(Variation)]` for an enum of
+/// the crate that can't be marked with it, from a restatement of the enum
+/// named with its path.
+#[proc_macro]
+pub fn augment(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let augment = match syn::parse::<augment::Augment>(input) {
+        Ok(augment) => augment,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    augment
+        .check()
+        .and_then(|()| impl_variation(&augment.ast, Generator::Augment))
+        .map(|gen| augment.scoped(gen))
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Adds a hidden placeholder variant to the enum, which `take_*` methods
+/// generated by `#[derive(Variation)]` leave behind in place of the variant
+/// they move the inner values out of.
+#[proc_macro_attribute]
+pub fn placeholder(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut ast = match syn::parse::<DeriveInput>(input) {
+        Ok(ast) => ast,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    match placeholder::add_placeholder(args.into(), &mut ast) {
+        Ok(()) => quote!(#ast).into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Records the methods of a trait for the enums marked with
+/// `#[variation(dispatch = "...")]`, which implement it by forwarding them to
+/// the value they hold.
+#[proc_macro_attribute]
+pub fn dispatchable(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    dispatch::dispatchable(args.into(), input.into())
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// What the enum is given to, which `#[derive(Variation)]` and `augment!`
+/// generate everything for and `variation_remote!` only the accessor trait.
+#[derive(Clone, Copy, PartialEq)]
+enum Generator {
+    Derive,
+    Remote,
+    Augment,
+}
+
+impl Generator {
+    fn name(self) -> &'static str {
+        match self {
+            Generator::Derive => "#[derive(Variation)]",
+            Generator::Remote => "variation_remote!",
+            Generator::Augment => "augment!",
+        }
+    }
+}
+
+/// Generates everything for the enum, once for each combination of the
+/// predicates of `#[cfg]` attributes on its fields if it has any.
+fn impl_variation(ast: &syn::DeriveInput, generator: Generator) -> Result<TokenStream> {
+    let predicates = field_cfg::predicates(ast)?;
+
+    if predicates.is_empty() {
+        return impl_configured(ast, generator);
+    }
+
+    let mut gen = TokenStream::new();
+
+    for enabled in 0..1 << predicates.len() {
+        let (configured, cfg) = field_cfg::configure(ast, &predicates, enabled)?;
+        gen.extend(attr_items(impl_configured(&configured, generator)?, &cfg));
+    }
+
+    Ok(gen)
+}
+
+/// Generates everything for the enum, with the fields its configuration
+/// leaves out already removed by `impl_variation`.
+fn impl_configured(ast: &syn::DeriveInput, generator: Generator) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let mut implementation = TokenStream::new();
+    let remote = generator == Generator::Remote;
+
+    let data = match ast.data {
+        Data::Enum(ref s) => s,
+        Data::Struct(ref s) if generator == Generator::Derive => return newtype::impl_newtype(ast, s),
+        _ => {
+            return Err(Error::new(
+                name.span(),
+                format!("`{}` is only available for enums", generator.name()),
+            ))
+        }
+    };
+
+    let container = ContainerAttrs::parse(&ast.attrs)?;
+
+    match container.remote {
+        Some((span, _)) if !remote => {
+            return Err(Error::new(
+                span,
+                "`#[variation(remote = \"...\")]` is only available in `variation_remote!`",
+            ))
+        }
+        None if remote => {
+            return Err(Error::new(
+                name.span(),
+                "`variation_remote!` requires the path of the enum, such as \
+                 `#[variation(remote = \"other_crate::Enum\")]`",
+            ))
+        }
+        _ => {}
+    }
+
+    // The accessors of a remote enum match on the variants through its path,
+    // the mirror definition doesn't exist outside of the macro.
+    let path = match container.remote {
+        Some((_, ref path)) => quote!(#path),
+        None => quote!(#name),
+    };
+    if let Some((span, _)) = container.variants {
+        return Err(Error::new(span, "`#[variation(variants(...))]` is only available for newtype structs"));
+    }
+
+    // The field types are copied into items of their own, such as the
+    // projection enums, where `Self` isn't the enum.
+    let replaced = self_ty::replace(ast, &path, &data.variants);
+    let mut variants = variant_attrs(name, &replaced, &container)?;
+
+    // The accessors match on the fields by position, which variants with
+    // named fields don't have.
+    let named = variants.iter().find(|(variant, attrs)| {
+        let attr::Families { is, as_ref, as_mut, into } = attrs.skip;
+        matches!(variant.fields, Fields::Named(_)) && [is, as_ref, as_mut, into].iter().any(Option::is_none)
+    });
+
+    if let Some((variant, _)) = named {
+        return Err(Error::new_spanned(
+            &variant.fields,
+            format!(
+                "`{}` doesn't support named fields, make `{}` a tuple variant or skip it with \
+                 `#[variation(skip)]`",
+                generator.name(),
+                variant.ident
+            ),
+        ));
+    }
+
+    // The placeholder variant of `take_*` is left out of everything, except
+    // for the exhaustive matches, where it panics or counts as nothing.
+    let placeholder = placeholder::find(&variants)?;
+
+    if let Some(placeholder) = placeholder {
+        attr::check_placeholder(ast)?;
+        variants.retain(|(variant, _)| variant.ident != placeholder.ident);
+    }
+
+    let case = container.rename_all.unwrap_or(names::Case::Snake);
+    let flattened = flatten::find(&variants, &container)?;
+    let forwarded = flattened.iter().flat_map(|flattened| &flattened.listed);
+    names::check_names(variants.iter().chain(forwarded), &container, case, placeholder.is_some())?;
+
+    let attr::MethodVis { is: is_vis, as_ref: as_vis, as_mut: as_mut_vis, into: into_vis } = &container.method_vis;
+    let bounds = &container.method_bounds;
+    let is_where = attr::method_where_clause(&bounds.is);
+    let as_where = attr::method_where_clause(&bounds.as_ref);
+    let as_mut_where = attr::method_where_clause(&bounds.as_mut);
+    let into_where = attr::method_where_clause(&bounds.into);
+    let constness = container.constness();
+    let attr::Families { is: is_must_use, as_ref: as_must_use, as_mut: as_mut_must_use, into: into_must_use } =
+        container.method_must_use();
+
+    let attr::Families { is: is_cfg, as_ref: as_cfg, as_mut: as_mut_cfg, into: into_cfg } =
+        container.method_cfg_attrs();
+    let attr::Families { is: is_attrs, as_ref: as_attrs, as_mut: as_mut_attrs, into: into_attrs } =
+        container.method_extra_attrs();
+    // `try_as_*` and `try_as_*_mut` hand out the kind of the wrong variant,
+    // so they come with the kind enum.
+    let try_as_kind = if container.kind() { Some(kind::kind_ident(name)) } else { None };
+    let mut accessors = Vec::new();
+    let mut panics = false;
+    let static_panic = container.static_panic.is_some();
+
+    // Skipped families are only left out of the methods of the variant's
+    // own, everything describing the enum as a whole still has to cover it.
+    for (variant, attrs) in &variants {
+        let skip = &attrs.skip;
+        let inline = attrs.inline(&container);
+        let extra_attrs = attrs.extra_attrs();
+        let doc = attrs.doc();
+        let variant_name = &variant.ident;
+        let attr::Families { is: is_fn, as_ref: as_fn, as_mut: as_mut_fn, into: into_fn } =
+            names::method_names(variant, attrs, case, &container.method_prefixes);
+        let field_count = variant.fields.iter().count();
+
+        if let Some(ref msg) = attrs.panic_msg {
+            if field_count == 0 || skip.into.is_some() || attrs.mode_of_into(&container) != IntoMode::Panic {
+                return Err(Error::new(
+                    msg.span(),
+                    "`#[variation(panic_msg = \"...\")]` requires a panicking `into_*` method",
+                ));
+            }
+
+            // The edition of the caller decides whether a lone literal of
+            // `panic!` is a format string, so braces can't be escaped.
+            if static_panic && msg.value().contains(['{', '}']) {
+                return Err(Error::new(
+                    msg.span(),
+                    "`#[variation(static_panic)]` requires a `panic_msg` without `{` or `}`, as it becomes the \
+                     literal of `panic!`",
+                ));
+            }
+        }
+
+        if skip.is.is_none() {
+            // A single-variant `matches!` already lowers to one comparison of
+            // the discriminant. `mem::discriminant` would need a value of the
+            // variant to compare with, and emits more IR in debug builds.
+            accessors.push(Accessor {
+                cfg: is_cfg.clone(),
+                attrs: quote!(#doc #is_must_use),
+                impl_attrs: quote!(#inline #extra_attrs #is_attrs),
+                vis: quote!(#is_vis),
+                signature: quote!(#constness fn #is_fn(&self) -> bool #is_where),
+                body: quote!(::core::matches!(self, #path::#variant_name { .. })),
+                consuming: false,
+            });
+        }
+
+        if field_count == 0 {
+            continue;
+        }
+
+        // Only built once the methods are known to be generated, as enums
+        // with hundreds of variants spend most of their expansion on tokens.
+        let value_fields = generate_ident_list_pattern(field_count, false, false);
+        let return_value = inner_value(field_count);
+
+        if skip.as_ref.is_none() {
+            let return_by_ref = inner_ref_type(variant, quote!(&));
+
+            accessors.push(Accessor {
+                cfg: as_cfg.clone(),
+                attrs: quote!(#doc #as_must_use),
+                impl_attrs: quote!(#inline #extra_attrs #as_attrs),
+                vis: quote!(#as_vis),
+                signature: quote!(#constness fn #as_fn(&self) -> ::core::option::Option<#return_by_ref> #as_where),
+                body: quote! {
+                    match self {
+                        #path::#variant_name#value_fields => ::core::option::Option::Some(#return_value),
+                        _ => ::core::option::Option::None,
+                    }
+                },
+                consuming: false,
+            });
+
+            if let Some(ref kind) = try_as_kind {
+                let try_as_fn = format_ident!("try_{}", as_fn);
+
+                accessors.push(Accessor {
+                    cfg: as_cfg.clone(),
+                    attrs: quote! {
+                        #doc
+                        /// Returns the inner type by reference, or the kind of
+                        /// the variant the enum holds in `Err` when this method
+                        /// is called on the wrong enum variant.
+                        #as_must_use
+                    },
+                    impl_attrs: quote!(#inline #extra_attrs #as_attrs),
+                    vis: quote!(#as_vis),
+                    signature: quote! {
+                        fn #try_as_fn(&self) -> ::core::result::Result<#return_by_ref, #kind> #as_where
+                    },
+                    body: quote! {
+                        match self {
+                            #path::#variant_name#value_fields => ::core::result::Result::Ok(#return_value),
+                            other => ::core::result::Result::Err(other.kind()),
+                        }
+                    },
+                    consuming: false,
+                });
+            }
+        }
+
+        if skip.as_mut.is_none() {
+            let return_by_ref_mut = inner_ref_type(variant, quote!(&mut));
+
+            accessors.push(Accessor {
+                cfg: as_mut_cfg.clone(),
+                attrs: quote!(#doc #as_mut_must_use),
+                impl_attrs: quote!(#inline #extra_attrs #as_mut_attrs),
+                vis: quote!(#as_mut_vis),
+                signature: quote!(fn #as_mut_fn(&mut self) -> ::core::option::Option<#return_by_ref_mut> #as_mut_where),
+                body: quote! {
+                    match self {
+                        #path::#variant_name#value_fields => ::core::option::Option::Some(#return_value),
+                        _ => ::core::option::Option::None,
+                    }
+                },
+                consuming: false,
+            });
+
+            if let Some(ref kind) = try_as_kind {
+                let try_as_mut_fn = format_ident!("try_{}", as_mut_fn);
+
+                accessors.push(Accessor {
+                    cfg: as_mut_cfg.clone(),
+                    attrs: quote! {
+                        #doc
+                        /// Returns the inner type by mutable reference, or the
+                        /// kind of the variant the enum holds in `Err` when this
+                        /// method is called on the wrong enum variant.
+                        #as_mut_must_use
+                    },
+                    impl_attrs: quote!(#inline #extra_attrs #as_mut_attrs),
+                    vis: quote!(#as_mut_vis),
+                    signature: quote! {
+                        fn #try_as_mut_fn(&mut self) -> ::core::result::Result<#return_by_ref_mut, #kind> #as_mut_where
+                    },
+                    body: quote! {
+                        match self {
+                            #path::#variant_name#value_fields => ::core::result::Result::Ok(#return_value),
+                            other => ::core::result::Result::Err(other.kind()),
+                        }
+                    },
+                    consuming: false,
+                });
+            }
+        }
+
+        if skip.into.is_some() {
+            continue;
+        }
+
+        let return_by_value = inner_type(variant);
+        let into_mode = attrs.mode_of_into(&container);
+        let into_output = into_mode.output(&return_by_value, quote!(Self));
+        panics |= into_mode == IntoMode::Panic && !static_panic;
+        let into_body = match into_mode {
+            IntoMode::Panic => {
+                let into_panic = panic::wrong_variant(
+                    name,
+                    variant,
+                    &into_fn,
+                    attrs.panic_msg.as_ref(),
+                    remote,
+                    static_panic,
+                );
+
+                quote! {
+                    match self {
+                        #path::#variant_name#value_fields => #return_value,
+                        _ => #into_panic,
+                    }
+                }
+            }
+            IntoMode::Option => quote! {
+                match self {
+                    #path::#variant_name#value_fields => ::core::option::Option::Some(#return_value),
+                    _ => ::core::option::Option::None,
+                }
+            },
+            IntoMode::Result => quote! {
+                match self {
+                    #path::#variant_name#value_fields => ::core::result::Result::Ok(#return_value),
+                    other => ::core::result::Result::Err(other),
+                }
+            },
+        };
+        let into_doc = match into_mode {
+            IntoMode::Panic => quote! {
+                /// Consumes the enum and returns the inner type.
+                /// # Panics
+                /// When this method is called on the wrong enum variant.
+            },
+            IntoMode::Option => quote! {
+                /// Consumes the enum and returns the inner type, or `None`
+                /// when this method is called on the wrong enum variant.
+            },
+            IntoMode::Result => quote! {
+                /// Consumes the enum and returns the inner type, or the enum
+                /// itself in `Err` when this method is called on the wrong
+                /// enum variant.
+            },
+        };
+
+        accessors.push(Accessor {
+            cfg: into_cfg.clone(),
+            attrs: quote!(#doc #into_doc #into_must_use),
+            impl_attrs: quote!(#inline #extra_attrs #into_attrs),
+            vis: quote!(#into_vis),
+            signature: quote!(fn #into_fn(self) -> #into_output #into_where),
+            body: into_body,
+            consuming: true,
+        });
+
+        if container.boxed.is_some() {
+            let into_boxed_fn = Ident::new(&format!("{}_boxed", into_fn), Span::call_site());
+            let into_boxed_output = into_mode.output(&return_by_value, quote!(Box<Self>));
+
+            // Matching on `*self` moves the inner values straight out of the
+            // box, where `into_*` would first move the whole enum onto the
+            // stack. The box itself is only handed back when nothing was
+            // moved out of it.
+            let into_boxed_body = match into_mode {
+                IntoMode::Panic => {
+                    let into_boxed_panic = panic::wrong_variant(
+                        name,
+                        variant,
+                        &into_boxed_fn,
+                        attrs.panic_msg.as_ref(),
+                        remote,
+                        static_panic,
+                    );
+
+                    quote! {
+                        match *self {
+                            #path::#variant_name#value_fields => #return_value,
+                            _ => #into_boxed_panic,
+                        }
+                    }
+                }
+                IntoMode::Option => quote! {
+                    match *self {
+                        #path::#variant_name#value_fields => ::core::option::Option::Some(#return_value),
+                        _ => ::core::option::Option::None,
+                    }
+                },
+                IntoMode::Result => quote! {
+                    if let #path::#variant_name#value_fields = *self {
+                        ::core::result::Result::Ok(#return_value)
+                    } else {
+                        ::core::result::Result::Err(self)
+                    }
+                },
+            };
+            let into_boxed_doc = match into_mode {
+                IntoMode::Panic => quote! {
+                    /// Consumes the boxed enum and returns the inner type,
+                    /// without moving the rest of the enum out of the box.
+                    /// # Panics
+                    /// When this method is called on the wrong enum variant.
+                },
+                IntoMode::Option => quote! {
+                    /// Consumes the boxed enum and returns the inner type,
+                    /// without moving the rest of the enum out of the box, or
+                    /// `None` when this method is called on the wrong enum
+                    /// variant.
+                },
+                IntoMode::Result => quote! {
+                    /// Consumes the boxed enum and returns the inner type,
+                    /// without moving the rest of the enum out of the box, or
+                    /// the box itself in `Err` when this method is called on
+                    /// the wrong enum variant.
+                },
+            };
+
+            accessors.push(Accessor {
+                cfg: into_cfg.clone(),
+                attrs: quote!(#doc #into_boxed_doc #into_must_use),
+                impl_attrs: quote!(#inline #extra_attrs #into_attrs),
+                vis: quote!(#into_vis),
+                signature: quote!(fn #into_boxed_fn(self: Box<Self>) -> #into_boxed_output #into_where),
+                body: into_boxed_body,
+                consuming: true,
+            });
+        }
+    }
+
+    accessors.extend(flatten::accessors(&path, &flattened, &container, case));
+    accessors.extend(alias::methods(&variants, &container, case)?);
+
+    let all_variants = variants.iter().map(|&(variant, _)| variant).collect::<Vec<_>>();
+    let panic_helper = if panics {
+        panic::helper(ast, &path, &all_variants, &into_cfg, remote)
+    } else {
+        TokenStream::new()
+    };
+
+    let trait_name = match (&container.accessor_trait, &container.remote) {
+        (Some(trait_name), _) => Some(trait_name.clone()),
+        (None, Some(_)) => Some(Ident::new(&format!("{}VariationExt", name), Span::call_site())),
+        (None, None) => None,
+    };
+    let accessor_trait = match trait_name {
+        Some(ref trait_name) => accessor::accessor_trait(ast, &container, trait_name, &accessors),
+        None => {
+            implementation.extend(accessor::inherent(&accessors));
+            TokenStream::new()
+        }
+    };
+
+    if remote {
+        return Ok(item_attrs(quote!(#accessor_trait #panic_helper), &container));
+    }
+
+    implementation.extend(names::variant_name(name, &all_variants, placeholder, &container));
+
+    if let Some(placeholder) = placeholder {
+        implementation.extend(placeholder::take_methods(name, &variants, placeholder, &quote!(#into_vis), case));
+    }
+
+    if container.tag.is_some() {
+        implementation.extend(names::tag(name, &variants, case));
+    } else if let Some(tag) = variants.iter().find_map(|(_, attrs)| attrs.tag.as_ref()) {
+        return Err(Error::new(tag.span(), "`#[variation(tag = \"...\")]` requires `#[variation(tag)]` on the enum"));
+    }
+
+    implementation.extend(props::methods(name, &variants));
+
+    if let Some(span) = container.either {
+        implementation.extend(either::methods(name, &all_variants, span)?);
+    }
+
+    if container.fold.is_some() {
+        implementation.extend(fold::either(ast, &all_variants));
+        implementation.extend(fold::fold(ast, &all_variants, case));
+    }
+
+    if let Some(span) = container.into_ok_or_err {
+        let ty = homogeneous::require_common_type(&all_variants, span, "into_ok_or_err")?;
+        implementation.extend(homogeneous::into_ok_or_err(name, &all_variants, ty, span)?);
+    }
+
+    implementation.extend(common::methods(name, &all_variants)?);
+    implementation.extend(dyn_iter::methods(ast, &all_variants, container.dyn_iter.as_ref().map(|(_, path)| path))?);
+
+    if let Some(span) = container.inner {
+        let ty = homogeneous::require_common_type(&all_variants, span, "inner")?;
+        implementation.extend(homogeneous::inner_methods(name, &all_variants, ty));
+    }
+
+    let delegate_impls = delegate::impl_delegates(ast, &all_variants, &container.delegate)?;
+    let dispatch_impls = dispatch::impl_dispatch(ast, &all_variants, &container.dispatch)?;
+    implementation.extend(delegate::delegate_fns(name, &all_variants, &container.delegate_fns)?);
+
+    let deref_impls = match container.deref {
+        Some(span) => {
+            let ty = homogeneous::require_common_type(&all_variants, span, "deref")?;
+            homogeneous::impl_deref(ast, &all_variants, ty)
+        }
+        None => TokenStream::new(),
+    };
+
+    let visitor_traits = if container.visitor.is_some() {
+        implementation.extend(visitor::methods(ast, &all_variants, case));
+        visitor::visitor_traits(ast, &all_variants, case)
+    } else {
+        TokenStream::new()
+    };
+
+    let kind_enum = if container.kind() {
+        implementation.extend(kind::methods(name, &all_variants, placeholder, container.group_by_kind.is_some()));
+        kind::kind_enum(ast, &all_variants)
+    } else {
+        TokenStream::new()
+    };
+    let serde_impls = if container.kind_serde.is_some() {
+        kind::impl_serde(name, &variants)
+    } else {
+        TokenStream::new()
+    };
+    let variant_kind_impl = match (container.variant_kind, &container.crate_path) {
+        (Some(_), _) => kind::impl_variant_kind(ast, &all_variants, &container),
+        (None, Some((span, _))) => {
+            return Err(Error::new(*span, "`#[variation(crate = \"...\")]` requires `#[variation(kind(variant_kind))]`"))
+        }
+        (None, None) => TokenStream::new(),
+    };
+
+    if let Some(span) = container.iter {
+        fieldless::require_fieldless(&all_variants, span, "iter")?;
+        implementation.extend(fieldless::iter_methods(name, &all_variants));
+    }
+
+    if let Some(span) = container.cycle {
+        fieldless::require_fieldless(&all_variants, span, "cycle")?;
+        implementation.extend(fieldless::cycle_methods(name, &all_variants));
+    }
+
+    // The strings also name the kind enum's variants with `kind(serde)`.
+    if container.as_str.is_none() && container.from_str.is_none() && container.kind_serde.is_none() {
+        if let Some(string) = variants.iter().find_map(|(_, attrs)| attrs.str.as_ref()) {
+            return Err(Error::new(
+                string.span(),
+                "`#[variation(str = \"...\")]` requires `#[variation(as_str)]`, `#[variation(from_str)]` or \
+                 `#[variation(kind(serde))]`",
+            ));
+        }
+    }
+
+    if let Some(span) = container.as_str {
+        fieldless::require_fieldless(&all_variants, span, "as_str")?;
+        implementation.extend(fieldless::as_str(name, &variants, span)?);
+    }
+
+    let from_str_impl = match container.from_str {
+        Some(span) => {
+            fieldless::require_fieldless(&all_variants, span, "from_str")?;
+            fieldless::impl_from_str(ast, &variants, span)?
+        }
+        None => TokenStream::new(),
+    };
+
+    let mut try_from_repr_impl = TokenStream::new();
+
+    if let Some((span, attribute)) = container.repr() {
+        fieldless::require_fieldless(&all_variants, span, attribute)?;
+        let repr = fieldless::repr_type(ast, span, attribute)?;
+        implementation.extend(fieldless::repr_methods(name, &all_variants, &repr));
+
+        if container.try_from_repr.is_some() {
+            try_from_repr_impl = fieldless::impl_try_from_repr(ast, &repr);
+        }
+    }
+
+    if container.drain.is_some() {
+        // Draining moves the inner values out like `into_*`.
+        let drained = variants
+            .iter()
+            .filter(|(_, attrs)| attrs.skip.into.is_none())
+            .map(|&(variant, _)| variant)
+            .collect::<Vec<_>>();
+        implementation.extend(drain::methods(name, &drained, case));
+    }
+
+    let companion_derives = match container.companion_derive {
+        Some((span, ref derives)) => {
+            let generated = [
+                container.partition,
+                container.count_kinds,
+                container.markers,
+                container.ref_enum,
+                container.mut_enum,
+                container.pin_project,
+            ];
+
+            if generated.iter().all(Option::is_none) {
+                return Err(Error::new(
+                    span,
+                    "`#[variation(companion_derive(...))]` requires `#[variation(partition)]`, \
+                     `#[variation(count_kinds)]`, `#[variation(markers)]` or one of the projection enums, which \
+                     generate the types it applies to",
+                ));
+            }
+
+            &derives[..]
+        }
+        None => &[],
+    };
+
+    for (variant, attrs) in &variants {
+        if let Some((span, _)) = attrs.companion_derive {
+            if container.markers.is_none() {
+                return Err(Error::new(
+                    span,
+                    "`#[variation(companion_derive(...))]` on a variant requires `#[variation(markers)]` on the enum",
+                ));
+            }
+
+            if variant.fields.iter().count() != 1 {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "`#[variation(companion_derive(...))]` requires `{}` to have a single field, as only those \
+                         get a marker type",
+                        variant.ident
+                    ),
+                ));
+            }
+        }
+    }
+
+    let kind_counts = if container.count_kinds.is_some() {
+        kind::counts_struct(ast, &all_variants, placeholder, case, companion_derives)?
+    } else {
+        TokenStream::new()
+    };
+
+    let markers = if container.markers.is_some() {
+        implementation.extend(markers::methods(name));
+        markers::markers(ast, &variants, companion_derives)
+    } else {
+        TokenStream::new()
+    };
+
+    let match_macro = match container.match_macro {
+        Some((_, ref given)) => {
+            let macro_name = match_macro::macro_ident(name, given.as_ref());
+            match_macro::match_macro(ast, &all_variants, placeholder, &macro_name, case)
+        }
+        None => TokenStream::new(),
+    };
+
+    let partition_struct = if container.partition.is_some() {
+        implementation.extend(partition::methods(ast, &all_variants, case));
+        partition::partition_struct(ast, &all_variants, case, companion_derives)
+    } else {
+        TokenStream::new()
+    };
+
+    let iterator_ext = if container.iterator_ext.is_some() {
+        // The adapters hand out the inner values of owned items like
+        // `into_*` and borrow those of references like `as_*`.
+        let adapted = variants
+            .iter()
+            .filter(|(_, attrs)| attrs.skip.as_ref.is_none() && attrs.skip.into.is_none())
+            .map(|&(variant, _)| variant)
+            .collect::<Vec<_>>();
+        iterator::iterator_ext(ast, &adapted, case)
+    } else {
+        TokenStream::new()
+    };
+
+    let mut projection_enums = TokenStream::new();
+
+    let projections: [(_, _, _, fn(_, _, _) -> _); 2] = [
+        (container.ref_enum, "ref_enum", "as_ref", projection::ref_enum),
+        (container.mut_enum, "mut_enum", "as_mut", projection::mut_enum),
+    ];
+
+    for &(flag, attribute, method, generate) in &projections {
+        let span = match flag {
+            Some(span) => span,
+            None => continue,
+        };
+
+        if let Some((variant, _)) = variants.iter().find(|(_, attrs)| attrs.as_ref.is_some()) {
+            return Err(Error::new(
+                span,
+                format!(
+                    "`#[variation({})]` can't be combined with `#[variation(as_ref)]` on `{}`, \
+                     as both generate an `{}` method",
+                    attribute, variant.ident, method
+                ),
+            ));
+        }
+
+        let (method, projection_enum) = generate(ast, &all_variants, companion_derives)?;
+        implementation.extend(method);
+        projection_enums.extend(projection_enum);
+    }
+
+    // The `Future` impl polls every inner value pinned, so handing one out
+    // from the projection as a plain `&mut` would let it be moved.
+    let delegates_future = container.delegate.iter().any(|(_, delegate)| delegate.name() == "Future");
+
+    for variant in &all_variants {
+        for field in &variant.fields {
+            match (FieldAttrs::parse(&field.attrs)?.pin, container.pin_project) {
+                (Some(span), None) => {
+                    return Err(Error::new(span, "`#[variation(pin)]` requires `#[variation(pin_project)]` on the enum"))
+                }
+                (None, Some(_)) if delegates_future => {
+                    return Err(Error::new_spanned(
+                        field,
+                        format!(
+                            "`delegate(Future)` pins every inner value, so the field of `{}` has to be marked \
+                             `#[variation(pin)]`",
+                            variant.ident
+                        ),
+                    ))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if container.pin_project.is_some() {
+        let (method, projection_enum) = projection::pin_project(ast, &all_variants, companion_derives, delegates_future)?;
+        implementation.extend(method);
+        projection_enums.extend(projection_enum);
+    }
+
+    let option_ext = if container.option_ext.is_some() {
+        option::option_ext(ast, &variants, case, &container.method_prefixes)
+    } else {
+        TokenStream::new()
+    };
+
+    let result_variants = result::ResultVariants::find(&variants)?;
+
+    if let Some(ref result) = result_variants {
+        implementation.extend(result::methods(name, result, case));
+    }
+
+    let option_variants = optional::OptionVariants::find(&variants)?;
+
+    if let Some(ref option) = option_variants {
+        implementation.extend(optional::methods(name, option));
+    }
+
+    let foreign_from = match (&result_variants, &option_variants) {
+        (Some(_), _) => Some("the `From<Result<..>>` impl of the `ok` and `err` variants"),
+        (None, Some(_)) => Some("the `From<Option<..>>` impl of the `some` and `none` variants"),
+        (None, None) => None,
+    };
+    let from_impls = conversions::impl_from(ast, &variants, foreign_from)?;
+    let try_into_impls = conversions::impl_try_into(ast, &variants)?;
+    let try_into_ref_impls = conversions::impl_try_into_ref(ast, &variants)?;
+    let partial_eq_impls = conversions::impl_partial_eq(ast, &variants)?;
+    let subset_impls = match container.subset_of {
+        Some((_, ref superset)) => conversions::impl_subset_of(ast, &all_variants, placeholder, superset),
+        None => TokenStream::new(),
+    };
+    let subset_enums = subset::subsets(ast, &all_variants, &container.subsets)?;
+    let as_ref_impls = conversions::impl_as_ref(ast, &variants)?;
+    let borrow_impls = conversions::impl_borrow(ast, &variants)?;
+    let default_impl = default::impl_default(ast, &variants)?;
+    let delegates_display = container.delegate.iter().any(|(_, delegate)| delegate.name() == "Display");
+    let display_impl = match container.display {
+        Some(span) => {
+            if delegates_display {
+                return Err(Error::new(
+                    span,
+                    "`#[variation(display)]` can't be combined with `delegate(Display)`, as both implement `Display`",
+                ));
+            }
+
+            names::impl_display(ast)
+        }
+        None => TokenStream::new(),
+    };
+
+    if container.error.is_none() {
+        if let Some(msg) = variants.iter().find_map(|(_, attrs)| attrs.error_msg.as_ref()) {
+            return Err(Error::new(
+                msg.span(),
+                "`#[variation(error(msg = \"...\"))]` requires `#[variation(error)]` on the enum",
+            ));
+        }
+    }
+
+    let error_impl = match container.error {
+        Some(span) => {
+            if container.display.is_some() || delegates_display {
+                return Err(Error::new(
+                    span,
+                    "`#[variation(error)]` implements `Display` itself, so it can't be combined with \
+                     `#[variation(display)]` or `delegate(Display)`",
+                ));
+            }
+
+            error::impl_error(ast, &variants, span)?
+        }
+        None => TokenStream::new(),
+    };
+    let debug_impl = if container.debug_redacted.is_some() {
+        names::impl_debug_redacted(ast, &all_variants)
+    } else {
+        TokenStream::new()
+    };
+    let from_result_impls = match result_variants {
+        Some(ref result) => result::impl_from_result(ast, result),
+        None => TokenStream::new(),
+    };
+    let from_option_impls = match option_variants {
+        Some(ref option) => optional::impl_from_option(ast, option),
+        None => TokenStream::new(),
+    };
+    let mut generics = ast.generics.clone();
+    generics.make_where_clause().predicates.extend(container.bounds.iter().cloned());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let gen = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #implementation
+        }
+
+        #accessor_trait
+        #panic_helper
+        #from_impls
+        #try_into_impls
+        #try_into_ref_impls
+        #partial_eq_impls
+        #subset_impls
+        #as_ref_impls
+        #borrow_impls
+        #default_impl
+        #display_impl
+        #error_impl
+        #debug_impl
+        #try_from_repr_impl
+        #from_str_impl
+        #deref_impls
+        #delegate_impls
+        #dispatch_impls
+        #from_result_impls
+        #from_option_impls
+        #visitor_traits
+        #iterator_ext
+        #option_ext
+        #projection_enums
+        #partition_struct
+        #subset_enums
+        #markers
+        #match_macro
+        #kind_enum
+        #variant_kind_impl
+        #serde_impls
+        #kind_counts
+    };
+
+    Ok(item_attrs(gen, &container))
+}
+
+/// Parses the options of `variants`, with the families the enum leaves out
+/// with `#[variation(methods(...))]` and `#[variation(only(...))]` skipped.
+fn variant_attrs<'a>(
+    name: &Ident,
+    variants: impl IntoIterator<Item = &'a Variant> + Clone,
+    container: &ContainerAttrs,
+) -> Result<Vec<(&'a Variant, VariantAttrs)>> {
+    let parsed = variants
+        .clone()
+        .into_iter()
+        .map(|variant| {
+            let mut attrs = VariantAttrs::parse(&variant.attrs)?;
+            attrs.skip.extend(&container.skip);
+            attrs.forbid_panics(&variant.attrs, container)?;
+
+            if let Some((span, ref only)) = container.only {
+                if !only.contains(&variant.ident) {
+                    attrs.skip = attr::Skipped::all(span);
+                }
+            }
+
+            Ok((variant, attrs))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if let Some((_, ref only)) = container.only {
+        for (i, listed) in only.iter().enumerate() {
+            if !variants.clone().into_iter().any(|variant| variant.ident == *listed) {
+                return Err(Error::new(listed.span(), format!("`{}` is not a variant of `{}`", listed, name)));
+            }
+
+            if only[..i].contains(listed) {
+                return Err(Error::new(listed.span(), format!("`{}` is listed twice", listed)));
+            }
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Puts the `#[cfg]` of `#[variation(cfg(...))]` and the attributes of
+/// `#[variation(attr(...))]` on every item of `gen`.
+fn item_attrs(gen: TokenStream, container: &ContainerAttrs) -> TokenStream {
+    let cfg = container.cfg.iter();
+    let attrs = &container.attrs;
+    let item_attrs = quote!(#(#[cfg(#cfg)])* #(#[#attrs])*);
+
+    if item_attrs.is_empty() {
+        gen
+    } else {
+        attr_items(gen, &item_attrs)
+    }
+}
+
+/// Puts `attrs` on every item of `items`. An item ends with its body in
+/// braces, along with a following `;` like that of `const _: () = {};`, or
+/// with a `;` outside of any braces like that of a tuple struct.
+fn attr_items(items: TokenStream, attrs: &TokenStream) -> TokenStream {
+    let mut attributed = TokenStream::new();
+    let mut item = TokenStream::new();
+    let mut tokens = items.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        let ends_item = match token {
+            proc_macro2::TokenTree::Group(ref group) => group.delimiter() == proc_macro2::Delimiter::Brace,
+            proc_macro2::TokenTree::Punct(ref punct) => punct.as_char() == ';',
+            _ => false,
+        };
+        item.extend(Some(token));
+
+        if !ends_item {
+            continue;
+        }
+
+        if let Some(proc_macro2::TokenTree::Punct(ref punct)) = tokens.peek() {
+            if punct.as_char() == ';' {
+                item.extend(tokens.next());
+            }
+        }
+
+        attributed.extend(quote! {
+            #attrs
+            #item
+        });
+        item = TokenStream::new();
+    }
+
+    attributed.extend(item);
+    attributed
+}
+
+/// The type a variant's fields are moved out as, the field's own type for
+/// single field variants and a tuple of the field types otherwise.
+fn inner_type(variant: &Variant) -> TokenStream {
+    match variant.fields.iter().count() {
+        0 => quote!(),
+        1 => variant.fields.iter().next().unwrap().ty.clone().into_token_stream(),
+        _ => {
+            let types = variant.fields.iter().map(|field| grouped_type(&field.ty));
+            quote!((#(#types,)*))
+        }
+    }
+}
+
+/// The type a variant's fields are borrowed as, where `reference` is the
+/// reference token sequence such as `&` or `&'a mut`.
+fn inner_ref_type(variant: &Variant, reference: TokenStream) -> TokenStream {
+    match variant.fields.iter().count() {
+        0 => quote!(),
+        1 => reference_type(&reference, &variant.fields.iter().next().unwrap().ty),
+        _ => {
+            let types = variant.fields.iter().map(|field| reference_type(&reference, &field.ty));
+            quote!((#(#types,)*))
+        }
+    }
+}
+
+/// A type to place in a tuple or behind a reference, in parentheses if it's a
+/// bare `fn` or a `dyn` or an `impl` type with more than one bound, which
+/// read on into a `+` following them.
+fn grouped_type(ty: &Type) -> TokenStream {
+    match *ty {
+        Type::BareFn(_) => quote!((#ty)),
+        Type::TraitObject(ref object) if object.bounds.len() > 1 => quote!((#ty)),
+        Type::ImplTrait(ref object) if object.bounds.len() > 1 => quote!((#ty)),
+        _ => ty.into_token_stream(),
+    }
+}
+
+/// A reference to `ty`, where `reference` is the reference token sequence
+/// such as `&` or `&'a mut`, so `&(dyn Error + Send)` rather than
+/// `&dyn Error + Send`.
+fn reference_type(reference: &TokenStream, ty: &Type) -> TokenStream {
+    let ty = grouped_type(ty);
+    quote!(#reference #ty)
+}
+
+/// The pieces needed to convert between a variant and its inner types, where
+/// unit variants convert into `()`.
+pub(crate) struct Conversion {
+    /// `Enum::Variant(v0, ..)`, both a pattern and a constructor.
+    pub pattern: TokenStream,
+    /// `Enum::Variant(ref v0, ..)`.
+    pub ref_pattern: TokenStream,
+    pub inner: TokenStream,
+    pub inner_ref: TokenStream,
+    /// The inner values bound by `pattern`.
+    pub value: TokenStream,
+    /// The inner values bound by `ref_pattern`.
+    pub ref_value: TokenStream,
+}
+
+impl Conversion {
+    pub(crate) fn new(name: &Ident, variant: &Variant) -> Self {
+        let variant_name = &variant.ident;
+        let field_count = variant.fields.iter().count();
+        let value_fields = generate_ident_list_pattern(field_count, false, false);
+        let ref_fields = generate_ident_list_pattern(field_count, true, false);
+
+        if field_count == 0 {
+            return Conversion {
+                pattern: quote!(#name::#variant_name),
+                ref_pattern: quote!(#name::#variant_name),
+                inner: quote!(()),
+                inner_ref: quote!(&()),
+                value: quote!(()),
+                ref_value: quote!(&()),
+            };
+        }
+
+        Conversion {
+            pattern: quote!(#name::#variant_name#value_fields),
+            ref_pattern: quote!(#name::#variant_name#ref_fields),
+            inner: inner_type(variant),
+            inner_ref: inner_ref_type(variant, quote!(&)),
+            value: inner_value(field_count),
+            ref_value: inner_value(field_count),
+        }
+    }
+}
+
+/// The expression (or pattern) matching `inner_type` over the bindings
+/// created by `generate_ident_list_pattern`.
+fn inner_value(count: usize) -> TokenStream {
+    match count {
+        0 => quote!(),
+        1 => Ident::new("v0", Span::call_site()).into_token_stream(),
+        _ => generate_ident_list_pattern(count, false, false),
+    }
+}
+
+/// The keywords of any edition, which identifiers can't be.
+const KEYWORDS: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro",
+    "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self", "static", "struct", "super",
+    "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// A type parameter for a generated method named `base`, with underscores
+/// appended until it doesn't clash with one of the enum's.
+fn fresh_type_param(generics: &Generics, base: &str) -> Ident {
+    let mut name = base.to_owned();
+
+    while generics.type_params().any(|param| param.ident == name) {
+        name.push('_');
+    }
+
+    Ident::new(&name, Span::call_site())
+}
+
+/// The `#[derive(...)]` of a generated struct, with the traits it always
+/// derives followed by those of `#[variation(companion_derive(...))]`, leaving
+/// out any it already derives.
+fn companion_derive(own: &[&str], extra: &[Path]) -> TokenStream {
+    let extra = extra.iter().filter(|path| match path.segments.last() {
+        Some(last) => !own.iter().any(|&trait_name| last.ident == trait_name),
+        None => true,
+    });
+    let own = own.iter().map(|trait_name| Ident::new(trait_name, Span::call_site()));
+
+    quote!(#[derive(#(#own,)* #(#extra),*)])
+}
+
+/// An identifier for `name`, made raw if it's a keyword.
+fn ident(name: &str) -> Ident {
+    // Parsing goes through the compiler, which only keywords and unusual
+    // names need.
+    let plain = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&name);
+
+    if plain {
+        return Ident::new(name, Span::call_site());
+    }
+
+    syn::parse_str(name)
+        .or_else(|_| syn::parse_str(&format!("r#{}", name)))
+        .unwrap_or_else(|_| Ident::new(&format!("{}_", name), Span::call_site()))
+}
+
+/// The method stem of a variant as an identifier, like the `foo_bar` in
+/// `is_foo_bar`, for places where it is used on its own.
+fn stem_ident(variant: &Variant, case: names::Case) -> Ident {
+    ident(&names::method_stem(variant, case))
+}
+
+fn generate_ident_list_pattern(count: usize, refed: bool, mutable: bool) -> TokenStream {
+    if count == 0 {
+        return TokenStream::new();
+    }
+
+    let binding = match (refed, mutable) {
+        (true, true) => quote!(ref mut),
+        (true, false) => quote!(ref),
+        (false, true) => quote!(mut),
+        (false, false) => TokenStream::new(),
+    };
+    let bindings = iter::repeat(binding);
+    let fields = field_idents(count);
+
+    quote!((#(#bindings #fields,)*))
+}
+
+/// The bindings `v0`, `v1` and so on of the fields of a variant.
+fn field_idents(count: usize) -> impl Iterator<Item = Ident> {
+    (0..count).map(|i| Ident::new(&format!("v{}", i), Span::call_site()))
 }
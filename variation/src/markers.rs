@@ -0,0 +1,143 @@
+//! The `{enum}_markers` module for `#[variation(markers)]`, with a marker
+//! type for each variant to pick it with `get::<V>()` in generic code.
+
+use heck::SnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::attr::VariantAttrs;
+use crate::companion_derive;
+
+pub(crate) fn markers_ident(name: &Ident) -> Ident {
+    Ident::new(&format!("{}_markers", name.to_string().to_snake_case()), Span::call_site())
+}
+
+/// Generates the module with the `Variant` trait and a marker for every
+/// variant with a single field, and the trait impls of the markers. The
+/// markers derive the traits of `#[variation(companion_derive(...))]` on the
+/// variant, or else on the enum, which are named from the module, so it
+/// imports everything of its parent when there are any.
+pub(crate) fn markers(ast: &DeriveInput, variants: &[(&Variant, VariantAttrs)], derives: &[Path]) -> TokenStream {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let module = markers_ident(name);
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let module_doc = format!("The variants of [`{}`] as types, to use with its `get` methods.", name);
+    let mut marker_types = TokenStream::new();
+    let mut impls = TokenStream::new();
+    let mut derives_extra = false;
+
+    for (variant, attrs) in variants.iter().filter(|(variant, _)| variant.fields.iter().count() == 1) {
+        let variant_name = &variant.ident;
+        let ty = &variant.fields.iter().next().unwrap().ty;
+        let doc = format!("The `{}` variant of [`{}`](super::{}).", variant_name, name, name);
+
+        let derives = attrs.companion_derive.as_ref().map_or(derives, |(_, derives)| derives);
+        let derive = companion_derive(&["Clone", "Copy", "Debug"], derives);
+        derives_extra |= !derives.is_empty();
+
+        marker_types.extend(quote! {
+            #[doc = #doc]
+            #derive
+            pub struct #variant_name;
+        });
+        impls.extend(quote! {
+            impl #impl_generics #module::Variant<#name #ty_generics> for #module::#variant_name #where_clause {
+                type Output = #ty;
+
+                #[allow(unreachable_patterns)]
+                fn get<'variation>(
+                    value: &'variation #name #ty_generics,
+                ) -> ::std::option::Option<&'variation Self::Output> {
+                    match value {
+                        #name::#variant_name(v0) => ::std::option::Option::Some(v0),
+                        _ => ::std::option::Option::None,
+                    }
+                }
+
+                #[allow(unreachable_patterns)]
+                fn get_mut<'variation>(
+                    value: &'variation mut #name #ty_generics,
+                ) -> ::std::option::Option<&'variation mut Self::Output> {
+                    match value {
+                        #name::#variant_name(v0) => ::std::option::Option::Some(v0),
+                        _ => ::std::option::Option::None,
+                    }
+                }
+
+                #[allow(unreachable_patterns)]
+                fn into_variant(
+                    value: #name #ty_generics,
+                ) -> ::std::result::Result<Self::Output, #name #ty_generics> {
+                    match value {
+                        #name::#variant_name(v0) => ::std::result::Result::Ok(v0),
+                        value => ::std::result::Result::Err(value),
+                    }
+                }
+            }
+        });
+    }
+
+    let parent = if derives_extra {
+        quote! {
+            #[allow(unused_imports)]
+            use super::*;
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        #[doc = #module_doc]
+        #vis mod #module {
+            #parent
+
+            /// A variant of the enum `E` with a single field, picking it in
+            /// the `get`, `get_mut` and `into_variant` methods of the enum.
+            pub trait Variant<E> {
+                /// The type of the variant's field.
+                type Output;
+
+                /// Returns the field if `value` holds the variant.
+                fn get<'variation>(value: &'variation E) -> ::std::option::Option<&'variation Self::Output>;
+
+                /// Returns the field mutably if `value` holds the variant.
+                fn get_mut<'variation>(value: &'variation mut E) -> ::std::option::Option<&'variation mut Self::Output>;
+
+                /// Moves the field out if `value` holds the variant, or hands
+                /// `value` back.
+                fn into_variant(value: E) -> ::std::result::Result<Self::Output, E>;
+            }
+
+            #marker_types
+        }
+
+        #impls
+    }
+}
+
+/// Generates `get`, `get_mut` and `into_variant`, generic over the markers.
+pub(crate) fn methods(name: &Ident) -> TokenStream {
+    let module = markers_ident(name);
+
+    quote! {
+        /// Returns the inner value of the variant `V` names, or `None` for
+        /// other variants.
+        pub fn get<V: #module::Variant<Self>>(&self) -> ::std::option::Option<&V::Output> {
+            V::get(self)
+        }
+
+        /// Returns the inner value of the variant `V` names mutably, or
+        /// `None` for other variants.
+        pub fn get_mut<V: #module::Variant<Self>>(&mut self) -> ::std::option::Option<&mut V::Output> {
+            V::get_mut(self)
+        }
+
+        /// Moves out the inner value of the variant `V` names, or returns the
+        /// enum itself in `Err` for other variants.
+        pub fn into_variant<V: #module::Variant<Self>>(self) -> ::std::result::Result<V::Output, Self> {
+            V::into_variant(self)
+        }
+    }
+}
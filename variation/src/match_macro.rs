@@ -0,0 +1,83 @@
+//! The macro of `#[variation(match_macro)]`, expanding to a match with an
+//! arm for every variant, so that matching on the enum breaks when a variant
+//! is added.
+
+use heck::SnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::*;
+
+use crate::names::{method_stem, Case};
+use crate::placeholder::taken_arm;
+
+/// The name of the macro, `match_{enum}` unless it's given.
+pub(crate) fn macro_ident(name: &Ident, given: Option<&Ident>) -> Ident {
+    match given {
+        Some(given) => given.clone(),
+        None => Ident::new(&format!("match_{}", name.to_string().to_snake_case()), Span::call_site()),
+    }
+}
+
+/// Generates the macro, taking the value and the arms named after the
+/// variants in the order they're declared, and imports it so other modules
+/// of the crate can use it by path. The fields of every variant are matched
+/// by position, even named ones, with the metavariables numbered after the
+/// variant and the field so that they can't collide.
+pub(crate) fn match_macro(
+    ast: &DeriveInput,
+    variants: &[&Variant],
+    placeholder: Option<&Variant>,
+    macro_name: &Ident,
+    case: Case,
+) -> TokenStream {
+    let name = &ast.ident;
+    let mut matchers = Vec::new();
+    let mut arms = Vec::new();
+
+    for (i, variant) in variants.iter().enumerate() {
+        let variant_name = &variant.ident;
+        let stem = format_ident!("{}", method_stem(variant, case));
+        let body = format_ident!("__e{}", i);
+        let patterns = (0..variant.fields.iter().count()).map(|j| format_ident!("__p{}_{}", i, j)).collect::<Vec<_>>();
+
+        let (params, pattern) = match variant.fields {
+            Fields::Unit => (TokenStream::new(), quote!(#name::#variant_name)),
+            Fields::Unnamed(_) => {
+                (quote!((#($#patterns:pat),*)), quote!(#name::#variant_name(#($#patterns),*)))
+            }
+            Fields::Named(ref fields) => {
+                let field_names = fields.named.iter().map(|field| &field.ident);
+                (
+                    quote!((#($#patterns:pat),*)),
+                    quote!(#name::#variant_name { #(#field_names: $#patterns),* }),
+                )
+            }
+        };
+
+        matchers.push(quote!(#stem #params => $#body:expr));
+        arms.push(quote!(#pattern => $#body,));
+    }
+
+    let taken = taken_arm(name, placeholder, &format!("{}!", macro_name));
+    let doc = format!(
+        "Matches a [`{}`] with an arm for each variant, named `variant` or `variant(pattern, ...)` in the order \
+         they're declared.",
+        name
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {
+            ($value:expr, { #(#matchers),* $(,)? }) => {
+                match $value {
+                    #(#arms)*
+                    #taken
+                }
+            };
+        }
+
+        #[allow(unused_imports)]
+        pub(crate) use #macro_name;
+    }
+}
@@ -0,0 +1,373 @@
+//! The names of variants as strings, `variant_name`, `tag` and the
+//! `#[variation(display)]` and `#[variation(debug = "redacted")]` impls.
+
+use std::collections::HashMap;
+
+use heck::{CamelCase, SnakeCase};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::attr::{ContainerAttrs, Families, MethodPrefixes, VariantAttrs};
+use crate::placeholder::taken_arm;
+
+/// The name of a variant as written, without the `r#` of raw identifiers.
+fn ident_str(variant: &Variant) -> String {
+    let name = variant.ident.to_string();
+
+    match name.starts_with("r#") {
+        true => name[2..].to_owned(),
+        false => name,
+    }
+}
+
+/// The name generated names of a variant are derived from, the one given
+/// with `#[variation(rename = "...")]` or its own.
+pub(crate) fn variant_str(variant: &Variant) -> String {
+    // The attributes were already checked by `impl_variation`.
+    match VariantAttrs::parse(&variant.attrs) {
+        Ok(VariantAttrs { rename: Some(rename), .. }) => rename.value(),
+        _ => ident_str(variant),
+    }
+}
+
+/// How the names of variants become method stems, set with
+/// `#[variation(rename_all = "...")]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Case {
+    /// `FooBar` becomes `foo_bar`, the default.
+    Snake,
+    /// `FooBar` becomes `foobar`.
+    Lower,
+    /// `FooBar` stays `FooBar`, for variants already named in snake case.
+    Verbatim,
+}
+
+impl Case {
+    pub(crate) fn parse(value: &LitStr) -> Result<Self> {
+        match &*value.value() {
+            "snake_case" => Ok(Case::Snake),
+            "lowercase" => Ok(Case::Lower),
+            "verbatim" => Ok(Case::Verbatim),
+            other => Err(Error::new(
+                value.span(),
+                format!(
+                    "unknown `rename_all` style `{}`, expected `snake_case`, `lowercase` or `verbatim`",
+                    other
+                ),
+            )),
+        }
+    }
+
+    fn apply(self, name: &str) -> String {
+        match self {
+            Case::Snake => name.to_snake_case(),
+            Case::Lower => name.to_lowercase(),
+            Case::Verbatim => name.to_owned(),
+        }
+    }
+}
+
+/// The name of a variant in method names, like the `foo_bar` in
+/// `is_foo_bar`.
+pub(crate) fn method_stem(variant: &Variant, case: Case) -> String {
+    case.apply(&variant_str(variant))
+}
+
+/// The variant of the `{Enum}Kind` enum for a variant, named after it unless
+/// it's renamed.
+pub(crate) fn kind_variant(variant: &Variant) -> Ident {
+    match VariantAttrs::parse(&variant.attrs) {
+        Ok(VariantAttrs { rename: Some(rename), .. }) => Ident::new(&rename.value().to_camel_case(), rename.span()),
+        _ => variant.ident.clone(),
+    }
+}
+
+/// The methods of the whole enum, which the names given to the methods of a
+/// variant can't take.
+const ENUM_METHODS: &[&str] = &[
+    "accept", "accept_owned", "as_either", "as_err", "as_inner", "as_inner_mut", "as_mut", "as_ok", "as_option",
+    "as_ref", "as_result", "as_str", "either", "either_ref", "err", "fold", "fold_ref", "from_either", "from_repr",
+    "get", "get_mut", "get_property", "group_by_kind", "into_either", "into_inner", "into_ok_or_err", "into_option",
+    "into_result", "into_variant", "iter", "kind", "map_err", "map_inner", "map_inner_mut", "map_ok", "next", "ok",
+    "partition_variants", "prev", "project", "properties", "retain_kind", "tag", "to_repr", "unwrap_or", "variant_name",
+];
+
+/// The names of the `is_*`, `as_*`, `as_*_mut` and `into_*` methods for
+/// `stem`, with the prefixes set with `#[variation(prefix(...))]`.
+pub(crate) fn family_names(stem: &str, prefixes: &MethodPrefixes) -> Families<String> {
+    let name = |prefix: &Option<LitStr>, default: String| match prefix {
+        Some(prefix) => format!("{}{}", prefix.value(), stem),
+        None => default,
+    };
+
+    Families {
+        is: name(&prefixes.is, format!("is_{}", stem)),
+        as_ref: name(&prefixes.as_ref, format!("as_{}", stem)),
+        as_mut: name(&prefixes.as_mut, format!("as_{}_mut", stem)),
+        into: name(&prefixes.into, format!("into_{}", stem)),
+    }
+}
+
+/// The names of the `is_*`, `as_*`, `as_*_mut` and `into_*` methods of a
+/// variant, the ones given with `#[variation(is = "...")]` and so on or those
+/// derived from its name.
+pub(crate) fn method_names(variant: &Variant, attrs: &VariantAttrs, case: Case, prefixes: &MethodPrefixes) -> Families<Ident> {
+    let derived = family_names(&method_stem(variant, case), prefixes);
+    let name = |given: &Option<LitStr>, derived: String| {
+        Ident::new(&given.as_ref().map_or(derived, LitStr::value), Span::call_site())
+    };
+    let given = &attrs.method_names;
+
+    Families {
+        is: name(&given.is, derived.is),
+        as_ref: name(&given.as_ref, derived.as_ref),
+        as_mut: name(&given.as_mut, derived.as_mut),
+        into: name(&given.into, derived.into),
+    }
+}
+
+/// Rejects renames and method names that aren't valid identifiers, variants
+/// whose method or kind names are the same, and methods of the variants that
+/// share a name. Method names that are set, given to a variant or through a
+/// prefix, can't take the name of a method of the whole enum either.
+pub(crate) fn check_names<'a>(
+    variants: impl IntoIterator<Item = &'a (&'a Variant, VariantAttrs)>,
+    container: &ContainerAttrs,
+    case: Case,
+    take: bool,
+) -> Result<()> {
+    let prefixes = &container.method_prefixes;
+    let mut stems = HashMap::new();
+    let mut kinds = HashMap::new();
+    let mut methods = HashMap::new();
+
+    for (variant, attrs) in variants {
+        if let Some(ref rename) = attrs.rename {
+            let stem = case.apply(&rename.value());
+
+            if stem.is_empty()
+                || syn::parse_str::<Ident>(&format!("is_{}", stem)).is_err()
+                || syn::parse_str::<Ident>(&rename.value().to_camel_case()).is_err()
+            {
+                return Err(Error::new(
+                    rename.span(),
+                    format!("`{}` is not a valid name for `{}`", rename.value(), variant.ident),
+                ));
+            }
+        }
+
+        let rename = attrs.rename.as_ref().map(LitStr::span);
+        insert_name(&mut stems, method_stem(variant, case), variant, rename)?;
+        insert_name(&mut kinds, kind_variant(variant).to_string(), variant, rename)?;
+
+        let skip = &attrs.skip;
+        let has_fields = variant.fields.iter().count() != 0;
+        let generated = Families {
+            is: skip.is.is_none(),
+            as_ref: has_fields && skip.as_ref.is_none(),
+            as_mut: has_fields && skip.as_mut.is_none(),
+            into: has_fields && skip.into.is_none(),
+        };
+        let given = &attrs.method_names;
+        let derived = family_names(&method_stem(variant, case), prefixes);
+        // `set` is whether the name is set rather than the default one, which
+        // only makes it conflict through the variant's name.
+        let mut add = |name: String, span: Option<Span>, set: bool, boxed: bool| -> Result<()> {
+            if set {
+                let span = span.unwrap_or_else(|| variant.ident.span());
+
+                if syn::parse_str::<Ident>(&name).is_err() {
+                    return Err(Error::new(span, format!("`{}` is not a valid method name", name)));
+                }
+
+                if ENUM_METHODS.contains(&&*name) {
+                    return Err(Error::new(
+                        span,
+                        format!("conflicting method name, `{}` is a method of the whole enum", name),
+                    ));
+                }
+            }
+
+            if boxed && container.boxed.is_some() {
+                insert_name(&mut methods, format!("{}_boxed", name), variant, span)?;
+            }
+
+            insert_name(&mut methods, name, variant, span)
+        };
+
+        let families = [
+            (generated.is, &given.is, &prefixes.is, derived.is, false),
+            (generated.as_ref, &given.as_ref, &prefixes.as_ref, derived.as_ref, false),
+            (generated.as_mut, &given.as_mut, &prefixes.as_mut, derived.as_mut, false),
+            (generated.into, &given.into, &prefixes.into, derived.into, true),
+        ];
+
+        for (generated, given, prefix, derived, boxed) in families.iter().cloned() {
+            if !generated {
+                continue;
+            }
+
+            match (given, prefix) {
+                (Some(given), _) => add(given.value(), Some(given.span()), true, boxed)?,
+                (None, Some(prefix)) => add(derived, Some(prefix.span()), true, boxed)?,
+                (None, None) => add(derived, None, false, boxed)?,
+            }
+        }
+
+        // The kind enum comes with `try_as_*` and `try_as_*_mut`, named after
+        // `as_*` and `as_*_mut`.
+        if container.kind() {
+            let names = method_names(variant, attrs, case, prefixes);
+
+            if generated.as_ref {
+                add(format!("try_{}", names.as_ref), None, false, false)?;
+            }
+
+            if generated.as_mut {
+                add(format!("try_{}", names.as_mut), None, false, false)?;
+            }
+        }
+
+        if take && generated.into {
+            add(format!("take_{}", method_stem(variant, case)), None, false, false)?;
+        }
+
+        for alias in &attrs.aliases {
+            let alias_name = alias.value().to_snake_case();
+
+            // Rejected by `alias::methods`.
+            if alias_name == method_stem(variant, case) {
+                continue;
+            }
+
+            let aliases = family_names(&alias_name, prefixes);
+            let families = [
+                (generated.is, &prefixes.is, aliases.is),
+                (generated.as_ref, &prefixes.as_ref, aliases.as_ref),
+                (generated.as_mut, &prefixes.as_mut, aliases.as_mut),
+                (generated.into, &prefixes.into, aliases.into),
+            ];
+
+            for (generated, prefix, name) in families.iter().cloned() {
+                if generated {
+                    add(name, Some(alias.span()), prefix.is_some(), false)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Records that `variant` uses `name`, given by the attribute at `span` if
+/// any. A conflict points at that attribute, or the other variant's.
+fn insert_name<'a>(
+    seen: &mut HashMap<String, (&'a Ident, Option<Span>)>,
+    name: String,
+    variant: &'a Variant,
+    span: Option<Span>,
+) -> Result<()> {
+    match seen.insert(name.clone(), (&variant.ident, span)) {
+        Some((other, other_span)) if *other == variant.ident => Err(Error::new(
+            span.or(other_span).unwrap_or_else(|| variant.ident.span()),
+            format!("conflicting names, two methods of `{}` are called `{}`", variant.ident, name),
+        )),
+        Some((other, other_span)) => Err(Error::new(
+            span.or(other_span).unwrap_or_else(|| variant.ident.span()),
+            format!("conflicting names, `{}` and `{}` both use `{}`", other, variant.ident, name),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Generates `variant_name`.
+pub(crate) fn variant_name(
+    name: &Ident,
+    variants: &[&Variant],
+    placeholder: Option<&Variant>,
+    container: &ContainerAttrs,
+) -> TokenStream {
+    let constness = container.constness();
+    let must_use = container.must_use("this only returns the name of the variant");
+    let arms = variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        let string = variant_str(variant);
+        quote!(#name::#variant_name { .. } => #string,)
+    });
+    let taken = taken_arm(name, placeholder, "variant_name");
+
+    quote! {
+        /// Returns the name of the variant the enum holds.
+        #must_use
+        pub #constness fn variant_name(&self) -> &'static str {
+            match self {
+                #(#arms)*
+                #taken
+            }
+        }
+    }
+}
+
+/// Generates `tag`, returning the string given with `#[variation(tag = "...")]`
+/// or the name of the variant cased like the method stems. Tags are meant for
+/// external systems such as metrics, so they aren't checked for duplicates.
+pub(crate) fn tag(name: &Ident, variants: &[(&Variant, VariantAttrs)], case: Case) -> TokenStream {
+    let arms = variants.iter().map(|(variant, attrs)| {
+        let variant_name = &variant.ident;
+        let tag = match attrs.tag {
+            Some(ref tag) => tag.value(),
+            None => method_stem(variant, case),
+        };
+        quote!(#name::#variant_name { .. } => #tag,)
+    });
+
+    quote! {
+        /// Returns the tag of the variant the enum holds.
+        pub fn tag(&self) -> &'static str {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+}
+
+/// Generates `impl Display for Enum` writing the name of the variant.
+pub(crate) fn impl_display(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                f.write_str(self.variant_name())
+            }
+        }
+    }
+}
+
+/// Generates `impl Debug for Enum` writing `Enum::Variant(..)`, never touching
+/// the inner values so they don't have to implement `Debug`.
+pub(crate) fn impl_debug_redacted(ast: &DeriveInput, variants: &[&Variant]) -> TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let arms = variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        let string = match variant.fields {
+            Fields::Unit => format!("{}::{}", name, ident_str(variant)),
+            Fields::Unnamed(_) => format!("{}::{}(..)", name, ident_str(variant)),
+            Fields::Named(_) => format!("{}::{} {{ .. }}", name, ident_str(variant)),
+        };
+        quote!(#name::#variant_name { .. } => #string,)
+    });
+
+    quote! {
+        impl #impl_generics ::std::fmt::Debug for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                f.write_str(match self {
+                    #(#arms)*
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! `#[derive(Variation)]` on a newtype struct wrapping an enum, forwarding
+//! the accessor methods to it.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::*;
+
+use crate::accessor::{self, Accessor};
+use crate::attr::{self, ContainerAttrs, Families, IntoMode};
+use crate::{inner_ref_type, inner_type, item_attrs, names, variant_attrs};
+
+/// Generates the `is_*`, `as_*`, `as_*_mut` and `into_*` methods of the
+/// variants listed with `#[variation(variants(...))]`, calling those of the
+/// wrapped enum. They're named as they are for the enum, so the options
+/// naming them have to match those of the enum.
+pub(crate) fn impl_newtype(ast: &DeriveInput, data: &DataStruct) -> Result<TokenStream> {
+    let name = &ast.ident;
+
+    match data.fields {
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {}
+        _ => {
+            return Err(Error::new(
+                name.span(),
+                "`#[derive(Variation)]` is only available for enums and newtype structs wrapping one",
+            ))
+        }
+    }
+
+    let container = ContainerAttrs::parse(&ast.attrs)?;
+    let listed = match container.variants {
+        Some((_, ref listed)) => listed,
+        None => {
+            return Err(Error::new(
+                name.span(),
+                "`#[derive(Variation)]` on a newtype struct requires the variants of the enum it wraps, such as \
+                 `#[variation(variants(Integer(i64), Null))]`",
+            ))
+        }
+    };
+
+    attr::check_newtype(ast, listed)?;
+
+    let variants = variant_attrs(name, listed, &container)?;
+    let case = container.rename_all.unwrap_or(names::Case::Snake);
+    names::check_names(&variants, &container, case, false)?;
+
+    let attr::MethodVis { is: is_vis, as_ref: as_vis, as_mut: as_mut_vis, into: into_vis } = &container.method_vis;
+    let bounds = &container.method_bounds;
+    let is_where = attr::method_where_clause(&bounds.is);
+    let as_where = attr::method_where_clause(&bounds.as_ref);
+    let as_mut_where = attr::method_where_clause(&bounds.as_mut);
+    let into_where = attr::method_where_clause(&bounds.into);
+    let Families { is: is_must_use, as_ref: as_must_use, as_mut: as_mut_must_use, into: into_must_use } =
+        container.method_must_use();
+    let Families { is: is_cfg, as_ref: as_cfg, as_mut: as_mut_cfg, into: into_cfg } = container.method_cfg_attrs();
+    let Families { is: is_attrs, as_ref: as_attrs, as_mut: as_mut_attrs, into: into_attrs } =
+        container.method_extra_attrs();
+    let mut accessors = Vec::new();
+
+    for (variant, attrs) in &variants {
+        let skip = &attrs.skip;
+        let inline = attrs.inline(&container);
+        let extra_attrs = attrs.extra_attrs();
+        let doc = attrs.doc();
+        let Families { is: is_fn, as_ref: as_fn, as_mut: as_mut_fn, into: into_fn } =
+            names::method_names(variant, attrs, case, &container.method_prefixes);
+
+        if skip.is.is_none() {
+            accessors.push(Accessor {
+                cfg: is_cfg.clone(),
+                attrs: quote!(#doc #is_must_use),
+                impl_attrs: quote!(#inline #extra_attrs #is_attrs),
+                vis: quote!(#is_vis),
+                signature: quote!(fn #is_fn(&self) -> bool #is_where),
+                body: quote!(self.0.#is_fn()),
+                consuming: false,
+            });
+        }
+
+        if variant.fields.iter().count() == 0 {
+            continue;
+        }
+
+        if skip.as_ref.is_none() {
+            let return_by_ref = inner_ref_type(variant, quote!(&));
+
+            accessors.push(Accessor {
+                cfg: as_cfg.clone(),
+                attrs: quote!(#doc #as_must_use),
+                impl_attrs: quote!(#inline #extra_attrs #as_attrs),
+                vis: quote!(#as_vis),
+                signature: quote!(fn #as_fn(&self) -> Option<#return_by_ref> #as_where),
+                body: quote!(self.0.#as_fn()),
+                consuming: false,
+            });
+        }
+
+        if skip.as_mut.is_none() {
+            let return_by_ref_mut = inner_ref_type(variant, quote!(&mut));
+
+            accessors.push(Accessor {
+                cfg: as_mut_cfg.clone(),
+                attrs: quote!(#doc #as_mut_must_use),
+                impl_attrs: quote!(#inline #extra_attrs #as_mut_attrs),
+                vis: quote!(#as_mut_vis),
+                signature: quote!(fn #as_mut_fn(&mut self) -> Option<#return_by_ref_mut> #as_mut_where),
+                body: quote!(self.0.#as_mut_fn()),
+                consuming: false,
+            });
+        }
+
+        if skip.into.is_some() {
+            continue;
+        }
+
+        let into_mode = attrs.mode_of_into(&container);
+        let into_output = into_mode.output(&inner_type(variant), quote!(Self));
+        // The enum hands itself back for the wrong variant, which goes back
+        // into the wrapper.
+        let (into_body, into_doc) = match into_mode {
+            IntoMode::Panic => (
+                quote!(self.0.#into_fn()),
+                quote! {
+                    /// Consumes the wrapper and returns the inner type of the
+                    /// wrapped enum.
+                    /// # Panics
+                    /// When this method is called on the wrong enum variant.
+                },
+            ),
+            IntoMode::Option => (
+                quote!(self.0.#into_fn()),
+                quote! {
+                    /// Consumes the wrapper and returns the inner type of the
+                    /// wrapped enum, or `None` when this method is called on
+                    /// the wrong enum variant.
+                },
+            ),
+            IntoMode::Result => (
+                quote!(self.0.#into_fn().map_err(#name)),
+                quote! {
+                    /// Consumes the wrapper and returns the inner type of the
+                    /// wrapped enum, or the wrapper itself in `Err` when this
+                    /// method is called on the wrong enum variant.
+                },
+            ),
+        };
+
+        accessors.push(Accessor {
+            cfg: into_cfg.clone(),
+            attrs: quote!(#doc #into_doc #into_must_use),
+            impl_attrs: quote!(#inline #extra_attrs #into_attrs),
+            vis: quote!(#into_vis),
+            signature: quote!(fn #into_fn(self) -> #into_output #into_where),
+            body: into_body,
+            consuming: true,
+        });
+    }
+
+    let methods = accessor::inherent(&accessors);
+    let mut generics = ast.generics.clone();
+    generics.make_where_clause().predicates.extend(container.bounds.iter().cloned());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let gen = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #methods
+        }
+    };
+
+    Ok(item_attrs(gen, &container))
+}
@@ -0,0 +1,169 @@
+//! The `{Enum}OptionExt` trait for `#[variation(option_ext)]`, with the
+//! variant accessors for optional values of the enum.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::attr::{Families, MethodPrefixes, VariantAttrs};
+use crate::names::{method_names, Case};
+use crate::{inner_ref_type, Conversion};
+
+/// Generates the `{Enum}OptionExt` trait, implemented for `Option<Enum>` and
+/// `Option<&Enum>`, with `is_*`, `as_*` and `into_*` methods treating `None`
+/// like a different variant. The trait takes the enum's generic parameters,
+/// so its methods can name the inner types. Families skipped on a variant
+/// are left out of the trait as well.
+pub(crate) fn option_ext(
+    ast: &DeriveInput,
+    variants: &[(&Variant, VariantAttrs)],
+    case: Case,
+    prefixes: &MethodPrefixes,
+) -> TokenStream {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let mut ref_generics = ast.generics.clone();
+    ref_generics.params.insert(0, parse_quote!('variation));
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+    let ext_trait = Ident::new(&format!("{}OptionExt", name), Span::call_site());
+    let doc = format!(
+        "The variant accessors of [`{}`] for `Option<{0}>` and `Option<&{0}>`, which treat `None` like a different variant.",
+        name
+    );
+
+    let mut trait_items = TokenStream::new();
+    let mut owned_items = TokenStream::new();
+    let mut ref_items = TokenStream::new();
+
+    for (variant, attrs) in variants {
+        let variant_name = &variant.ident;
+        let Families { is: is_fn, as_ref: as_fn, into: into_fn, .. } = method_names(variant, attrs, case, prefixes);
+        let field_count = variant.fields.iter().count();
+        let ignoring = if field_count > 0 {
+            quote!(#name::#variant_name(..))
+        } else {
+            quote!(#name::#variant_name)
+        };
+        let is_doc = format!("Returns `true` if the value is `Some` holding the `{}` variant.", variant_name);
+
+        if attrs.skip.is.is_none() {
+            trait_items.extend(quote! {
+                #[doc = #is_doc]
+                fn #is_fn(&self) -> bool;
+            });
+
+            owned_items.extend(quote! {
+                #[allow(unreachable_patterns)]
+                fn #is_fn(&self) -> bool {
+                    match *self {
+                        Some(#ignoring) => true,
+                        _ => false,
+                    }
+                }
+            });
+
+            ref_items.extend(quote! {
+                #[allow(unreachable_patterns)]
+                fn #is_fn(&self) -> bool {
+                    match *self {
+                        Some(&#ignoring) => true,
+                        _ => false,
+                    }
+                }
+            });
+        }
+
+        if field_count == 0 {
+            continue;
+        }
+
+        let Conversion { pattern, ref_pattern, inner, inner_ref, value, .. } = Conversion::new(name, variant);
+        let ref_inner = inner_ref_type(variant, quote!(&'variation));
+        let as_doc = format!(
+            "Borrows the inner values when the value is `Some` holding the `{}` variant.",
+            variant_name
+        );
+        let into_doc = format!(
+            "Converts the value into its inner values when it is `Some` holding the `{}` variant.",
+            variant_name
+        );
+
+        if attrs.skip.as_ref.is_none() {
+            trait_items.extend(quote! {
+                #[doc = #as_doc]
+                fn #as_fn(&self) -> Option<#inner_ref>;
+            });
+
+            owned_items.extend(quote! {
+                #[allow(unreachable_patterns)]
+                fn #as_fn(&self) -> Option<#inner_ref> {
+                    match *self {
+                        Some(#ref_pattern) => Some(#value),
+                        _ => None,
+                    }
+                }
+            });
+
+            ref_items.extend(quote! {
+                #[allow(unreachable_patterns)]
+                fn #as_fn(&self) -> Option<#inner_ref> {
+                    match *self {
+                        Some(&#ref_pattern) => Some(#value),
+                        _ => None,
+                    }
+                }
+            });
+        }
+
+        if attrs.skip.into.is_none() {
+            trait_items.extend(quote! {
+                /// The inner values `into_*` returns, owned for `Option<Enum>`
+                /// and borrowed for `Option<&Enum>`.
+                type #variant_name;
+
+                #[doc = #into_doc]
+                fn #into_fn(self) -> Option<Self::#variant_name>;
+            });
+
+            owned_items.extend(quote! {
+                type #variant_name = #inner;
+
+                #[allow(unreachable_patterns)]
+                fn #into_fn(self) -> Option<#inner> {
+                    match self {
+                        Some(#pattern) => Some(#value),
+                        _ => None,
+                    }
+                }
+            });
+
+            ref_items.extend(quote! {
+                type #variant_name = #ref_inner;
+
+                #[allow(unreachable_patterns)]
+                fn #into_fn(self) -> Option<#ref_inner> {
+                    match self {
+                        Some(&#ref_pattern) => Some(#value),
+                        _ => None,
+                    }
+                }
+            });
+        }
+    }
+
+    quote! {
+        #[doc = #doc]
+        #vis trait #ext_trait #impl_generics #where_clause {
+            #trait_items
+        }
+
+        impl #impl_generics #ext_trait #ty_generics for Option<#name #ty_generics> #where_clause {
+            #owned_items
+        }
+
+        impl #ref_impl_generics #ext_trait #ty_generics for Option<&'variation #name #ty_generics> #where_clause {
+            #ref_items
+        }
+    }
+}
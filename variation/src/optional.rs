@@ -0,0 +1,148 @@
+//! Methods for `Option`-like enums, which mark one variant with
+//! `#[variation(some)]` and a unit variant with `#[variation(none)]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::attr::VariantAttrs;
+use crate::Conversion;
+
+/// The variants marked as `some` and `none`.
+pub(crate) struct OptionVariants<'a> {
+    pub some: &'a Variant,
+    pub none: &'a Variant,
+}
+
+impl<'a> OptionVariants<'a> {
+    /// Finds the marked variants, returning `None` when the enum doesn't use
+    /// the markers.
+    pub(crate) fn find(variants: &[(&'a Variant, VariantAttrs)]) -> Result<Option<Self>> {
+        let some = marked(variants, |attrs| attrs.some, "some")?;
+        let none = marked(variants, |attrs| attrs.none, "none")?;
+
+        match (some, none) {
+            (None, None) => Ok(None),
+            (Some((some, _)), Some((none, _))) => Ok(Some(OptionVariants { some, none })),
+            (Some((_, span)), None) => {
+                Err(Error::new(span, "`#[variation(some)]` requires a variant marked `#[variation(none)]`"))
+            }
+            (None, Some((_, span))) => {
+                Err(Error::new(span, "`#[variation(none)]` requires a variant marked `#[variation(some)]`"))
+            }
+        }
+    }
+}
+
+/// Finds the single variant marked with the attribute selected by `flag`,
+/// which has fields for `some` and none for `none`.
+fn marked<'a>(
+    variants: &[(&'a Variant, VariantAttrs)],
+    flag: impl Fn(&VariantAttrs) -> Option<Span>,
+    attribute: &str,
+) -> Result<Option<(&'a Variant, Span)>> {
+    let mut found: Option<(&Variant, Span)> = None;
+
+    for (variant, attrs) in variants {
+        let span = match flag(attrs) {
+            Some(span) => span,
+            None => continue,
+        };
+
+        if let Some((other, _)) = found {
+            return Err(Error::new(
+                span,
+                format!("`#[variation({})]` is already set on `{}`", attribute, other.ident),
+            ));
+        }
+
+        if attrs.some.is_some() && attrs.none.is_some() {
+            return Err(Error::new(span, "a variant can't be marked both `some` and `none`"));
+        }
+
+        let field_count = variant.fields.iter().count();
+
+        if attribute == "some" && field_count == 0 {
+            return Err(Error::new(
+                span,
+                format!("`#[variation(some)]` requires `{}` to have fields", variant.ident),
+            ));
+        }
+
+        if attribute == "none" && field_count != 0 {
+            return Err(Error::new(
+                span,
+                format!("`#[variation(none)]` requires `{}` to be a unit variant", variant.ident),
+            ));
+        }
+
+        found = Some((*variant, span));
+    }
+
+    Ok(found)
+}
+
+/// Generates `into_option`, `as_option` and `unwrap_or`, which treat any
+/// variant other than `some` like `none`.
+pub(crate) fn methods(name: &Ident, option: &OptionVariants) -> TokenStream {
+    let some_name = &option.some.ident;
+    let Conversion { pattern, ref_pattern, inner, inner_ref, value, .. } = Conversion::new(name, option.some);
+    let into_doc = format!(
+        "Converts the enum into `Some` of the inner type when it holds the `{}` variant, `None` otherwise.",
+        some_name
+    );
+    let as_doc = format!(
+        "Borrows the inner type as `Some` when the enum holds the `{}` variant, returning `None` otherwise.",
+        some_name
+    );
+    let unwrap_or_doc = format!(
+        "Returns the inner type when the enum holds the `{}` variant, `default` otherwise.",
+        some_name
+    );
+
+    quote! {
+        #[doc = #into_doc]
+        pub fn into_option(self) -> ::core::option::Option<#inner> {
+            match self {
+                #pattern => ::core::option::Option::Some(#value),
+                _ => ::core::option::Option::None,
+            }
+        }
+
+        #[doc = #as_doc]
+        pub fn as_option(&self) -> ::core::option::Option<#inner_ref> {
+            match *self {
+                #ref_pattern => ::core::option::Option::Some(#value),
+                _ => ::core::option::Option::None,
+            }
+        }
+
+        #[doc = #unwrap_or_doc]
+        pub fn unwrap_or(self, default: #inner) -> #inner {
+            match self {
+                #pattern => #value,
+                _ => default,
+            }
+        }
+    }
+}
+
+/// Generates `impl From<Option<Some>> for Enum`, converting `None` into the
+/// `none` variant.
+pub(crate) fn impl_from_option(ast: &DeriveInput, option: &OptionVariants) -> TokenStream {
+    let name = &ast.ident;
+    let none_name = &option.none.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let Conversion { pattern, inner, value, .. } = Conversion::new(name, option.some);
+
+    quote! {
+        impl #impl_generics ::core::convert::From<::core::option::Option<#inner>> for #name #ty_generics #where_clause {
+            fn from(option: ::core::option::Option<#inner>) -> Self {
+                match option {
+                    ::core::option::Option::Some(#value) => #pattern,
+                    ::core::option::Option::None => #name::#none_name,
+                }
+            }
+        }
+    }
+}
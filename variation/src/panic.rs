@@ -0,0 +1,113 @@
+//! The helper the panicking `into_*` methods share, keeping the formatting
+//! of the panic message out of the methods themselves, or the literal
+//! message they panic with under `#[variation(static_panic)]`.
+
+use heck::SnakeCase;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::names::variant_str;
+
+fn helper_ident(name: &Ident) -> Ident {
+    Ident::new(&format!("__variation_wrong_variant_{}", name.to_string().to_snake_case()), Span::call_site())
+}
+
+fn variant_name_ident(name: &Ident) -> Ident {
+    Ident::new(&format!("__variation_variant_name_{}", name.to_string().to_snake_case()), Span::call_site())
+}
+
+/// The call panicking in `method` for a value that isn't `variant`, with the
+/// message of `#[variation(panic_msg = "...")]` in place of the default one.
+/// The helper adds the name of the variant the enum holds, which comes from
+/// `variant_name` or, for a remote enum, from the function `helper` generates.
+/// With `static_panic` the message is a literal of `panic!` instead, naming
+/// the enum but not the variant it holds, so nothing is formatted.
+pub(crate) fn wrong_variant(
+    name: &Ident,
+    variant: &Variant,
+    method: &Ident,
+    panic_msg: Option<&LitStr>,
+    remote: bool,
+    static_panic: bool,
+) -> TokenStream {
+    let method = method.to_string();
+    let method = method.trim_start_matches("r#");
+
+    if static_panic {
+        let msg = match panic_msg {
+            Some(msg) => msg.value(),
+            None => format!(
+                "`{}` called on the wrong variant of `{}`, expected `{}`",
+                method,
+                name.to_string().trim_start_matches("r#"),
+                variant_str(variant)
+            ),
+        };
+
+        return quote!(panic!(#msg));
+    }
+
+    let helper = helper_ident(name);
+    let expected = match panic_msg {
+        Some(msg) => msg.value(),
+        None => format!("`{}` called on the wrong variant, expected `{}`", method, variant_str(variant)),
+    };
+    let actual = if remote {
+        let variant_name = variant_name_ident(name);
+        quote!(#variant_name(&self))
+    } else {
+        quote!(self.variant_name())
+    };
+
+    quote!(#helper(#expected, #actual))
+}
+
+/// Generates the helper `wrong_variant` calls, along with the function naming
+/// the variant of a remote enum, which has no `variant_name` method. The
+/// mirror of a remote enum may leave out some of its variants. They can still
+/// go unused, when `#[variation(trait_object_safe)]` leaves out the consuming
+/// methods or the attributes of a variant configure its methods away.
+pub(crate) fn helper(
+    ast: &DeriveInput,
+    path: &TokenStream,
+    variants: &[&Variant],
+    cfg: &TokenStream,
+    remote: bool,
+) -> TokenStream {
+    let name = &ast.ident;
+    let helper = helper_ident(name);
+    let mut gen = quote! {
+        #cfg
+        #[cold]
+        #[inline(never)]
+        #[allow(dead_code)]
+        fn #helper(expected: &'static str, actual: &'static str) -> ! {
+            panic!("{}, found `{}`", expected, actual)
+        }
+    };
+
+    if remote {
+        let variant_name = variant_name_ident(name);
+        let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+        let arms = variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            let string = variant_str(variant);
+            quote!(#path::#variant_ident { .. } => #string,)
+        });
+
+        gen.extend(quote! {
+            #cfg
+            #[cold]
+            #[allow(dead_code, unreachable_patterns)]
+            fn #variant_name #impl_generics(value: &#path #ty_generics) -> &'static str #where_clause {
+                match value {
+                    #(#arms)*
+                    _ => "an unlisted variant",
+                }
+            }
+        });
+    }
+
+    gen
+}
@@ -0,0 +1,85 @@
+//! Splitting a collection of the enum by variant for `#[variation(partition)]`.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::names::Case;
+use crate::{companion_derive, inner_type, stem_ident, Conversion};
+
+fn partition_ident(name: &Ident) -> Ident {
+    Ident::new(&format!("{}Partition", name), Span::call_site())
+}
+
+/// Generates the `{Enum}Partition` struct, with a field for each variant named
+/// after it holding the inner values of that variant, or the number of values
+/// for unit variants, deriving the traits of
+/// `#[variation(companion_derive(...))]` along with `Debug` and `Default`.
+pub(crate) fn partition_struct(ast: &DeriveInput, variants: &[&Variant], case: Case, derives: &[Path]) -> TokenStream {
+    let vis = &ast.vis;
+    let generics = &ast.generics;
+    let where_clause = &ast.generics.where_clause;
+    let partition = partition_ident(&ast.ident);
+    let derive = companion_derive(&["Debug", "Default"], derives);
+    let doc = format!(
+        "The values of [`{0}`] split by their variant, created by [`{0}::partition_variants`].",
+        ast.ident
+    );
+
+    let fields = variants.iter().map(|variant| {
+        let field = stem_ident(variant, case);
+        let ty = if variant.fields.iter().count() == 0 {
+            quote!(usize)
+        } else {
+            let inner = inner_type(variant);
+            quote!(Vec<#inner>)
+        };
+
+        quote!(pub #field: #ty)
+    });
+
+    quote! {
+        #[doc = #doc]
+        #derive
+        #vis struct #partition #generics #where_clause {
+            #(#fields,)*
+        }
+    }
+}
+
+/// Generates `partition_variants`.
+pub(crate) fn methods(ast: &DeriveInput, variants: &[&Variant], case: Case) -> TokenStream {
+    let name = &ast.ident;
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    let partition = partition_ident(name);
+    let mut fields = TokenStream::new();
+    let mut arms = TokenStream::new();
+
+    for variant in variants {
+        let field = stem_ident(variant, case);
+        let Conversion { pattern, value, .. } = Conversion::new(name, variant);
+
+        if variant.fields.iter().count() == 0 {
+            fields.extend(quote!(#field: 0,));
+            arms.extend(quote!(#pattern => partition.#field += 1,));
+        } else {
+            fields.extend(quote!(#field: Vec::new(),));
+            arms.extend(quote!(#pattern => partition.#field.push(#value),));
+        }
+    }
+
+    quote! {
+        /// Splits `values` by their variant, keeping the order they were in.
+        pub fn partition_variants<I: IntoIterator<Item = Self>>(values: I) -> #partition #ty_generics {
+            let mut partition = #partition { #fields };
+
+            for value in values {
+                match value {
+                    #arms
+                }
+            }
+
+            partition
+        }
+    }
+}
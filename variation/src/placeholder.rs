@@ -0,0 +1,141 @@
+//! `#[variation::placeholder]`, adding the hidden variant `take_*` leaves
+//! behind when it moves the inner values out of a borrowed enum.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::attr::VariantAttrs;
+use crate::names::{method_stem, Case};
+use crate::{ident, Conversion};
+
+/// The name of the variant `#[variation::placeholder]` adds.
+const PLACEHOLDER: &str = "__Taken";
+
+/// Adds the hidden placeholder variant to the enum `ast`, marked for
+/// `#[derive(Variation)]` to leave it out.
+pub(crate) fn add_placeholder(args: TokenStream, ast: &mut DeriveInput) -> Result<()> {
+    if !args.is_empty() {
+        return Err(Error::new_spanned(args, "`#[variation::placeholder]` takes no arguments"));
+    }
+
+    // A derive before the attribute has already been expanded, without the
+    // placeholder variant.
+    if !ast.attrs.iter().any(|attr| attr.path.is_ident("derive")) {
+        return Err(Error::new(
+            ast.ident.span(),
+            "`#[variation::placeholder]` has to come before `#[derive(Variation)]`",
+        ));
+    }
+
+    let data = match ast.data {
+        Data::Enum(ref mut data) => data,
+        _ => {
+            return Err(Error::new(
+                ast.ident.span(),
+                "`#[variation::placeholder]` is only available for enums",
+            ))
+        }
+    };
+
+    if let Some(variant) = data.variants.iter().find(|variant| variant.ident == PLACEHOLDER) {
+        return Err(Error::new(
+            variant.ident.span(),
+            format!("`{}` is the name of the variant `#[variation::placeholder]` adds", PLACEHOLDER),
+        ));
+    }
+
+    let placeholder = Ident::new(PLACEHOLDER, Span::call_site());
+    data.variants.push(parse_quote! {
+        #[doc(hidden)]
+        #[variation(placeholder)]
+        #placeholder
+    });
+
+    Ok(())
+}
+
+/// Finds the variant marked `#[variation(placeholder)]`, which has to be a
+/// unit variant and the only one marked.
+pub(crate) fn find<'a>(variants: &[(&'a Variant, VariantAttrs)]) -> Result<Option<&'a Variant>> {
+    let mut found: Option<&Variant> = None;
+
+    for (variant, attrs) in variants {
+        let span = match attrs.placeholder {
+            Some(span) => span,
+            None => continue,
+        };
+
+        if variant.fields.iter().count() != 0 {
+            return Err(Error::new(span, "`#[variation(placeholder)]` requires a variant without fields"));
+        }
+
+        if let Some(other) = found {
+            return Err(Error::new(
+                span,
+                format!("`#[variation(placeholder)]` is already set on `{}`", other.ident),
+            ));
+        }
+
+        found = Some(variant);
+    }
+
+    Ok(found)
+}
+
+/// Generates `take_*` for every variant with fields that has `into_*`,
+/// leaving `placeholder` in place of the variant it moves the inner values
+/// out of.
+pub(crate) fn take_methods(
+    name: &Ident,
+    variants: &[(&Variant, VariantAttrs)],
+    placeholder: &Variant,
+    vis: &TokenStream,
+    case: Case,
+) -> TokenStream {
+    let placeholder = &placeholder.ident;
+    let mut methods = TokenStream::new();
+
+    for (variant, attrs) in variants {
+        if variant.fields.iter().count() == 0 || attrs.skip.into.is_some() {
+            continue;
+        }
+
+        let take_fn = ident(&format!("take_{}", method_stem(variant, case)));
+        let Conversion { pattern, inner, value, .. } = Conversion::new(name, variant);
+        let doc = format!(
+            "Moves the inner values out of a `{}`, leaving a taken value behind that no other \
+             method sees as any variant, or returns `None` and leaves the enum as it is for \
+             other variants.",
+            variant.ident
+        );
+
+        methods.extend(quote! {
+            #[doc = #doc]
+            #vis fn #take_fn(&mut self) -> Option<#inner> {
+                match ::std::mem::replace(self, #name::#placeholder) {
+                    #pattern => Some(#value),
+                    other => {
+                        *self = other;
+                        None
+                    }
+                }
+            }
+        });
+    }
+
+    methods
+}
+
+/// The arm of an exhaustive match on the enum for a taken value, panicking
+/// with the name of `method`.
+pub(crate) fn taken_arm(name: &Ident, placeholder: Option<&Variant>, method: &str) -> TokenStream {
+    match placeholder {
+        Some(placeholder) => {
+            let placeholder = &placeholder.ident;
+            let msg = format!("`{}` called on a value taken by `take_*`", method);
+            quote!(#name::#placeholder => panic!(#msg),)
+        }
+        None => TokenStream::new(),
+    }
+}
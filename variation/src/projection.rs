@@ -0,0 +1,249 @@
+//! Projection enums, copies of the enum with references to the inner values
+//! in place of the values themselves.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::attr::FieldAttrs;
+use crate::delegate::{conditional_unpin, forbid_drop};
+use crate::{companion_derive, reference_type};
+
+/// Generates the `{Enum}Ref` enum and `as_ref`. `Clone` and `Copy` are
+/// implemented by hand, as deriving them would require the enum's type
+/// parameters to implement them too, so they're left out of `derives`.
+pub(crate) fn ref_enum(
+    ast: &DeriveInput,
+    variants: &[&Variant],
+    derives: &[Path],
+) -> Result<(TokenStream, TokenStream)> {
+    let projection = Ident::new(&format!("{}Ref", ast.ident), Span::call_site());
+    let doc = format!(
+        "[`{}`] with shared references to the inner values, returned by `as_ref`.",
+        ast.ident
+    );
+    let generics = projection_generics(ast);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let derives = derives
+        .iter()
+        .filter(|path| !(path.is_ident("Clone") || path.is_ident("Copy")))
+        .cloned()
+        .collect::<Vec<_>>();
+    let (arms, mut projection_enum) = projection_enum(
+        ast,
+        variants,
+        (&projection, &doc, &derives),
+        (quote!(&'variation), quote!(ref)),
+        |_| Ok(false),
+    )?;
+    let method_doc = format!("Converts a reference to the enum into a [`{}`].", projection);
+
+    projection_enum.extend(quote! {
+        impl #impl_generics ::std::clone::Clone for #projection #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #impl_generics ::std::marker::Copy for #projection #ty_generics #where_clause {}
+    });
+
+    let method = quote! {
+        #[doc = #method_doc]
+        pub fn as_ref<'variation>(&'variation self) -> #projection #ty_generics {
+            match *self {
+                #arms
+            }
+        }
+    };
+
+    Ok((method, projection_enum))
+}
+
+/// Generates the `{Enum}Mut` enum and `as_mut`.
+pub(crate) fn mut_enum(
+    ast: &DeriveInput,
+    variants: &[&Variant],
+    derives: &[Path],
+) -> Result<(TokenStream, TokenStream)> {
+    let projection = Ident::new(&format!("{}Mut", ast.ident), Span::call_site());
+    let doc = format!(
+        "[`{}`] with mutable references to the inner values, returned by `as_mut`.",
+        ast.ident
+    );
+    let generics = projection_generics(ast);
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let (arms, projection_enum) = projection_enum(
+        ast,
+        variants,
+        (&projection, &doc, derives),
+        (quote!(&'variation mut), quote!(ref mut)),
+        |_| Ok(false),
+    )?;
+    let method_doc = format!("Converts a mutable reference to the enum into a [`{}`].", projection);
+
+    let method = quote! {
+        #[doc = #method_doc]
+        pub fn as_mut<'variation>(&'variation mut self) -> #projection #ty_generics {
+            match *self {
+                #arms
+            }
+        }
+    };
+
+    Ok((method, projection_enum))
+}
+
+/// Generates the `{Enum}Proj` enum and `project`, which hands out the fields
+/// marked with `#[variation(pin)]` pinned and the others as plain mutable
+/// references.
+///
+/// This is only sound if the marked fields are structurally pinned: nothing
+/// may move them out of a pinned enum. The enum is prevented from
+/// implementing `Drop`, whose `&mut self` could move them, and the rest only
+/// get a `&mut` to the enum through the `Pin` if it's `Unpin`, which it only
+/// is if the marked fields are. `delegate(Future)` already does both when
+/// it's used as well, as it pins every field, so `guarded` skips them.
+pub(crate) fn pin_project(
+    ast: &DeriveInput,
+    variants: &[&Variant],
+    derives: &[Path],
+    guarded: bool,
+) -> Result<(TokenStream, TokenStream)> {
+    let projection = Ident::new(&format!("{}Proj", ast.ident), Span::call_site());
+    let doc = format!(
+        "A pinned [`{}`] with pinned references to its `#[variation(pin)]` inner values and mutable \
+         references to the others, returned by `project`.",
+        ast.ident
+    );
+    let generics = projection_generics(ast);
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let (arms, mut projection_enum) = projection_enum(
+        ast,
+        variants,
+        (&projection, &doc, derives),
+        (quote!(&'variation mut), quote!(ref mut)),
+        |field| Ok(FieldAttrs::parse(&field.attrs)?.pin.is_some()),
+    )?;
+    let method_doc = format!("Projects the pinned enum into a [`{}`].", projection);
+
+    if !guarded {
+        let mut pinned = Vec::new();
+
+        for field in variants.iter().flat_map(|variant| &variant.fields) {
+            if FieldAttrs::parse(&field.attrs)?.pin.is_some() {
+                pinned.push(&field.ty);
+            }
+        }
+
+        projection_enum.extend(forbid_drop(ast));
+        projection_enum.extend(conditional_unpin(ast, &pinned));
+    }
+
+    let method = quote! {
+        #[doc = #method_doc]
+        pub fn project<'variation>(self: ::std::pin::Pin<&'variation mut Self>) -> #projection #ty_generics {
+            // SAFETY: The enum is never moved out of the `Pin`, and the
+            // `#[variation(pin)]` fields are only handed out pinned. They are
+            // structurally pinned, as the enum can't implement `Drop` and
+            // its own `Unpin` impl requires them to be `Unpin`.
+            unsafe {
+                match *::std::pin::Pin::get_unchecked_mut(self) {
+                    #arms
+                }
+            }
+        }
+    };
+
+    Ok((method, projection_enum))
+}
+
+/// The enum's generics with the `'variation` lifetime of the references.
+fn projection_generics(ast: &DeriveInput) -> Generics {
+    let mut generics = ast.generics.clone();
+    generics.params.insert(0, parse_quote!('variation));
+    generics
+}
+
+/// Generates the enum `projection` with the variants of `ast`, whose fields
+/// are `reference` followed by the field's type, wrapped in a `Pin` if
+/// `pinned` returns `true` for the field, deriving the traits of
+/// `#[variation(companion_derive(...))]`. Returns it along with the match
+/// arms converting a place of the enum into it, binding the fields with
+/// `binding`.
+fn projection_enum(
+    ast: &DeriveInput,
+    variants: &[&Variant],
+    (projection, doc, derives): (&Ident, &str, &[Path]),
+    (reference, binding): (TokenStream, TokenStream),
+    pinned: impl Fn(&Field) -> Result<bool>,
+) -> Result<(TokenStream, TokenStream)> {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let generics = projection_generics(ast);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let mut projected_variants = TokenStream::new();
+    let mut arms = TokenStream::new();
+
+    for variant in variants {
+        let variant_name = &variant.ident;
+        let mut fields = TokenStream::new();
+        let mut bindings = TokenStream::new();
+        let mut values = TokenStream::new();
+
+        for (i, field) in variant.fields.iter().enumerate() {
+            let ty = reference_type(&reference, &field.ty);
+            let binding_name = match field.ident {
+                Some(ref field_name) => field_name.clone(),
+                None => Ident::new(&format!("v{}", i), Span::call_site()),
+            };
+            let (ty, value) = if pinned(field)? {
+                (
+                    quote!(::std::pin::Pin<#ty>),
+                    quote!(::std::pin::Pin::new_unchecked(#binding_name)),
+                )
+            } else {
+                (ty, quote!(#binding_name))
+            };
+
+            bindings.extend(quote!(#binding #binding_name,));
+
+            match field.ident {
+                Some(ref field_name) => {
+                    fields.extend(quote!(#field_name: #ty,));
+                    values.extend(quote!(#field_name: #value,));
+                }
+                None => {
+                    fields.extend(quote!(#ty,));
+                    values.extend(quote!(#value,));
+                }
+            }
+        }
+
+        match variant.fields {
+            Fields::Unit => {
+                projected_variants.extend(quote!(#variant_name,));
+                arms.extend(quote!(#name::#variant_name => #projection::#variant_name,));
+            }
+            Fields::Unnamed(_) => {
+                projected_variants.extend(quote!(#variant_name(#fields),));
+                arms.extend(quote!(#name::#variant_name(#bindings) => #projection::#variant_name(#values),));
+            }
+            Fields::Named(_) => {
+                projected_variants.extend(quote!(#variant_name { #fields },));
+                arms.extend(quote!(#name::#variant_name { #bindings } => #projection::#variant_name { #values },));
+            }
+        }
+    }
+
+    let derive = companion_derive(&[], derives);
+    let projection_enum = quote! {
+        #[doc = #doc]
+        #derive
+        #vis enum #projection #impl_generics #where_clause {
+            #projected_variants
+        }
+    };
+
+    Ok((arms, projection_enum))
+}
@@ -0,0 +1,43 @@
+//! The `properties` and `get_property` methods of the variants setting
+//! `#[variation(props(...))]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::*;
+
+use crate::attr::VariantAttrs;
+
+/// Generates `properties`, returning the keys and values a variant sets, and
+/// `get_property` looking one of them up. Nothing is generated unless a
+/// variant sets properties, the others return none.
+pub(crate) fn methods(name: &Ident, variants: &[(&Variant, VariantAttrs)]) -> TokenStream {
+    if variants.iter().all(|(_, attrs)| attrs.props.is_empty()) {
+        return TokenStream::new();
+    }
+
+    let arms = variants.iter().filter(|(_, attrs)| !attrs.props.is_empty()).map(|(variant, attrs)| {
+        let variant_name = &variant.ident;
+        let keys = attrs.props.iter().map(|(key, _)| key.unraw().to_string());
+        let values = attrs.props.iter().map(|(_, value)| value);
+        quote!(#name::#variant_name { .. } => &[#((#keys, #values)),*],)
+    });
+
+    quote! {
+        /// Returns the properties of the variant the enum holds, as pairs of
+        /// keys and values in the order they are given.
+        #[allow(unreachable_patterns)]
+        pub fn properties(&self) -> &'static [(&'static str, &'static str)] {
+            match *self {
+                #(#arms)*
+                _ => &[],
+            }
+        }
+
+        /// Returns the value of the property `key` of the variant the enum
+        /// holds, or `None` if it doesn't set one.
+        pub fn get_property(&self, key: &str) -> ::core::option::Option<&'static str> {
+            self.properties().iter().find(|&&(name, _)| name == key).map(|&(_, value)| value)
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Methods for `Result`-like enums, which mark one variant with
+//! `#[variation(ok)]` and another with `#[variation(err)]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::attr::VariantAttrs;
+use crate::names::{method_stem, Case};
+use crate::Conversion;
+
+/// The variants marked as `ok` and `err`.
+pub(crate) struct ResultVariants<'a> {
+    pub ok: &'a Variant,
+    pub err: &'a Variant,
+    /// Whether `ok` and `err` are the enum's only variants.
+    pub exhaustive: bool,
+}
+
+impl<'a> ResultVariants<'a> {
+    /// Finds the marked variants, returning `None` when the enum doesn't use
+    /// the markers.
+    pub(crate) fn find(variants: &[(&'a Variant, VariantAttrs)]) -> Result<Option<Self>> {
+        let ok = marked(variants, |attrs| attrs.ok, "ok")?;
+        let err = marked(variants, |attrs| attrs.err, "err")?;
+
+        match (ok, err) {
+            (None, None) => Ok(None),
+            (Some((ok, _)), Some((err, _))) => Ok(Some(ResultVariants {
+                ok,
+                err,
+                exhaustive: variants.len() == 2,
+            })),
+            (Some((_, span)), None) => Err(Error::new(span, "`#[variation(ok)]` requires a variant marked `#[variation(err)]`")),
+            (None, Some((_, span))) => Err(Error::new(span, "`#[variation(err)]` requires a variant marked `#[variation(ok)]`")),
+        }
+    }
+}
+
+/// Finds the single variant marked with the attribute selected by `flag`.
+fn marked<'a>(
+    variants: &[(&'a Variant, VariantAttrs)],
+    flag: impl Fn(&VariantAttrs) -> Option<Span>,
+    attribute: &str,
+) -> Result<Option<(&'a Variant, Span)>> {
+    let mut found: Option<(&Variant, Span)> = None;
+
+    for (variant, attrs) in variants {
+        let span = match flag(attrs) {
+            Some(span) => span,
+            None => continue,
+        };
+
+        if let Some((other, _)) = found {
+            return Err(Error::new(
+                span,
+                format!("`#[variation({})]` is already set on `{}`", attribute, other.ident),
+            ));
+        }
+
+        if attrs.ok.is_some() && attrs.err.is_some() {
+            return Err(Error::new(span, "a variant can't be marked both `ok` and `err`"));
+        }
+
+        if variant.fields.iter().count() == 0 {
+            return Err(Error::new(
+                span,
+                format!("`#[variation({})]` requires `{}` to have fields", attribute, variant.ident),
+            ));
+        }
+
+        found = Some((*variant, span));
+    }
+
+    Ok(found)
+}
+
+/// Generates `ok`, `err`, `as_ok`, `as_err`, `map_ok` and `map_err`, along
+/// with `into_result` and `as_result` if the enum has no other variants.
+pub(crate) fn methods(name: &Ident, result: &ResultVariants, case: Case) -> TokenStream {
+    let mut methods = TokenStream::new();
+
+    if result.exhaustive {
+        let Conversion {
+            pattern: ok_pattern,
+            ref_pattern: ok_ref_pattern,
+            inner: ok_inner,
+            inner_ref: ok_inner_ref,
+            value: ok_value,
+            ..
+        } = Conversion::new(name, result.ok);
+        let Conversion {
+            pattern: err_pattern,
+            ref_pattern: err_ref_pattern,
+            inner: err_inner,
+            inner_ref: err_inner_ref,
+            value: err_value,
+            ..
+        } = Conversion::new(name, result.err);
+
+        methods.extend(quote! {
+            /// Converts the enum into the equivalent `Result`.
+            pub fn into_result(self) -> ::core::result::Result<#ok_inner, #err_inner> {
+                match self {
+                    #ok_pattern => ::core::result::Result::Ok(#ok_value),
+                    #err_pattern => ::core::result::Result::Err(#err_value),
+                }
+            }
+
+            /// Borrows the enum's inner types as a `Result`.
+            pub fn as_result(&self) -> ::core::result::Result<#ok_inner_ref, #err_inner_ref> {
+                match self {
+                    #ok_ref_pattern => ::core::result::Result::Ok(#ok_value),
+                    #err_ref_pattern => ::core::result::Result::Err(#err_value),
+                }
+            }
+        });
+    }
+
+    for &(variant, is_ok) in &[(result.ok, true), (result.err, false)] {
+        let (by_value, by_ref, map) = if is_ok {
+            (quote!(ok), quote!(as_ok), quote!(map_ok))
+        } else {
+            (quote!(err), quote!(as_err), quote!(map_err))
+        };
+
+        let variant_name = &variant.ident;
+        let Conversion { pattern, ref_pattern, inner, inner_ref, value, .. } = Conversion::new(name, variant);
+        let by_value_doc = format!(
+            "Converts the enum into `Some` of the inner type when it holds the `{}` variant, discarding it otherwise.",
+            variant_name
+        );
+        let map_doc = format!(
+            "Applies `f` to the inner type when the enum holds the `{}` variant, leaving any other variant untouched.",
+            variant_name
+        );
+
+        // A variant called `Ok` already gets an identical `as_ok` method.
+        let as_method = if method_stem(variant, case) == by_value.to_string() {
+            quote!()
+        } else {
+            quote! {
+                pub fn #by_ref(&self) -> ::core::option::Option<#inner_ref> {
+                    match self {
+                        #ref_pattern => ::core::option::Option::Some(#value),
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+        };
+
+        methods.extend(quote! {
+            #[doc = #by_value_doc]
+            pub fn #by_value(self) -> ::core::option::Option<#inner> {
+                match self {
+                    #pattern => ::core::option::Option::Some(#value),
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            #as_method
+
+            #[doc = #map_doc]
+            pub fn #map<F: FnOnce(#inner) -> #inner>(self, f: F) -> Self {
+                match self {
+                    #pattern => {
+                        let #value = f(#value);
+                        #pattern
+                    }
+                    other => other,
+                }
+            }
+        });
+    }
+
+    methods
+}
+
+/// Generates `impl From<Result<Ok, Err>> for Enum`.
+pub(crate) fn impl_from_result(ast: &DeriveInput, result: &ResultVariants) -> TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let Conversion { pattern: ok_pattern, inner: ok_inner, value: ok_value, .. } = Conversion::new(name, result.ok);
+    let Conversion { pattern: err_pattern, inner: err_inner, value: err_value, .. } = Conversion::new(name, result.err);
+
+    quote! {
+        impl #impl_generics ::core::convert::From<::core::result::Result<#ok_inner, #err_inner>> for #name #ty_generics #where_clause {
+            fn from(result: ::core::result::Result<#ok_inner, #err_inner>) -> Self {
+                match result {
+                    ::core::result::Result::Ok(#ok_value) => #ok_pattern,
+                    ::core::result::Result::Err(#err_value) => #err_pattern,
+                }
+            }
+        }
+    }
+}
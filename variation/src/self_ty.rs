@@ -0,0 +1,67 @@
+//! `Self` in the field types of the enum, which means another type or
+//! nothing at all in the items the fields are copied into, such as the
+//! projection enums, the marker impls or the visitor trait.
+
+use proc_macro2::{Group, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::*;
+
+/// The variants with `Self` in their field types replaced by `path` and the
+/// generics of the enum, which name the enum in and outside of its impls.
+pub(crate) fn replace<'a>(
+    ast: &DeriveInput,
+    path: &TokenStream,
+    variants: impl IntoIterator<Item = &'a Variant>,
+) -> Vec<Variant> {
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    let enum_ty = quote!(#path #ty_generics);
+
+    variants
+        .into_iter()
+        .map(|variant| {
+            let mut variant = variant.clone();
+
+            for field in variant.fields.iter_mut() {
+                let mut found = false;
+                let tokens = replace_tokens(field.ty.to_token_stream(), &enum_ty, &mut found);
+
+                if found {
+                    // Naming a type in place of `Self` keeps the type valid.
+                    field.ty = parse2(tokens).unwrap();
+                }
+            }
+
+            variant
+        })
+        .collect()
+}
+
+fn replace_tokens(tokens: TokenStream, enum_ty: &TokenStream, found: &mut bool) -> TokenStream {
+    let mut tokens = tokens.into_iter().peekable();
+    let mut replaced = TokenStream::new();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ref ident) if ident == "Self" => {
+                *found = true;
+
+                // A path can't go on from generic arguments, as in
+                // `Self::Item`, without being qualified.
+                match tokens.peek() {
+                    Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {
+                        replaced.extend(quote!(<#enum_ty>));
+                    }
+                    _ => replaced.extend(enum_ty.clone()),
+                }
+            }
+            TokenTree::Group(ref group) => {
+                let mut copy = Group::new(group.delimiter(), replace_tokens(group.stream(), enum_ty, found));
+                copy.set_span(group.span());
+                replaced.extend(Some(TokenTree::Group(copy)));
+            }
+            token => replaced.extend(Some(token)),
+        }
+    }
+
+    replaced
+}
@@ -0,0 +1,264 @@
+//! The enums of `#[variation(subset(...))]`, copies of some of the variants
+//! converting to and from the enum.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::*;
+
+use crate::attr::{Item, Value};
+use crate::generate_ident_list_pattern;
+
+/// An enum listed with `#[variation(subset(name = "...", variants(...)))]`.
+pub(crate) struct Subset {
+    pub span: Span,
+    pub name: Ident,
+    pub variants: Vec<Ident>,
+    /// `vis = "..."`, the visibility in place of the enum's.
+    pub vis: Option<Visibility>,
+    /// `derive(...)`, the traits derived for the subset.
+    pub derives: Vec<Path>,
+    /// `error = "..."`, the error of `TryFrom` in place of the enum, built
+    /// with its `From` impl for the enum.
+    pub error: Option<Type>,
+}
+
+impl Subset {
+    pub(crate) fn parse(item: &Item) -> Result<Self> {
+        let options = match item.value {
+            Value::List(ref tokens) => Punctuated::<Item, Token![,]>::parse_terminated.parse2(tokens.clone())?,
+            _ => {
+                return Err(Error::new(
+                    item.span(),
+                    "expected `subset(name = \"...\", variants(Variant, ...))`",
+                ))
+            }
+        };
+        let mut name = None;
+        let mut variants = None;
+        let mut vis = None;
+        let mut derives = None;
+        let mut error = None;
+
+        for option in &options {
+            let duplicate = match &*option.name.to_string() {
+                "name" => name.replace(option.expect_str()?.parse::<Ident>()?).is_some(),
+                "variants" => variants.replace(idents(option)?).is_some(),
+                "vis" => vis.replace(option.expect_str()?.parse::<Visibility>()?).is_some(),
+                "derive" => derives.replace(option.expect_paths()?).is_some(),
+                "error" => error.replace(option.expect_str()?.parse::<Type>()?).is_some(),
+                _ => {
+                    return Err(Error::new(
+                        option.span(),
+                        format!(
+                            "unknown `subset` option `{}`, expected `name`, `variants`, `vis`, `derive` or `error`",
+                            option.name
+                        ),
+                    ))
+                }
+            };
+
+            if duplicate {
+                return Err(Error::new(option.span(), format!("duplicate `{}` attribute", option.name)));
+            }
+        }
+
+        let (name, variants) = match (name, variants) {
+            (Some(name), Some(variants)) => (name, variants),
+            _ => {
+                return Err(Error::new(
+                    item.span(),
+                    "`#[variation(subset(...))]` requires the name and variants of the subset, such as \
+                     `subset(name = \"Scalar\", variants(Integer, Bool))`",
+                ))
+            }
+        };
+
+        Ok(Subset {
+            span: item.span(),
+            name,
+            variants,
+            vis,
+            derives: derives.unwrap_or_default(),
+            error,
+        })
+    }
+}
+
+fn idents(item: &Item) -> Result<Vec<Ident>> {
+    match item.value {
+        Value::List(ref tokens) => {
+            Ok(Punctuated::<Ident, Token![,]>::parse_terminated.parse2(tokens.clone())?.into_iter().collect())
+        }
+        _ => Err(Error::new(item.span(), format!("expected `{}(Variant, ...)`", item.name))),
+    }
+}
+
+/// Generates the subset enums, with `From<Subset> for Enum` and
+/// `TryFrom<Enum> for Subset`.
+pub(crate) fn subsets(ast: &DeriveInput, variants: &[&Variant], subsets: &[Subset]) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let mut gen = TokenStream::new();
+
+    for (i, subset) in subsets.iter().enumerate() {
+        if let Some(other) = subsets[..i].iter().find(|other| other.name == subset.name) {
+            return Err(Error::new(
+                subset.span,
+                format!("duplicate `#[variation(subset(...))]` named `{}`", other.name),
+            ));
+        }
+
+        let mut listed = Vec::new();
+
+        for (j, ident) in subset.variants.iter().enumerate() {
+            if subset.variants[..j].contains(ident) {
+                return Err(Error::new(ident.span(), format!("`{}` is listed twice", ident)));
+            }
+
+            match variants.iter().find(|variant| variant.ident == *ident) {
+                Some(variant) => listed.push(*variant),
+                None => return Err(Error::new(ident.span(), format!("`{}` is not a variant of `{}`", ident, name))),
+            }
+        }
+
+        let subset_name = &subset.name;
+        let vis = subset.vis.as_ref().unwrap_or(&ast.vis);
+        let derives = &subset.derives;
+        let derive = if derives.is_empty() { quote!() } else { quote!(#[derive(#(#derives),*)]) };
+        let generics = used_generics(&ast.generics, &listed);
+        let (_, subset_ty_generics, subset_where_clause) = generics.split_for_impl();
+        let doc = format!("A subset of the variants of [`{}`].", name);
+        let copies = listed.iter().map(|variant| copy(variant));
+        let mut into_arms = TokenStream::new();
+        let mut from_arms = TokenStream::new();
+
+        for variant in &listed {
+            let variant_name = &variant.ident;
+            let fields = generate_ident_list_pattern(variant.fields.iter().count(), false, false);
+
+            into_arms.extend(quote!(#subset_name::#variant_name#fields => #name::#variant_name#fields,));
+            from_arms.extend(quote! {
+                #name::#variant_name#fields => ::std::result::Result::Ok(#subset_name::#variant_name#fields),
+            });
+        }
+
+        let (error, err) = match subset.error {
+            Some(ref error) => (quote!(#error), quote!(::std::convert::From::from(value))),
+            None => (quote!(#name #ty_generics), quote!(value)),
+        };
+
+        gen.extend(quote! {
+            #[doc = #doc]
+            #derive
+            #vis enum #subset_name #generics #subset_where_clause {
+                #(#copies,)*
+            }
+
+            impl #impl_generics ::std::convert::From<#subset_name #subset_ty_generics> for #name #ty_generics
+                #where_clause
+            {
+                fn from(value: #subset_name #subset_ty_generics) -> Self {
+                    match value {
+                        #into_arms
+                    }
+                }
+            }
+
+            impl #impl_generics ::std::convert::TryFrom<#name #ty_generics> for #subset_name #subset_ty_generics
+                #where_clause
+            {
+                type Error = #error;
+
+                #[allow(unreachable_patterns)]
+                fn try_from(value: #name #ty_generics) -> ::std::result::Result<Self, Self::Error> {
+                    match value {
+                        #from_arms
+                        value => ::std::result::Result::Err(#err),
+                    }
+                }
+            }
+        });
+    }
+
+    Ok(gen)
+}
+
+/// The variant without its `#[variation(...)]` attributes and those of its
+/// fields.
+fn copy(variant: &Variant) -> Variant {
+    let mut copy = variant.clone();
+    copy.attrs.retain(|attr| !attr.path.is_ident("variation"));
+
+    for field in copy.fields.iter_mut() {
+        field.attrs.retain(|attr| !attr.path.is_ident("variation"));
+    }
+
+    copy
+}
+
+/// The generic parameters of the enum the fields of `variants` use, and the
+/// where predicates only naming those, as the subset can't have unused ones.
+fn used_generics(generics: &Generics, variants: &[&Variant]) -> Generics {
+    let mut fields = TokenStream::new();
+
+    for variant in variants {
+        for field in &variant.fields {
+            fields.extend(quote!(#field));
+        }
+    }
+
+    let names = |param: &GenericParam| match param {
+        GenericParam::Type(param) => (param.ident.clone(), false),
+        GenericParam::Lifetime(param) => (param.lifetime.ident.clone(), true),
+        GenericParam::Const(param) => (param.ident.clone(), false),
+    };
+    let (used, unused): (Vec<_>, Vec<_>) = generics.params.iter().partition(|param| {
+        let (ident, lifetime) = names(param);
+        mentions(fields.clone(), &ident, lifetime)
+    });
+    let mut subset = generics.clone();
+    subset.params = used.into_iter().cloned().collect();
+
+    if let Some(ref mut where_clause) = subset.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate| {
+                unused.iter().all(|param| {
+                    let (ident, lifetime) = names(param);
+                    !mentions(quote!(#predicate), &ident, lifetime)
+                })
+            })
+            .cloned()
+            .collect();
+    }
+
+    subset
+}
+
+/// Whether `tokens` name the type parameter or, with `lifetime`, the lifetime
+/// `ident`.
+fn mentions(tokens: TokenStream, ident: &Ident, lifetime: bool) -> bool {
+    let mut after_quote = false;
+
+    for token in tokens {
+        let found = match token {
+            TokenTree::Ident(ref other) => other == ident && after_quote == lifetime,
+            TokenTree::Group(ref group) => mentions(group.stream(), ident, lifetime),
+            _ => false,
+        };
+
+        if found {
+            return true;
+        }
+
+        after_quote = match token {
+            TokenTree::Punct(ref punct) => punct.as_char() == '\'',
+            _ => false,
+        };
+    }
+
+    false
+}
@@ -0,0 +1,106 @@
+//! Visitor traits for `#[variation(visitor)]`, with a method for each variant.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::*;
+
+use crate::{field_idents, generate_ident_list_pattern, reference_type};
+use crate::names::{method_stem, Case};
+
+/// Generates the `{Enum}Visitor` trait visiting references to the inner values
+/// and the `{Enum}OwnedVisitor` trait visiting the inner values themselves.
+pub(crate) fn visitor_traits(ast: &DeriveInput, variants: &[&Variant], case: Case) -> TokenStream {
+    let vis = &ast.vis;
+    let (impl_generics, _, where_clause) = ast.generics.split_for_impl();
+    let visitor = visitor_ident(&ast.ident, false);
+    let owned_visitor = visitor_ident(&ast.ident, true);
+    let visit_methods = trait_methods(variants, false, case);
+    let owned_visit_methods = trait_methods(variants, true, case);
+    let doc = format!("A visitor of references to the inner values of [`{}`].", ast.ident);
+    let owned_doc = format!("A visitor consuming the inner values of [`{}`].", ast.ident);
+
+    // The visit methods are written in terms of the field types, which makes
+    // them look like `&String` or `&Box<T>` parameters to clippy.
+    quote! {
+        #[doc = #doc]
+        #[allow(clippy::ptr_arg, clippy::borrowed_box)]
+        #vis trait #visitor #impl_generics #where_clause {
+            type Output;
+
+            #visit_methods
+        }
+
+        #[doc = #owned_doc]
+        #vis trait #owned_visitor #impl_generics #where_clause {
+            type Output;
+
+            #owned_visit_methods
+        }
+    }
+}
+
+/// Generates `accept` and `accept_owned`, dispatching to the visitor traits.
+pub(crate) fn methods(ast: &DeriveInput, variants: &[&Variant], case: Case) -> TokenStream {
+    let name = &ast.ident;
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    let visitor = visitor_ident(name, false);
+    let owned_visitor = visitor_ident(name, true);
+    let mut arms = TokenStream::new();
+    let mut owned_arms = TokenStream::new();
+
+    for variant in variants {
+        let variant_name = &variant.ident;
+        let visit = visit_ident(variant, case);
+        let field_count = variant.fields.iter().count();
+        let value_fields = generate_ident_list_pattern(field_count, false, false);
+        let args = if field_count == 0 { quote!(()) } else { value_fields.clone() };
+
+        arms.extend(quote!(#name::#variant_name#value_fields => visitor.#visit #args,));
+        owned_arms.extend(quote!(#name::#variant_name#value_fields => visitor.#visit #args,));
+    }
+
+    quote! {
+        /// Calls the method of `visitor` for the enum's variant with
+        /// references to the inner values.
+        pub fn accept<V: #visitor #ty_generics>(&self, visitor: &mut V) -> V::Output {
+            match self {
+                #arms
+            }
+        }
+
+        /// Consumes the enum, calling the method of `visitor` for its variant
+        /// with the inner values.
+        pub fn accept_owned<V: #owned_visitor #ty_generics>(self, visitor: &mut V) -> V::Output {
+            match self {
+                #owned_arms
+            }
+        }
+    }
+}
+
+fn visitor_ident(name: &Ident, owned: bool) -> Ident {
+    let suffix = if owned { "OwnedVisitor" } else { "Visitor" };
+    Ident::new(&format!("{}{}", name, suffix), Span::call_site())
+}
+
+fn visit_ident(variant: &Variant, case: Case) -> Ident {
+    let snake_case = method_stem(variant, case);
+    Ident::new(&format!("visit_{}", snake_case), Span::call_site())
+}
+
+fn trait_methods(variants: &[&Variant], owned: bool, case: Case) -> TokenStream {
+    let reference = if owned { quote!() } else { quote!(&) };
+    let mut methods = TokenStream::new();
+
+    for variant in variants {
+        let visit = visit_ident(variant, case);
+        let fields = field_idents(variant.fields.iter().count());
+        let types = variant.fields.iter().map(|field| reference_type(&reference, &field.ty));
+
+        methods.extend(quote! {
+            fn #visit(&mut self #(, #fields: #types)*) -> Self::Output;
+        });
+    }
+
+    methods
+}